@@ -0,0 +1,142 @@
+//! Derive macros for `acton-ern`.
+//!
+//! Enable them through the `derive` feature of `acton-ern` rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Derives `ErnNewtype`, `Display`, `FromStr`, `TryFrom<Ern>`, `AsRef<Ern>`, and `From<Self> for Ern`
+/// for a single-field tuple struct wrapping an `Ern`.
+///
+/// The optional `#[ern(domain = "...", category = "...", account = "...")]` attribute fixes those
+/// components; values that do not match are rejected when parsing or converting. `Serialize` and
+/// `Deserialize` are also generated when `acton-ern` is built with its `serde` feature.
+#[proc_macro_derive(ErnNewtype, attributes(ern))]
+pub fn derive_ern_newtype(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Constraints {
+    domain: Option<LitStr>,
+    category: Option<LitStr>,
+    account: Option<LitStr>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    check_newtype(&input)?;
+    let constraints = parse_constraints(&input)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let domain = option_tokens(&constraints.domain);
+    let category = option_tokens(&constraints.category);
+    let account = option_tokens(&constraints.account);
+
+    Ok(quote! {
+        impl #impl_generics ::acton_ern::ErnNewtype for #name #ty_generics #where_clause {
+            const DOMAIN: ::core::option::Option<&'static str> = #domain;
+            const CATEGORY: ::core::option::Option<&'static str> = #category;
+            const ACCOUNT: ::core::option::Option<&'static str> = #account;
+
+            fn from_ern_unchecked(ern: ::acton_ern::Ern) -> Self {
+                Self(ern)
+            }
+
+            fn as_ern(&self) -> &::acton_ern::Ern {
+                &self.0
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::acton_ern::prelude::ErnError;
+
+            fn from_str(value: &str) -> ::core::result::Result<Self, Self::Err> {
+                <Self as ::acton_ern::ErnNewtype>::parse(value)
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<::acton_ern::Ern> for #name #ty_generics #where_clause {
+            type Error = ::acton_ern::prelude::ErnError;
+
+            fn try_from(ern: ::acton_ern::Ern) -> ::core::result::Result<Self, Self::Error> {
+                <Self as ::acton_ern::ErnNewtype>::try_from_ern(ern)
+            }
+        }
+
+        impl #impl_generics ::core::convert::AsRef<::acton_ern::Ern> for #name #ty_generics #where_clause {
+            fn as_ref(&self) -> &::acton_ern::Ern {
+                &self.0
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for ::acton_ern::Ern #where_clause {
+            fn from(value: #name #ty_generics) -> Self {
+                value.0
+            }
+        }
+
+        ::acton_ern::__ern_newtype_serde!(#name);
+    })
+}
+
+fn check_newtype(input: &DeriveInput) -> syn::Result<()> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(()),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                "ErnNewtype requires a tuple struct with a single `Ern` field",
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            "ErnNewtype can only be derived for structs",
+        )),
+    }
+}
+
+fn parse_constraints(input: &DeriveInput) -> syn::Result<Constraints> {
+    let mut constraints = Constraints::default();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("ern"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let slot = if meta.path.is_ident("domain") {
+                &mut constraints.domain
+            } else if meta.path.is_ident("category") {
+                &mut constraints.category
+            } else if meta.path.is_ident("account") {
+                &mut constraints.account
+            } else {
+                return Err(meta.error("expected `domain`, `category`, or `account`"));
+            };
+            if slot.is_some() {
+                return Err(meta.error("duplicate constraint"));
+            }
+            *slot = Some(meta.value()?.parse()?);
+            Ok(())
+        })?;
+    }
+    Ok(constraints)
+}
+
+fn option_tokens(value: &Option<LitStr>) -> TokenStream2 {
+    match value {
+        Some(value) => quote!(::core::option::Option::Some(#value)),
+        None => quote!(::core::option::Option::None),
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use acton_ern::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn sample() -> Ern {
+    Ern::with_root("orders")
+        .and_then(|ern| ern.with_parts(["eu-west", "tenant-42", "shard-7"]))
+        .expect("sample ERN should be valid")
+}
+
+fn hashing(c: &mut Criterion) {
+    let ern = sample();
+    let mut group = c.benchmark_group("hash");
+    group.bench_function("structural", |b| {
+        b.iter(|| {
+            let mut hasher = DefaultHasher::new();
+            black_box(&ern).hash(&mut hasher);
+            hasher.finish()
+        })
+    });
+    group.bench_function("via_to_string", |b| {
+        b.iter(|| {
+            let mut hasher = DefaultHasher::new();
+            black_box(&ern).to_string().hash(&mut hasher);
+            hasher.finish()
+        })
+    });
+    group.finish();
+}
+
+fn equality(c: &mut Criterion) {
+    let ern = sample();
+    let same = ern.clone();
+    let sibling = ern
+        .with_parts(["eu-west", "tenant-42", "shard-8"])
+        .expect("sibling ERN should be valid");
+    let other = sample();
+    let mut group = c.benchmark_group("eq");
+    group.bench_function("equal", |b| b.iter(|| black_box(&ern) == black_box(&same)));
+    group.bench_function("last_part_differs", |b| {
+        b.iter(|| black_box(&ern) == black_box(&sibling))
+    });
+    group.bench_function("root_differs", |b| {
+        b.iter(|| black_box(&ern) == black_box(&other))
+    });
+    group.finish();
+}
+
+fn parsing(c: &mut Criterion) {
+    let text = sample().to_string();
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("owned", |b| {
+        b.iter(|| ErnParser::new(black_box(&text).to_string()).parse())
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| ErnParser::parse_ref(black_box(&text)).map(|ern| ern.parts().count()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, hashing, equality, parsing);
+criterion_main!(benches);
@@ -0,0 +1,9 @@
+use acton_ern::bench::{register, Corpus};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn workloads(c: &mut Criterion) {
+    register(c, &Corpus::new(1_000));
+}
+
+criterion_group!(benches, workloads);
+criterion_main!(benches);
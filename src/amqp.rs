@@ -0,0 +1,147 @@
+use crate::errors::ErnError;
+use crate::hash::stable_hash64;
+use crate::model::Ern;
+use crate::wire::ErnWire;
+
+/// The longest routing key AMQP 0-9-1 allows, in bytes.
+pub const MAX_ROUTING_KEY_LENGTH: usize = 255;
+
+/// The shortest `max_len` [`Ern::to_routing_key`] accepts: the `ern` token plus the hash token.
+pub const MIN_ROUTING_KEY_LENGTH: usize = 3 + HASH_TOKEN_LENGTH;
+
+/// The length of the `.~<hash>` token that ends a lossy routing key.
+const HASH_TOKEN_LENGTH: usize = 18;
+
+/// Marks the hash token that ends a routing key which cannot be turned back into an ERN (Entity Resource Name).
+const LOSSY_MARKER: char = '~';
+
+impl Ern {
+    /// Derives a dot-delimited AMQP routing key `ern.<domain>.<category>.<account>.<root>.<parts…>`
+    /// of at most `max_len` bytes.
+    ///
+    /// When that form cannot be reversed, because a component contains `.` or the key is longer
+    /// than `max_len`, dots become `_`, the key is truncated, and a final `~<hash>` token of the
+    /// full ERN is appended. The same ERN always yields the same key, and collisions are unlikely:
+    /// two such keys only collide if their truncated prefixes and 64-bit hashes both match.
+    /// [`Ern::from_routing_key`] rejects such keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is less than [`MIN_ROUTING_KEY_LENGTH`], which leaves no room for the
+    /// hash token.
+    pub fn to_routing_key(&self, max_len: usize) -> String {
+        assert!(
+            max_len >= MIN_ROUTING_KEY_LENGTH,
+            "routing keys need at least {MIN_ROUTING_KEY_LENGTH} bytes"
+        );
+        let wire = ErnWire::from(self);
+        let mut key = String::from("ern");
+        let mut lossless = true;
+        for token in wire.segments() {
+            lossless &= !token.contains('.');
+            key.push('.');
+            key.extend(token.chars().map(|c| if c == '.' { '_' } else { c }));
+        }
+        // A last part that looks like a hash token would be misread as one
+        lossless &= !wire
+            .parts
+            .last()
+            .is_some_and(|part| part.starts_with(LOSSY_MARKER));
+
+        if lossless && key.len() <= max_len {
+            return key;
+        }
+        let suffix = format!(
+            ".{}{:016x}",
+            LOSSY_MARKER,
+            stable_hash64(self.to_string().as_bytes())
+        );
+        debug_assert_eq!(suffix.len(), HASH_TOKEN_LENGTH);
+        let mut keep = (max_len - suffix.len()).min(key.len());
+        while !key.is_char_boundary(keep) {
+            keep -= 1;
+        }
+        key.truncate(keep);
+        key.push_str(&suffix);
+        key
+    }
+
+    /// Parses a routing key produced losslessly by [`Ern::to_routing_key`], keeping the root id.
+    pub fn from_routing_key(key: &str) -> Result<Self, ErnError> {
+        let tokens = key
+            .strip_prefix("ern.")
+            .ok_or_else(|| {
+                ErnError::ParseFailure("routing key", "expected the `ern.` prefix".to_string())
+            })?
+            .split('.')
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if tokens
+            .last()
+            .is_some_and(|token| token.starts_with(LOSSY_MARKER))
+        {
+            return Err(ErnError::ParseFailure(
+                "routing key",
+                "the key was truncated or hashed and cannot be reversed".to_string(),
+            ));
+        }
+        Ern::try_from(ErnWire::from_segments("routing key", tokens)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lossless_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["eu", "shard-2"])?;
+        let key = ern.to_routing_key(MAX_ROUTING_KEY_LENGTH);
+        assert!(key.starts_with("ern.acton.reactive.component.orders_"));
+        assert!(key.ends_with(".eu.shard-2"));
+        assert_eq!(Ern::from_routing_key(&key)?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_long_keys_are_truncated_deterministically() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["a".repeat(100), "b".repeat(100)])?;
+        let other = ern.add_part("c")?;
+        let key = ern.to_routing_key(64);
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, ern.to_routing_key(64));
+        assert_ne!(key, other.to_routing_key(64));
+        assert!(Ern::from_routing_key(&key).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_shortest_keys_keep_the_prefix_and_hash() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.add_part("eu")?;
+        let key = ern.to_routing_key(MIN_ROUTING_KEY_LENGTH);
+        assert_eq!(key.len(), MIN_ROUTING_KEY_LENGTH);
+        assert!(key.starts_with("ern.~"));
+        let hash_token = &key[3..];
+        assert!(ern.to_routing_key(40).ends_with(hash_token));
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "routing keys need at least")]
+    fn test_max_len_below_the_minimum_panics() {
+        let ern = Ern::with_root("orders").expect("valid root");
+        ern.to_routing_key(10);
+    }
+
+    #[test]
+    fn test_dotted_components_are_lossy() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.add_part("v1.2")?;
+        let key = ern.to_routing_key(MAX_ROUTING_KEY_LENGTH);
+        assert!(key.contains(".v1_2.~"));
+        assert!(Ern::from_routing_key(&key).is_err());
+
+        let tilde = Ern::with_root("orders")?.add_part("~tmp")?;
+        assert!(Ern::from_routing_key(&tilde.to_routing_key(MAX_ROUTING_KEY_LENGTH)).is_err());
+        Ok(())
+    }
+}
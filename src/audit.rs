@@ -0,0 +1,152 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use crate::model::Ern;
+
+/// A lifecycle change to an ERN (Entity Resource Name).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "event", rename_all = "snake_case"))]
+pub enum AuditEvent {
+    /// A resource was created.
+    Created {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_ern"))]
+        ern: Ern,
+    },
+    /// A resource moved from one ERN (Entity Resource Name) to another.
+    Renamed {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_ern"))]
+        old: Ern,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_ern"))]
+        new: Ern,
+    },
+    /// An additional ERN (Entity Resource Name) now refers to an existing resource.
+    Aliased {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_ern"))]
+        ern: Ern,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_ern"))]
+        alias: Ern,
+    },
+    /// A resource was deleted.
+    Deleted {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_ern"))]
+        ern: Ern,
+    },
+}
+
+impl AuditEvent {
+    /// Returns the event name used in structured output (`created`, `renamed`, `aliased`, `deleted`).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AuditEvent::Created { .. } => "created",
+            AuditEvent::Renamed { .. } => "renamed",
+            AuditEvent::Aliased { .. } => "aliased",
+            AuditEvent::Deleted { .. } => "deleted",
+        }
+    }
+
+    /// Returns the ERN (Entity Resource Name) of the affected resource; for renames this is the new name.
+    pub fn subject(&self) -> &Ern {
+        match self {
+            AuditEvent::Created { ern }
+            | AuditEvent::Aliased { ern, .. }
+            | AuditEvent::Deleted { ern } => ern,
+            AuditEvent::Renamed { new, .. } => new,
+        }
+    }
+}
+
+impl fmt::Display for AuditEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditEvent::Created { ern } | AuditEvent::Deleted { ern } => {
+                write!(f, "{} {}", self.kind(), ern)
+            }
+            AuditEvent::Renamed { old, new } => write!(f, "{} {} -> {}", self.kind(), old, new),
+            AuditEvent::Aliased { ern, alias } => write!(f, "{} {} as {}", self.kind(), ern, alias),
+        }
+    }
+}
+
+/// An [`AuditEvent`] together with when it happened and who caused it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditRecord {
+    /// What happened. Flattened so the event tag and ERNs sit at the top level of the output.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub event: AuditEvent,
+    /// When it happened.
+    pub at: SystemTime,
+    /// The ERN (Entity Resource Name) of the actor responsible, if known.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_ern::option", default))]
+    pub actor: Option<Ern>,
+}
+
+impl AuditRecord {
+    /// Creates a record for `event` stamped with the current time.
+    pub fn new(event: AuditEvent) -> Self {
+        Self {
+            event,
+            at: SystemTime::now(),
+            actor: None,
+        }
+    }
+
+    /// Sets the actor responsible for the event.
+    pub fn by(mut self, actor: Ern) -> Self {
+        self.actor = Some(actor);
+        self
+    }
+}
+
+impl fmt::Display for AuditRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.actor {
+            Some(actor) => write!(f, "{} by {}", self.event, actor),
+            None => write!(f, "{}", self.event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_kind_and_subject() -> anyhow::Result<()> {
+        let old = Ern::with_root("orders")?;
+        let new = old.add_part("archived")?;
+        let event = AuditEvent::Renamed {
+            old: old.clone(),
+            new: new.clone(),
+        };
+        assert_eq!(event.kind(), "renamed");
+        assert_eq!(event.subject(), &new);
+        assert_eq!(event.to_string(), format!("renamed {} -> {}", old, new));
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_display_includes_actor() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?;
+        let actor = Ern::with_root("admin")?;
+        let record = AuditRecord::new(AuditEvent::Deleted { ern: ern.clone() }).by(actor.clone());
+        assert_eq!(record.to_string(), format!("deleted {} by {}", ern, actor));
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_record_serde_shape() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?;
+        let record = AuditRecord::new(AuditEvent::Created { ern: ern.clone() });
+        let value = serde_json::to_value(&record)?;
+        assert_eq!(value["event"], "created");
+        assert_eq!(value["ern"], ern.to_string());
+        assert!(value["actor"].is_null());
+
+        let restored: AuditRecord = serde_json::from_value(value)?;
+        assert_eq!(restored, record);
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use apache_avro::types::Value;
+use apache_avro::Schema;
+
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+use crate::wire::ErnWire;
+
+/// The Avro schema for the string form: the canonical ERN (Entity Resource Name) with an `ern` logical type.
+///
+/// Readers that do not know the logical type treat the value as a plain string.
+pub const ERN_AVRO_STRING_SCHEMA: &str = r#"{"type": "string", "logicalType": "ern"}"#;
+
+/// The Avro schema for the structured form, one field per component.
+pub const ERN_AVRO_RECORD_SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "Ern",
+    "namespace": "acton",
+    "fields": [
+        {"name": "domain", "type": "string"},
+        {"name": "category", "type": "string"},
+        {"name": "account", "type": "string"},
+        {"name": "root", "type": "string"},
+        {"name": "revision", "type": ["null", "long"], "default": null},
+        {"name": "parts", "type": {"type": "array", "items": "string"}}
+    ]
+}"#;
+
+/// Which Avro representation of an ERN (Entity Resource Name) to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AvroForm {
+    /// See [`ERN_AVRO_STRING_SCHEMA`].
+    #[default]
+    String,
+    /// See [`ERN_AVRO_RECORD_SCHEMA`].
+    Record,
+}
+
+impl AvroForm {
+    /// Returns the parsed schema for this form.
+    pub fn schema(&self) -> Schema {
+        let json = match self {
+            AvroForm::String => ERN_AVRO_STRING_SCHEMA,
+            AvroForm::Record => ERN_AVRO_RECORD_SCHEMA,
+        };
+        Schema::parse_str(json).expect("the bundled ERN schemas are valid")
+    }
+}
+
+impl Ern {
+    /// Converts this ERN (Entity Resource Name) to an Avro value matching [`AvroForm::schema`].
+    ///
+    /// Fails with [`ErnError::RevisionOverflow`] if the revision does not fit in an Avro `long`.
+    pub fn to_avro_value(&self, form: AvroForm) -> Result<Value, ErnError> {
+        if form == AvroForm::String {
+            return Ok(Value::String(self.to_string()));
+        }
+        let wire = ErnWire::from(self);
+        let revision = match wire.revision {
+            Some(revision) => {
+                let revision = i64::try_from(revision).map_err(|_| ErnError::RevisionOverflow)?;
+                Value::Union(1, Box::new(Value::Long(revision)))
+            }
+            None => Value::Union(0, Box::new(Value::Null)),
+        };
+        Ok(Value::Record(vec![
+            ("domain".to_string(), Value::String(wire.domain)),
+            ("category".to_string(), Value::String(wire.category)),
+            ("account".to_string(), Value::String(wire.account)),
+            ("root".to_string(), Value::String(wire.root)),
+            ("revision".to_string(), revision),
+            (
+                "parts".to_string(),
+                Value::Array(wire.parts.into_iter().map(Value::String).collect()),
+            ),
+        ]))
+    }
+
+    /// Converts an Avro value in either [`AvroForm`], or a union holding one, validating it as an ERN (Entity Resource Name).
+    pub fn from_avro_value(value: &Value) -> Result<Self, ErnError> {
+        match value {
+            Value::String(value) => ErnParser::new(value.clone()).parse(),
+            Value::Record(fields) => Ern::try_from(record_wire(fields)?),
+            Value::Union(_, value) => Self::from_avro_value(value),
+            _ => Err(ErnError::parse_failure(
+                "avro",
+                "expected a string or an Ern record",
+            )),
+        }
+    }
+}
+
+fn record_wire(fields: &[(String, Value)]) -> Result<ErnWire, ErnError> {
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| ErnError::parse_failure("avro", format!("missing field `{}`", name)))
+    };
+    let text = |name: &str| match field(name)? {
+        Value::String(value) => Ok(value.clone()),
+        _ => Err(ErnError::parse_failure(
+            "avro",
+            format!("field `{}` must be a string", name),
+        )),
+    };
+    let revision = match field("revision")? {
+        Value::Union(_, value) => value.as_ref(),
+        value => value,
+    };
+    let revision = match revision {
+        Value::Null => None,
+        Value::Long(revision) => {
+            Some(u64::try_from(*revision).map_err(|error| ErnError::parse_failure("avro", error))?)
+        }
+        _ => {
+            return Err(ErnError::parse_failure(
+                "avro",
+                "field `revision` must be null or a long",
+            ))
+        }
+    };
+    let parts: Vec<String> = match field("parts")? {
+        Value::Array(parts) => parts
+            .iter()
+            .map(|part| match part {
+                Value::String(part) => Ok(part.clone()),
+                _ => Err(ErnError::parse_failure(
+                    "avro",
+                    "field `parts` must contain strings",
+                )),
+            })
+            .collect::<Result<_, _>>()?,
+        _ => {
+            return Err(ErnError::parse_failure(
+                "avro",
+                "field `parts` must be an array",
+            ))
+        }
+    };
+    let components = [
+        text("domain")?,
+        text("category")?,
+        text("account")?,
+        text("root")?,
+    ];
+    Ok(ErnWire {
+        revision,
+        ..ErnWire::from_segments("avro", components.into_iter().chain(parts))?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use apache_avro::{from_avro_datum, to_avro_datum};
+
+    use super::*;
+
+    #[test]
+    fn test_both_forms_roundtrip_through_datum() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?
+            .with_parts(["a", "b"])?
+            .with_revision(3);
+        for form in [AvroForm::String, AvroForm::Record] {
+            let schema = form.schema();
+            let bytes = to_avro_datum(&schema, ern.to_avro_value(form)?)?;
+            let value = from_avro_datum(&schema, &mut bytes.as_slice(), None)?;
+            assert_eq!(Ern::from_avro_value(&value)?, ern);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_values_are_rejected_at_the_edge() {
+        let bad_part = Value::Record(vec![
+            ("domain".to_string(), Value::String("acton".to_string())),
+            (
+                "category".to_string(),
+                Value::String("reactive".to_string()),
+            ),
+            (
+                "account".to_string(),
+                Value::String("component".to_string()),
+            ),
+            (
+                "root".to_string(),
+                Value::String("root_01h455vb4pex5vsknk084sn02q".to_string()),
+            ),
+            ("revision".to_string(), Value::Null),
+            (
+                "parts".to_string(),
+                Value::Array(vec![Value::String("a/b".to_string())]),
+            ),
+        ]);
+        assert_eq!(
+            Ern::from_avro_value(&bad_part),
+            Err(ErnError::InvalidPartFormat)
+        );
+        assert!(matches!(
+            Ern::from_avro_value(&Value::Long(1)),
+            Err(ErnError::ParseFailure("avro", _))
+        ));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+use crate::url::percent_decode;
+
+/// The W3C Baggage key carrying the ERN (Entity Resource Name) of the resource that owns a trace.
+pub const BAGGAGE_KEY: &str = "acton.ern";
+
+/// The property marking a baggage entry whose ERN lost trailing parts to fit the size limit.
+pub const BAGGAGE_TRUNCATED_PROPERTY: &str = "truncated";
+
+/// The largest `baggage` header the W3C Baggage specification requires propagators to accept.
+pub const MAX_BAGGAGE_LENGTH: usize = 8192;
+
+/// An ERN (Entity Resource Name) read from a `baggage` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaggageErn {
+    pub ern: Ern,
+    /// Whether the sender dropped trailing parts, so `ern` is an ancestor of the original.
+    pub truncated: bool,
+}
+
+impl Ern {
+    /// Encodes the ERN (Entity Resource Name) as a `baggage` list member, e.g. `acton.ern=ern%3Aacton%3A…`.
+    ///
+    /// The value is percent-encoded UTF-8. If the entry is longer than `max_len` bytes, trailing
+    /// parts are dropped one at a time until it fits and the entry gets the
+    /// [`BAGGAGE_TRUNCATED_PROPERTY`] property, so the receiver still sees the nearest ancestor
+    /// and knows it is one. Fails with [`ErnError::LimitExceeded`] if even the ERN without parts
+    /// does not fit.
+    pub fn to_baggage_entry(&self, max_len: usize) -> Result<String, ErnError> {
+        let mut entry = format!("{BAGGAGE_KEY}={}", percent_encode(&self.to_string()));
+        let mut ancestor = self.clone();
+        while entry.len() > max_len {
+            ancestor = match ancestor.parent() {
+                Some(parent) => parent,
+                None => {
+                    return Err(ErnError::LimitExceeded(
+                        "baggage entry",
+                        max_len,
+                        entry.len(),
+                    ))
+                }
+            };
+            entry = format!(
+                "{BAGGAGE_KEY}={};{BAGGAGE_TRUNCATED_PROPERTY}",
+                percent_encode(&ancestor.to_string())
+            );
+        }
+        Ok(entry)
+    }
+
+    /// Finds and decodes the [`BAGGAGE_KEY`] entry in a `baggage` header, returning `None` if there is none.
+    pub fn from_baggage(header: &str) -> Result<Option<BaggageErn>, ErnError> {
+        for member in header.split(',') {
+            let mut properties = member
+                .split(';')
+                .map(|property| property.trim_matches([' ', '\t']));
+            let Some((key, value)) = properties.next().and_then(|pair| pair.split_once('=')) else {
+                continue;
+            };
+            if key.trim_end_matches([' ', '\t']) != BAGGAGE_KEY {
+                continue;
+            }
+            let text = percent_decode(value.trim_start_matches([' ', '\t'])).ok_or_else(|| {
+                ErnError::parse_failure(
+                    "baggage",
+                    format!("`{value}` is not valid percent-encoded UTF-8"),
+                )
+            })?;
+            let truncated = properties.any(|property| property == BAGGAGE_TRUNCATED_PROPERTY);
+            return Ok(Some(BaggageErn {
+                ern: ErnParser::new(text).parse()?,
+                truncated,
+            }));
+        }
+        Ok(None)
+    }
+}
+
+/// Escapes everything outside the baggage value alphabet, plus `%` itself.
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_graphic() && !matches!(byte, b'"' | b',' | b';' | b'\\' | b'%') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_baggage_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["zürich", "a;b"])?;
+        let entry = ern.to_baggage_entry(MAX_BAGGAGE_LENGTH)?;
+        assert!(entry.starts_with("acton.ern=ern:acton:reactive:component:orders_"));
+        assert!(entry.ends_with("/z%C3%BCrich/a%3Bb"));
+        let header = format!("userId=alice, {entry} ,isProduction=false");
+        let decoded = Ern::from_baggage(&header)?.expect("entry present");
+        assert_eq!(
+            decoded,
+            BaggageErn {
+                ern,
+                truncated: false
+            }
+        );
+        assert_eq!(Ern::from_baggage("userId=alice")?, None);
+        assert!(Ern::from_baggage("acton.ern=ern%+1").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncation_keeps_the_nearest_ancestor() -> anyhow::Result<()> {
+        let ern =
+            Ern::with_root("orders")?.with_parts(["region", "shard", "replica-of-the-shard"])?;
+        let full = ern.to_baggage_entry(MAX_BAGGAGE_LENGTH)?;
+        let entry = ern.to_baggage_entry(full.len() - 1)?;
+        assert!(entry.ends_with("/region/shard;truncated"));
+        let decoded = Ern::from_baggage(&entry)?.expect("entry present");
+        assert!(decoded.truncated);
+        assert!(ern.is_child_of(&decoded.ern));
+        assert!(ern
+            .to_baggage_entry(entry.len() - 1)?
+            .ends_with("/region;truncated"));
+        assert!(matches!(
+            ern.to_baggage_entry(20),
+            Err(ErnError::LimitExceeded("baggage entry", 20, _))
+        ));
+        Ok(())
+    }
+}
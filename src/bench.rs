@@ -0,0 +1,189 @@
+//! Representative workloads for measuring ERN (Entity Resource Name) performance the same way everywhere.
+//!
+//! Enabled with the `bench` feature. A [`Corpus`] is a deterministic mix of ERN shapes; each
+//! [`Workload`] runs one hot path over it and returns a checksum so the work cannot be optimized
+//! away. [`register`] adds every workload to a Criterion benchmark, and [`Workload::run`] can be
+//! timed by any other harness.
+
+use criterion::{black_box, BenchmarkId, Criterion, Throughput};
+
+use crate::builder::ErnBuilder;
+use crate::errors::ErnError;
+use crate::model::{Account, Category, Domain, EntityRoot, Ern, Part};
+use crate::parser::ErnParser;
+use crate::MAX_ROUTING_KEY_LENGTH;
+
+const DOMAINS: [&str; 3] = ["acton", "billing", "inventory"];
+const CATEGORIES: [&str; 3] = ["reactive", "orders", "ledger"];
+const ACCOUNTS: [&str; 4] = ["tenant-1", "tenant-2", "tenant-3", "tenant-4"];
+
+/// The pattern the [`Workload::Match`] workload tests every ERN against.
+pub const MATCH_PATTERN: &str = "ern:*:orders:*:*/region-1/**";
+
+/// A deterministic set of ERNs and their displayed text, shared by every workload.
+#[derive(Debug, Clone)]
+pub struct Corpus {
+    erns: Vec<Ern>,
+    texts: Vec<String>,
+}
+
+impl Corpus {
+    /// Builds `size` ERNs cycling through several domains, categories, and accounts, with zero to
+    /// four parts each.
+    pub fn new(size: usize) -> Self {
+        let erns = (0..size)
+            .map(|i| {
+                let parts = (0..i % 5).map(|depth| match depth {
+                    0 => format!("region-{}", i % 3),
+                    1 => format!("service-{}", i % 7),
+                    _ => format!("shard-{}", (i + depth) % 10),
+                });
+                Ern::new(
+                    Domain::from_static(DOMAINS[i % DOMAINS.len()]),
+                    Category::from_static(CATEGORIES[i % CATEGORIES.len()]),
+                    Account::from_static(ACCOUNTS[i % ACCOUNTS.len()]),
+                    EntityRoot::new(format!("entity{}", i % 11)).expect("corpus roots are valid"),
+                    parts
+                        .map(|part| Part::new(part).expect("corpus parts are valid"))
+                        .collect(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let texts = erns.iter().map(Ern::to_string).collect();
+        Self { erns, texts }
+    }
+
+    /// Returns the number of ERNs in the corpus.
+    pub fn len(&self) -> usize {
+        self.erns.len()
+    }
+
+    /// Returns true if the corpus is empty.
+    pub fn is_empty(&self) -> bool {
+        self.erns.is_empty()
+    }
+
+    pub fn erns(&self) -> &[Ern] {
+        &self.erns
+    }
+
+    /// Returns the displayed form of every ERN, in the same order as [`Corpus::erns`].
+    pub fn texts(&self) -> &[String] {
+        &self.texts
+    }
+}
+
+/// One hot path to measure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Workload {
+    /// Parses every ERN string with [`ErnParser`].
+    Parse,
+    /// Rebuilds every ERN through the type-state [`ErnBuilder`].
+    Build,
+    /// Tests every ERN against [`MATCH_PATTERN`].
+    Match,
+    /// Derives an AMQP routing key for every ERN.
+    Route,
+}
+
+impl Workload {
+    /// Every workload, in a stable order.
+    pub const ALL: [Workload; 4] = [
+        Workload::Parse,
+        Workload::Build,
+        Workload::Match,
+        Workload::Route,
+    ];
+
+    /// Returns the benchmark name of the workload.
+    pub fn name(self) -> &'static str {
+        match self {
+            Workload::Parse => "parse",
+            Workload::Build => "build",
+            Workload::Match => "match",
+            Workload::Route => "route",
+        }
+    }
+
+    /// Runs the workload once over `corpus` and returns a checksum of the results.
+    pub fn run(self, corpus: &Corpus) -> usize {
+        match self {
+            Workload::Parse => corpus
+                .texts
+                .iter()
+                .filter_map(|text| ErnParser::new(text.clone()).parse().ok())
+                .map(|ern| ern.parts.len())
+                .sum(),
+            Workload::Build => corpus
+                .erns
+                .iter()
+                .filter_map(|ern| rebuild(ern).ok())
+                .map(|ern| ern.parts.len())
+                .sum(),
+            Workload::Match => corpus
+                .erns
+                .iter()
+                .filter(|ern| ern.matches_glob(MATCH_PATTERN))
+                .count(),
+            Workload::Route => corpus
+                .erns
+                .iter()
+                .map(|ern| ern.to_routing_key(MAX_ROUTING_KEY_LENGTH).len())
+                .sum(),
+        }
+    }
+}
+
+fn rebuild(ern: &Ern) -> Result<Ern, ErnError> {
+    let builder = ErnBuilder::new()
+        .with::<Domain>(ern.domain.as_str().to_string())?
+        .with::<Category>(ern.category.as_str().to_string())?
+        .with::<Account>(ern.account.as_str().to_string())?
+        .with::<EntityRoot>(ern.root.base_name().to_string())?;
+    let mut parts = (&ern.parts).into_iter();
+    match parts.next() {
+        None => builder.build(),
+        Some(first) => {
+            let mut builder = builder.with::<Part>(first.as_str().to_string())?;
+            for part in parts {
+                builder = builder.with::<Part>(part.as_str().to_string())?;
+            }
+            builder.build()
+        }
+    }
+}
+
+/// Registers every [`Workload`] over `corpus` as a Criterion benchmark group named `workloads`.
+pub fn register(c: &mut Criterion, corpus: &Corpus) {
+    let mut group = c.benchmark_group("workloads");
+    group.throughput(Throughput::Elements(corpus.len() as u64));
+    for workload in Workload::ALL {
+        group.bench_with_input(
+            BenchmarkId::new(workload.name(), corpus.len()),
+            corpus,
+            |b, corpus| b.iter(|| workload.run(black_box(corpus))),
+        );
+    }
+    group.finish();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workloads_are_deterministic() {
+        let corpus = Corpus::new(100);
+        assert_eq!(corpus.len(), 100);
+        for workload in Workload::ALL {
+            assert_eq!(
+                workload.run(&corpus),
+                workload.run(&Corpus::new(100)),
+                "{}",
+                workload.name()
+            );
+        }
+        assert_eq!(Workload::Parse.run(&corpus), Workload::Build.run(&corpus));
+        assert!(Workload::Match.run(&corpus) > 0);
+    }
+}
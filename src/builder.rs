@@ -1,10 +1,12 @@
+use std::borrow::Cow;
 use std::hash::Hash;
 use std::str::FromStr;
 
-use crate::EntityRoot;
 use crate::errors::ErnError;
 use crate::model::{Account, Category, Domain, Ern, Part, Parts};
+use crate::pool::{self, BuilderPoolStats};
 use crate::traits::ErnComponent;
+use crate::EntityRoot;
 
 /// A builder for constructing ERN (Entity Resource Name) instances using a state-driven approach with type safety.
 pub struct ErnBuilder<State> {
@@ -21,6 +23,70 @@ impl ErnBuilder<()> {
             _marker: std::marker::PhantomData,
         }
     }
+
+    /// Creates a builder whose part storage comes from a thread-local pool of reusable buffers.
+    ///
+    /// Intended for services that build and drop many ERNs (Entity Resource Names) per second.
+    /// A builder dropped without building returns its buffer automatically; pass finished ERNs
+    /// that are no longer needed to [`ErnBuilder::recycle`] to return theirs.
+    pub fn pooled() -> ErnBuilder<Domain> {
+        ErnBuilder {
+            builder: PrivateErnBuilder {
+                domain: None,
+                category: None,
+                account: None,
+                root: None,
+                parts: Parts(pool::take_buffer()),
+                revision: None,
+                pooled: true,
+            },
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Returns the part buffer of an ERN (Entity Resource Name) to the current thread's pool.
+    pub fn recycle(ern: Ern) {
+        pool::recycle(ern);
+    }
+
+    /// Returns the current thread's pool counters, including the hit rate.
+    pub fn pool_stats() -> BuilderPoolStats {
+        pool::stats()
+    }
+}
+
+/// Implementation of `ErnBuilder` for deriving children from an existing ERN (Entity Resource Name).
+impl ErnBuilder<()> {
+    /// Creates a builder pre-filled with `parent`'s domain, category, account, root, revision, and parts.
+    ///
+    /// The builder cannot be finished until at least one further part is added, so the result is
+    /// always a strict descendant of `parent`. The revision is kept, as with [`Ern::parent`] and
+    /// [`ErnBatchBuilder::from_parent`], so moving down and back up the hierarchy returns `parent`.
+    pub fn child_of(parent: &Ern) -> ErnBuilder<ChildPart> {
+        ErnBuilder {
+            builder: PrivateErnBuilder {
+                domain: Some(parent.domain.clone()),
+                category: Some(parent.category.clone()),
+                account: Some(parent.account.clone()),
+                root: Some(parent.root.clone()),
+                parts: parent.parts.clone(),
+                revision: parent.revision,
+                pooled: false,
+            },
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+/// The builder state returned by [`ErnBuilder::child_of`], which requires a `Part` before building.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChildPart;
+
+impl ErnComponent for ChildPart {
+    fn prefix() -> &'static str {
+        ""
+    }
+    type NextState = Parts;
 }
 
 /// Implementation of `ErnBuilder` for `Part` states, allowing for building the final ERN (Entity Resource Name).
@@ -42,15 +108,17 @@ impl ErnBuilder<Parts> {
 /// Generic implementation of `ErnBuilder` for all states that can transition to another state.
 impl<Component: ErnComponent + Hash + Clone + PartialEq + Eq> ErnBuilder<Component> {
     /// Adds a new part to the ERN (Entity Resource Name), transitioning to the next appropriate state.
+    ///
+    /// Accepts `&'static str` and owned `String` values alike; neither is copied on the way in.
     pub fn with<N>(
         self,
-        part: String,
+        part: impl Into<Cow<'static, str>>,
     ) -> Result<ErnBuilder<N::NextState>, ErnError>
     where
         N: ErnComponent<NextState=Component::NextState> + Hash,
     {
         Ok(ErnBuilder {
-            builder: self.builder.add_part(N::prefix(), part)?,
+            builder: self.builder.add_part(N::prefix(), part.into())?,
             _marker: std::marker::PhantomData,
         })
     }
@@ -63,6 +131,18 @@ struct PrivateErnBuilder {
     account: Option<Account>,
     root: Option<EntityRoot>,
     parts: Parts,
+    /// The revision of the parent a child builder started from.
+    revision: Option<u64>,
+    /// Whether `parts` came from the builder pool and should go back if the build is abandoned.
+    pooled: bool,
+}
+
+impl Drop for PrivateErnBuilder {
+    fn drop(&mut self) {
+        if self.pooled {
+            pool::return_buffer(std::mem::take(&mut self.parts.0));
+        }
+    }
 }
 
 impl PrivateErnBuilder {
@@ -74,28 +154,30 @@ impl PrivateErnBuilder {
             account: None,
             root: None,
             parts: Parts::new(Vec::new()),
+            revision: None,
+            pooled: false,
         }
     }
 
-    fn add_part(mut self, prefix: &'static str, part: String) -> Result<Self, ErnError> {
+    fn add_part(mut self, prefix: &'static str, part: Cow<'static, str>) -> Result<Self, ErnError> {
         match prefix {
             p if p == Domain::prefix() => {
-                self.domain = Some(Domain::new(part)?);
+                self.domain = Some(Domain::from_cow(part)?);
             }
             "" => {
                 if self.domain.is_some() && self.category.is_none() {
-                    self.category = Some(Category::new(part));
+                    self.category = Some(Category::from_cow(part));
                 } else if self.category.is_some() && self.account.is_none() {
-                    self.account = Some(Account::new(part));
+                    self.account = Some(Account::from_cow(part));
                 } else if self.account.is_some() && self.root.is_none() {
-                    self.root = Some(EntityRoot::from_str(part.as_str()).unwrap());
+                    self.root = Some(EntityRoot::from_str(&part)?);
                 } else {
                     // add the first part
-                    self.parts = self.parts.add_part(Part::new(part)?);
+                    self.parts.0.push(Part::from_cow(part)?);
                 }
             }
             ":" => {
-                self.parts = self.parts.add_part(Part::new(part)?);
+                self.parts.0.push(Part::from_cow(part)?);
             }
             _ => return Err(ErnError::InvalidPrefix(prefix.to_string())),
         }
@@ -103,19 +185,218 @@ impl PrivateErnBuilder {
     }
 
     /// Finalizes and builds the ERN (Entity Resource Name).
-    fn build(self) -> Result<Ern, ErnError> {
+    fn build(mut self) -> Result<Ern, ErnError> {
         let domain = self
             .domain
+            .take()
             .ok_or(ErnError::MissingPart("domain".to_string()))?;
         let category = self
             .category
+            .take()
             .ok_or(ErnError::MissingPart("category".to_string()))?;
         let account = self
             .account
+            .take()
             .ok_or(ErnError::MissingPart("account".to_string()))?;
-        let root = self.root.ok_or(ErnError::MissingPart("root".to_string()))?;
+        let root = self
+            .root
+            .take()
+            .ok_or(ErnError::MissingPart("root".to_string()))?;
+
+        let ern = Ern::new(
+            domain,
+            category,
+            account,
+            root,
+            std::mem::take(&mut self.parts),
+        );
+        Ok(match self.revision {
+            Some(revision) => ern.with_revision(revision),
+            None => ern,
+        })
+    }
+}
+
+
+/// A builder that fixes the domain, category, account, and root once and then produces many sibling ERNs (Entity Resource Names).
+///
+/// The shared prefix is validated a single time when the batch builder is created, so emitting
+/// each child only validates and allocates its own parts.
+#[derive(Debug, Clone)]
+pub struct ErnBatchBuilder {
+    prefix: Ern,
+}
+
+impl ErnBatchBuilder {
+    /// Creates a batch builder for children of the given root.
+    pub fn new(domain: Domain, category: Category, account: Account, root: EntityRoot) -> Self {
+        Self {
+            prefix: Ern::new(domain, category, account, root, Parts::default()),
+        }
+    }
+
+    /// Creates a batch builder whose children are nested under `parent`, including its parts.
+    pub fn from_parent(parent: &Ern) -> Self {
+        Self {
+            prefix: parent.clone(),
+        }
+    }
+
+    /// Returns the shared prefix every child is built on.
+    pub fn prefix(&self) -> &Ern {
+        &self.prefix
+    }
+
+    /// Builds one child by appending `parts` to the shared prefix.
+    pub fn child<I>(&self, parts: I) -> Result<Ern, ErnError>
+    where
+        I: IntoIterator,
+        I::Item: Into<Cow<'static, str>>,
+    {
+        let parts = parts.into_iter();
+        let mut child_parts = Vec::with_capacity(self.prefix.parts.len() + parts.size_hint().0);
+        child_parts.extend(self.prefix.parts.0.iter().cloned());
+        for part in parts {
+            child_parts.push(Part::from_cow(part)?);
+        }
+        Ok(Ern {
+            domain: self.prefix.domain.clone(),
+            category: self.prefix.category.clone(),
+            account: self.prefix.account.clone(),
+            root: self.prefix.root.clone(),
+            parts: Parts(child_parts),
+            revision: self.prefix.revision,
+        })
+    }
 
-        Ok(Ern::new(domain, category, account, root, self.parts))
+    /// Builds a child for every part list yielded by `batch`; a failure in one child does not stop the rest.
+    pub fn build_all<'a, B, I>(
+        &'a self,
+        batch: B,
+    ) -> impl Iterator<Item = Result<Ern, ErnError>> + 'a
+    where
+        B: IntoIterator<Item = I> + 'a,
+        I: IntoIterator,
+        I::Item: Into<Cow<'static, str>>,
+    {
+        batch.into_iter().map(move |parts| self.child(parts))
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch() -> anyhow::Result<ErnBatchBuilder> {
+        Ok(ErnBatchBuilder::new(
+            Domain::new("acton")?,
+            Category::new("hr"),
+            Account::new("company123"),
+            EntityRoot::new("root".to_string())?,
+        ))
+    }
+
+    #[test]
+    fn test_child_of_is_strict_descendant() -> anyhow::Result<()> {
+        let parent = Ern::with_root("root")?.add_part("departmentA")?;
+        let child = ErnBuilder::child_of(&parent)
+            .with::<Part>("team1")?
+            .with::<Part>("lead")?
+            .build()?;
+        assert!(child.is_child_of(&parent));
+        assert_eq!(child.parts.to_string(), "departmentA/team1/lead");
+        Ok(())
+    }
+
+    #[test]
+    fn test_child_of_keeps_the_revision() -> anyhow::Result<()> {
+        let parent = Ern::with_root("root")?
+            .add_part("departmentA")?
+            .with_revision(4);
+        let child = ErnBuilder::child_of(&parent)
+            .with::<Part>("team1")?
+            .build()?;
+        assert_eq!(child.revision(), Some(4));
+        assert_eq!(child.parent(), Some(parent.clone()));
+        assert_eq!(child.ancestors().next(), Some(parent));
+        Ok(())
+    }
+
+    #[test]
+    fn test_child_of_validates_parts() -> anyhow::Result<()> {
+        let parent = Ern::with_root("root")?;
+        let result = ErnBuilder::child_of(&parent).with::<Part>("bad/part");
+        assert!(matches!(result, Err(ErnError::InvalidPartFormat)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pooled_builder_reuses_buffers() -> anyhow::Result<()> {
+        let build = || {
+            ErnBuilder::pooled()
+                .with::<Domain>("acton")?
+                .with::<Category>("hr")?
+                .with::<Account>("company123")?
+                .with::<EntityRoot>("root")?
+                .with::<Part>("team1")?
+                .build()
+        };
+        let before = ErnBuilder::pool_stats();
+        let first = build()?;
+        assert_eq!(first.parts.to_string(), "team1");
+        ErnBuilder::recycle(first);
+        let second = build()?;
+
+        let after = ErnBuilder::pool_stats();
+        assert_eq!(after.misses - before.misses, 1);
+        assert_eq!(after.hits - before.hits, 1);
+        assert!(second.parts.0.capacity() >= 8);
+
+        // An abandoned build hands its buffer back too
+        assert!(ErnBuilder::pooled().with::<Domain>("").is_err());
+        assert_eq!(ErnBuilder::pool_stats().returned - after.returned, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pool_hit_rate() {
+        let stats = BuilderPoolStats {
+            hits: 3,
+            misses: 1,
+            ..Default::default()
+        };
+        assert_eq!(stats.hit_rate(), 0.75);
+        assert_eq!(BuilderPoolStats::default().hit_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_batch_children_share_prefix() -> anyhow::Result<()> {
+        let batch = batch()?;
+        let first = batch.child(["team1"])?;
+        let second = batch.child(vec!["team2".to_string(), "lead".to_string()])?;
+        assert_eq!(first.root, second.root);
+        assert_eq!(first.parts.to_string(), "team1");
+        assert_eq!(second.parts.to_string(), "team2/lead");
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_from_parent_nests_children() -> anyhow::Result<()> {
+        let parent = Ern::with_root("root")?.add_part("departmentA")?;
+        let child = ErnBatchBuilder::from_parent(&parent).child(["team1"])?;
+        assert!(child.is_child_of(&parent));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_all_reports_each_result() -> anyhow::Result<()> {
+        let batch = batch()?;
+        let results: Vec<_> = batch
+            .build_all(vec![vec!["a"], vec!["bad/part"], vec!["c"]])
+            .collect();
+        assert!(results[0].is_ok());
+        assert_eq!(results[1], Err(ErnError::InvalidPartFormat));
+        assert!(results[2].is_ok());
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+use crate::errors::ErnError;
+use crate::model::Ern;
+
+/// A HyperLogLog sketch estimating the number of distinct ERNs (Entity Resource Names) observed.
+///
+/// Memory use is fixed at `2^precision` bytes regardless of how many ERNs are inserted, and
+/// sketches built with the same precision can be merged, which makes the type suitable for
+/// streaming and sharded counting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErnCardinality {
+    precision: u8,
+    registers: Vec<u8>,
+}
+
+impl ErnCardinality {
+    /// The smallest supported precision.
+    pub const MIN_PRECISION: u8 = 4;
+    /// The largest supported precision.
+    pub const MAX_PRECISION: u8 = 18;
+
+    /// Creates an empty sketch with `2^precision` registers.
+    ///
+    /// The standard error of the estimate is roughly `1.04 / sqrt(2^precision)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `precision` is outside `MIN_PRECISION..=MAX_PRECISION`.
+    pub fn new(precision: u8) -> Self {
+        assert!(
+            (Self::MIN_PRECISION..=Self::MAX_PRECISION).contains(&precision),
+            "precision must be between {} and {}",
+            Self::MIN_PRECISION,
+            Self::MAX_PRECISION
+        );
+        Self {
+            precision,
+            registers: vec![0; 1 << precision],
+        }
+    }
+
+    /// Returns the precision the sketch was created with.
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Records an ERN (Entity Resource Name) in the sketch.
+    pub fn insert(&mut self, ern: &Ern) {
+        self.insert_hash(ern.stable_hash());
+    }
+
+    /// Records a precomputed stable hash, as returned by [`Ern::stable_hash`].
+    pub fn insert_hash(&mut self, hash: u64) {
+        let index = (hash >> (64 - self.precision)) as usize;
+        let remaining = hash << self.precision;
+        let max_rank = 64 - self.precision + 1;
+        let rank = (remaining.leading_zeros() as u8 + 1).min(max_rank);
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    /// Folds another sketch into this one, so the result estimates the union of both streams.
+    pub fn merge(&mut self, other: &ErnCardinality) -> Result<(), ErnError> {
+        if self.precision != other.precision {
+            return Err(ErnError::IncompatibleSketch(
+                self.precision,
+                other.precision,
+            ));
+        }
+        for (mine, theirs) in self.registers.iter_mut().zip(&other.registers) {
+            *mine = (*mine).max(*theirs);
+        }
+        Ok(())
+    }
+
+    /// Returns the estimated number of distinct ERNs (Entity Resource Names) inserted.
+    pub fn estimate(&self) -> u64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&rank| 2f64.powi(-i32::from(rank)))
+            .sum();
+        let raw = alpha * m * m / sum;
+
+        let zeros = self.registers.iter().filter(|&&rank| rank == 0).count();
+        let estimate = if raw <= 2.5 * m && zeros > 0 {
+            // Linear counting is more accurate while many registers are still empty.
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        };
+        estimate.round() as u64
+    }
+
+    /// Returns true if nothing has been inserted.
+    pub fn is_empty(&self) -> bool {
+        self.registers.iter().all(|&rank| rank == 0)
+    }
+}
+
+impl Default for ErnCardinality {
+    /// Creates a sketch with precision 12 (4 KiB, roughly 1.6% standard error).
+    fn default() -> Self {
+        Self::new(12)
+    }
+}
+
+impl Extend<Ern> for ErnCardinality {
+    fn extend<T: IntoIterator<Item = Ern>>(&mut self, iter: T) {
+        for ern in iter {
+            self.insert(&ern);
+        }
+    }
+}
+
+impl<'a> Extend<&'a Ern> for ErnCardinality {
+    fn extend<T: IntoIterator<Item = &'a Ern>>(&mut self, iter: T) {
+        for ern in iter {
+            self.insert(ern);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn erns(count: usize) -> Vec<Ern> {
+        (0..count)
+            .map(|i| {
+                Ern::with_root("root")
+                    .unwrap()
+                    .add_part(format!("item{i}"))
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_sketch() {
+        let sketch = ErnCardinality::default();
+        assert!(sketch.is_empty());
+        assert_eq!(sketch.estimate(), 0);
+    }
+
+    #[test]
+    fn test_estimate_is_close() {
+        let mut sketch = ErnCardinality::new(14);
+        sketch.extend(&erns(10_000));
+        let estimate = sketch.estimate() as f64;
+        assert!(
+            (estimate - 10_000.0).abs() / 10_000.0 < 0.05,
+            "estimate was {estimate}"
+        );
+    }
+
+    #[test]
+    fn test_duplicates_are_not_counted() {
+        let ern = Ern::with_root("root").unwrap();
+        let mut sketch = ErnCardinality::default();
+        for _ in 0..100 {
+            sketch.insert(&ern);
+        }
+        assert_eq!(sketch.estimate(), 1);
+    }
+
+    #[test]
+    fn test_merge() -> anyhow::Result<()> {
+        let all = erns(2_000);
+        let mut left = ErnCardinality::default();
+        let mut right = ErnCardinality::default();
+        left.extend(&all[..1_500]);
+        right.extend(&all[500..]);
+        left.merge(&right)?;
+        let estimate = left.estimate() as f64;
+        assert!(
+            (estimate - 2_000.0).abs() / 2_000.0 < 0.05,
+            "estimate was {estimate}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_rejects_mismatched_precision() {
+        let mut left = ErnCardinality::new(10);
+        let right = ErnCardinality::new(12);
+        assert_eq!(
+            left.merge(&right),
+            Err(ErnError::IncompatibleSketch(10, 12))
+        );
+    }
+}
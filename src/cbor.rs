@@ -0,0 +1,163 @@
+use ciborium::value::Value;
+
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+use crate::wire::ErnWire;
+
+/// The CBOR tag that marks an encoded ERN (Entity Resource Name).
+///
+/// The value lies in the first-come-first-served range of the IANA CBOR tags registry and has not
+/// been registered yet.
+pub const ERN_CBOR_TAG: u64 = 52_015;
+
+/// How an ERN (Entity Resource Name) is laid out inside its CBOR tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CborForm {
+    /// The canonical string, e.g. `ern:acton:reactive:component:root_…/a`.
+    #[default]
+    Text,
+    /// The array `[domain, category, account, root, revision / null, [parts…]]`, which avoids
+    /// re-parsing the string on constrained devices.
+    Structured,
+}
+
+impl Ern {
+    /// Returns the tagged CBOR value for this ERN (Entity Resource Name) in the given form.
+    pub fn to_cbor_value(&self, form: CborForm) -> Value {
+        let content = match form {
+            CborForm::Text => Value::Text(self.to_string()),
+            CborForm::Structured => {
+                let wire = ErnWire::from(self);
+                Value::Array(vec![
+                    Value::Text(wire.domain),
+                    Value::Text(wire.category),
+                    Value::Text(wire.account),
+                    Value::Text(wire.root),
+                    wire.revision
+                        .map_or(Value::Null, |revision| Value::Integer(revision.into())),
+                    Value::Array(wire.parts.into_iter().map(Value::Text).collect()),
+                ])
+            }
+        };
+        Value::Tag(ERN_CBOR_TAG, Box::new(content))
+    }
+
+    /// Encodes this ERN (Entity Resource Name) as tagged CBOR bytes.
+    pub fn to_cbor(&self, form: CborForm) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&self.to_cbor_value(form), &mut bytes)
+            .expect("writing CBOR to a Vec cannot fail");
+        bytes
+    }
+
+    /// Decodes a tagged CBOR value in either [`CborForm`].
+    pub fn from_cbor_value(value: Value) -> Result<Self, ErnError> {
+        let content = match value {
+            Value::Tag(ERN_CBOR_TAG, content) => *content,
+            Value::Tag(tag, _) => {
+                return Err(ErnError::parse_failure(
+                    "cbor",
+                    format!("unexpected tag {}", tag),
+                ))
+            }
+            _ => return Err(ErnError::parse_failure("cbor", "missing ERN tag")),
+        };
+        match content {
+            Value::Text(value) => ErnParser::new(value).parse(),
+            Value::Array(fields) => Ern::try_from(structured_wire(fields)?),
+            _ => Err(ErnError::parse_failure(
+                "cbor",
+                "expected a text string or an array",
+            )),
+        }
+    }
+
+    /// Decodes tagged CBOR bytes in either [`CborForm`].
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, ErnError> {
+        let value: Value =
+            ciborium::from_reader(bytes).map_err(|error| ErnError::parse_failure("cbor", error))?;
+        Self::from_cbor_value(value)
+    }
+}
+
+fn structured_wire(fields: Vec<Value>) -> Result<ErnWire, ErnError> {
+    let text = |value: Value| match value {
+        Value::Text(value) => Ok(value),
+        _ => Err(ErnError::parse_failure("cbor", "expected a text string")),
+    };
+    let Ok([domain, category, account, root, revision, parts]) = <[Value; 6]>::try_from(fields)
+    else {
+        return Err(ErnError::parse_failure(
+            "cbor",
+            "expected an array of 6 fields",
+        ));
+    };
+    let revision = match revision {
+        Value::Null => None,
+        Value::Integer(revision) => {
+            Some(u64::try_from(revision).map_err(|error| ErnError::parse_failure("cbor", error))?)
+        }
+        _ => {
+            return Err(ErnError::parse_failure(
+                "cbor",
+                "expected an unsigned revision or null",
+            ))
+        }
+    };
+    let parts: Vec<String> = match parts {
+        Value::Array(parts) => parts.into_iter().map(text).collect::<Result<_, _>>()?,
+        _ => {
+            return Err(ErnError::parse_failure(
+                "cbor",
+                "expected an array of parts",
+            ))
+        }
+    };
+    let components = [text(domain)?, text(category)?, text(account)?, text(root)?];
+    Ok(ErnWire {
+        revision,
+        ..ErnWire::from_segments("cbor", components.into_iter().chain(parts))?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_both_forms_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?
+            .with_parts(["a", "b"])?
+            .with_revision(2);
+        for form in [CborForm::Text, CborForm::Structured] {
+            assert_eq!(Ern::from_cbor(&ern.to_cbor(form))?, ern);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_text_form_is_tagged_string() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?;
+        let value: Value = ciborium::from_reader(ern.to_cbor(CborForm::Text).as_slice())?;
+        assert_eq!(
+            value,
+            Value::Tag(ERN_CBOR_TAG, Box::new(Value::Text(ern.to_string())))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_untagged_and_malformed_values_are_rejected() {
+        let untagged = Value::Text("ern:acton:reactive:component:root".to_string());
+        assert!(matches!(
+            Ern::from_cbor_value(untagged),
+            Err(ErnError::ParseFailure("cbor", _))
+        ));
+        let short = Value::Tag(ERN_CBOR_TAG, Box::new(Value::Array(vec![Value::Null])));
+        assert!(matches!(
+            Ern::from_cbor_value(short),
+            Err(ErnError::ParseFailure("cbor", _))
+        ));
+    }
+}
@@ -0,0 +1,329 @@
+use std::io;
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::config::ErnConfig;
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::stream::{parse_frame, DEFAULT_MAX_FRAME_LENGTH};
+
+/// How [`ErnCodec`] separates ERNs (Entity Resource Names) on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Framing {
+    /// Each ERN is followed by `\n`; a preceding `\r` is ignored when decoding.
+    #[default]
+    Newline,
+    /// Each ERN is preceded by its length as a big-endian `u32`.
+    LengthDelimited,
+}
+
+/// Encodes and decodes ERNs (Entity Resource Names) for use with `tokio_util::codec::Framed`.
+///
+/// Decoding yields one `Result` per frame, so a malformed or oversized ERN is reported without
+/// ending the stream; only I/O failures surface as the codec error.
+#[derive(Debug, Clone)]
+pub struct ErnCodec {
+    framing: Framing,
+    max_frame_length: usize,
+    config: ErnConfig,
+    /// Progress through an oversized frame that is being skipped.
+    discarding: Option<Discard>,
+    /// How many bytes at the start of the buffer are known to hold no newline.
+    scanned: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Discard {
+    /// Skipping until the next newline, having seen this many bytes so far.
+    UntilNewline(usize),
+    /// Skipping a length-delimited body with this many bytes left.
+    Remaining(usize),
+}
+
+impl Default for ErnCodec {
+    fn default() -> Self {
+        Self::new(Framing::Newline)
+    }
+}
+
+impl ErnCodec {
+    /// Creates a codec with the given framing and a limit of [`DEFAULT_MAX_FRAME_LENGTH`] bytes per ERN.
+    pub fn new(framing: Framing) -> Self {
+        Self {
+            framing,
+            max_frame_length: DEFAULT_MAX_FRAME_LENGTH,
+            config: ErnConfig::default(),
+            discarding: None,
+            scanned: 0,
+        }
+    }
+
+    /// Sets the maximum length of a single encoded ERN (Entity Resource Name).
+    pub fn max_frame_length(mut self, max_frame_length: usize) -> Self {
+        self.max_frame_length = max_frame_length;
+        self
+    }
+
+    /// Applies `config` when parsing decoded frames.
+    pub fn config(mut self, config: ErnConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    fn too_long(&self, length: usize) -> Result<Ern, ErnError> {
+        Err(ErnError::LimitExceeded(
+            "frame length",
+            self.max_frame_length,
+            length,
+        ))
+    }
+
+    fn decode_newline(&mut self, src: &mut BytesMut) -> Option<Result<Ern, ErnError>> {
+        let newline = src[self.scanned..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map(|at| self.scanned + at);
+        // Every branch below either consumes the buffer up to the newline or clears it, except
+        // when no newline has arrived yet and the frame is still within the limit.
+        self.scanned = 0;
+        if let Some(Discard::UntilNewline(seen)) = self.discarding {
+            return match newline {
+                Some(at) => {
+                    src.advance(at + 1);
+                    self.discarding = None;
+                    Some(self.too_long(seen + at))
+                }
+                None => {
+                    self.discarding = Some(Discard::UntilNewline(seen + src.len()));
+                    src.clear();
+                    None
+                }
+            };
+        }
+        match newline {
+            Some(at) if at <= self.max_frame_length => {
+                let frame = src.split_to(at + 1);
+                Some(parse_frame(&frame[..at], &self.config))
+            }
+            Some(at) => {
+                src.advance(at + 1);
+                Some(self.too_long(at))
+            }
+            None if src.len() > self.max_frame_length => {
+                self.discarding = Some(Discard::UntilNewline(src.len()));
+                src.clear();
+                None
+            }
+            None => {
+                self.scanned = src.len();
+                None
+            }
+        }
+    }
+
+    fn decode_length_delimited(&mut self, src: &mut BytesMut) -> Option<Result<Ern, ErnError>> {
+        if let Some(Discard::Remaining(remaining)) = self.discarding {
+            let skipped = remaining.min(src.len());
+            src.advance(skipped);
+            self.discarding =
+                (skipped < remaining).then(|| Discard::Remaining(remaining - skipped));
+            if self.discarding.is_some() {
+                return None;
+            }
+        }
+        if src.len() < 4 {
+            return None;
+        }
+        let length = u32::from_be_bytes([src[0], src[1], src[2], src[3]]) as usize;
+        if length > self.max_frame_length {
+            src.advance(4);
+            self.discarding = Some(Discard::Remaining(length));
+            return Some(self.too_long(length));
+        }
+        if src.len() < 4 + length {
+            src.reserve(4 + length - src.len());
+            return None;
+        }
+        src.advance(4);
+        let frame = src.split_to(length);
+        Some(parse_frame(&frame, &self.config))
+    }
+}
+
+impl Decoder for ErnCodec {
+    type Item = Result<Ern, ErnError>;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(match self.framing {
+            Framing::Newline => self.decode_newline(src),
+            Framing::LengthDelimited => self.decode_length_delimited(src),
+        })
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(item) = self.decode(src)? {
+            return Ok(Some(item));
+        }
+        match (self.framing, self.discarding.take()) {
+            (Framing::Newline, Some(Discard::UntilNewline(seen))) => Ok(Some(self.too_long(seen))),
+            (Framing::Newline, _) if !src.is_empty() => {
+                self.scanned = 0;
+                let frame = src.split();
+                Ok(Some(parse_frame(&frame, &self.config)))
+            }
+            (_, None) if src.is_empty() => Ok(None),
+            _ => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "stream ended in the middle of an ERN frame",
+            )),
+        }
+    }
+}
+
+impl Encoder<&Ern> for ErnCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, ern: &Ern, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let value = ern.to_string();
+        if value.len() > self.max_frame_length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                ErnError::LimitExceeded("frame length", self.max_frame_length, value.len())
+                    .to_string(),
+            ));
+        }
+        match self.framing {
+            Framing::Newline => {
+                dst.reserve(value.len() + 1);
+                dst.put_slice(value.as_bytes());
+                dst.put_u8(b'\n');
+            }
+            Framing::LengthDelimited => {
+                let length = u32::try_from(value.len()).map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        ErnError::LimitExceeded("frame length", u32::MAX as usize, value.len())
+                            .to_string(),
+                    )
+                })?;
+                dst.reserve(value.len() + 4);
+                dst.put_u32(length);
+                dst.put_slice(value.as_bytes());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Encoder<Ern> for ErnCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, ern: Ern, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode(&ern, dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn erns() -> anyhow::Result<Vec<Ern>> {
+        Ok(vec![
+            Ern::with_root("orders")?.with_parts(["a", "b"])?,
+            Ern::with_root("users")?,
+        ])
+    }
+
+    fn decode_all(
+        codec: &mut ErnCodec,
+        bytes: &[u8],
+    ) -> anyhow::Result<Vec<Result<Ern, ErnError>>> {
+        // Feed one byte at a time to exercise frames split across reads
+        let mut src = BytesMut::new();
+        let mut items = Vec::new();
+        for byte in bytes {
+            src.put_u8(*byte);
+            while let Some(item) = codec.decode(&mut src)? {
+                items.push(item);
+            }
+        }
+        items.extend(codec.decode_eof(&mut src)?);
+        Ok(items)
+    }
+
+    #[test]
+    fn test_roundtrip_both_framings() -> anyhow::Result<()> {
+        let erns = erns()?;
+        for framing in [Framing::Newline, Framing::LengthDelimited] {
+            let mut codec = ErnCodec::new(framing);
+            let mut dst = BytesMut::new();
+            for ern in &erns {
+                codec.encode(ern, &mut dst)?;
+            }
+            let decoded = decode_all(&mut codec, &dst)?;
+            assert_eq!(decoded, erns.iter().cloned().map(Ok).collect::<Vec<_>>());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_newline_scan_resumes_where_it_stopped() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?;
+        let text = ern.to_string();
+        let mut codec = ErnCodec::new(Framing::Newline);
+        let mut src = BytesMut::from(&text.as_bytes()[..10]);
+        assert!(codec.decode(&mut src)?.is_none());
+        assert_eq!(codec.scanned, 10);
+        src.put_slice(&text.as_bytes()[10..]);
+        src.put_slice(b"\n");
+        assert_eq!(codec.decode(&mut src)?, Some(Ok(ern)));
+        assert_eq!(codec.scanned, 0);
+        assert!(src.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_oversized_frames_are_skipped() -> anyhow::Result<()> {
+        let valid = Ern::with_root("root")?;
+        let limit = valid.to_string().len();
+        let oversized = "x".repeat(limit + 3);
+
+        let mut newline = ErnCodec::new(Framing::Newline).max_frame_length(limit);
+        let input = format!("{oversized}\n{valid}\n");
+        let decoded = decode_all(&mut newline, input.as_bytes())?;
+        assert_eq!(
+            decoded[0],
+            Err(ErnError::LimitExceeded("frame length", limit, limit + 3))
+        );
+        assert_eq!(decoded[1], Ok(valid.clone()));
+
+        let mut length = ErnCodec::new(Framing::LengthDelimited).max_frame_length(limit);
+        let mut input = BytesMut::new();
+        input.put_u32(oversized.len() as u32);
+        input.put_slice(oversized.as_bytes());
+        length.encode(&valid, &mut input)?;
+        let decoded = decode_all(&mut length, &input)?;
+        assert_eq!(
+            decoded[0],
+            Err(ErnError::LimitExceeded("frame length", limit, limit + 3))
+        );
+        assert_eq!(decoded[1], Ok(valid));
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncated_length_delimited_frame_is_an_io_error() -> anyhow::Result<()> {
+        let mut codec = ErnCodec::new(Framing::LengthDelimited);
+        let mut src = BytesMut::new();
+        src.put_u32(10);
+        src.put_slice(b"ern");
+        assert!(codec.decode(&mut src)?.is_none());
+        assert_eq!(
+            codec.decode_eof(&mut src).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        Ok(())
+    }
+}
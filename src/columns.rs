@@ -0,0 +1,149 @@
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::wire::ErnWire;
+
+/// ERNs (Entity Resource Names) split into parallel per-component columns.
+///
+/// Row `i` of every column belongs to the same ERN, which maps directly onto Arrow string, nullable
+/// `UInt64`, and list-of-string arrays when writing Parquet. Roots are stored verbatim so reassembly
+/// never generates new ids.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ErnColumns {
+    pub domain: Vec<String>,
+    pub category: Vec<String>,
+    pub account: Vec<String>,
+    pub root: Vec<String>,
+    pub revision: Vec<Option<u64>>,
+    pub parts: Vec<Vec<String>>,
+}
+
+impl ErnColumns {
+    /// Creates empty columns with room for `capacity` rows.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            domain: Vec::with_capacity(capacity),
+            category: Vec::with_capacity(capacity),
+            account: Vec::with_capacity(capacity),
+            root: Vec::with_capacity(capacity),
+            revision: Vec::with_capacity(capacity),
+            parts: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of rows.
+    pub fn len(&self) -> usize {
+        self.domain.len()
+    }
+
+    /// Returns true if there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.domain.is_empty()
+    }
+
+    /// Appends one ERN (Entity Resource Name) as a row.
+    pub fn push(&mut self, ern: &Ern) {
+        let wire = ErnWire::from(ern);
+        self.domain.push(wire.domain);
+        self.category.push(wire.category);
+        self.account.push(wire.account);
+        self.root.push(wire.root);
+        self.revision.push(wire.revision);
+        self.parts.push(wire.parts);
+    }
+
+    /// Reassembles the rows into ERNs (Entity Resource Names), validating each one.
+    ///
+    /// Fails with [`ErnError::InvalidFormat`] if the columns have different lengths.
+    pub fn into_erns(self) -> Result<Vec<Ern>, ErnError> {
+        let rows = self.len();
+        if [
+            self.category.len(),
+            self.account.len(),
+            self.root.len(),
+            self.revision.len(),
+            self.parts.len(),
+        ]
+        .iter()
+        .any(|len| *len != rows)
+        {
+            return Err(ErnError::InvalidFormat);
+        }
+        self.domain
+            .into_iter()
+            .zip(self.category)
+            .zip(self.account)
+            .zip(self.root)
+            .zip(self.revision)
+            .zip(self.parts)
+            .map(
+                |(((((domain, category), account), root), revision), parts)| {
+                    let components = [domain, category, account, root];
+                    Ern::try_from(ErnWire {
+                        revision,
+                        ..ErnWire::from_segments("columns", components.into_iter().chain(parts))?
+                    })
+                },
+            )
+            .collect()
+    }
+}
+
+impl<'a> FromIterator<&'a Ern> for ErnColumns {
+    fn from_iter<I: IntoIterator<Item = &'a Ern>>(iter: I) -> Self {
+        let mut columns = Self::default();
+        columns.extend(iter);
+        columns
+    }
+}
+
+impl FromIterator<Ern> for ErnColumns {
+    fn from_iter<I: IntoIterator<Item = Ern>>(iter: I) -> Self {
+        let mut columns = Self::default();
+        columns.extend(iter);
+        columns
+    }
+}
+
+impl<'a> Extend<&'a Ern> for ErnColumns {
+    fn extend<I: IntoIterator<Item = &'a Ern>>(&mut self, iter: I) {
+        for ern in iter {
+            self.push(ern);
+        }
+    }
+}
+
+impl Extend<Ern> for ErnColumns {
+    fn extend<I: IntoIterator<Item = Ern>>(&mut self, iter: I) {
+        for ern in iter {
+            self.push(&ern);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_roundtrip() -> anyhow::Result<()> {
+        let erns = vec![
+            Ern::with_root("orders")?.with_parts(["a", "b"])?,
+            Ern::with_root("users")?.with_revision(2),
+        ];
+        let columns: ErnColumns = erns.iter().collect();
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns.root[0], erns[0].root.to_string());
+        assert_eq!(columns.revision, vec![None, Some(2)]);
+        assert_eq!(columns.parts[0], vec!["a", "b"]);
+        assert_eq!(columns.into_erns()?, erns);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mismatched_columns_are_rejected() -> anyhow::Result<()> {
+        let mut columns: ErnColumns = [Ern::with_root("root")?].into_iter().collect();
+        columns.parts.push(Vec::new());
+        assert_eq!(columns.into_erns(), Err(ErnError::InvalidFormat));
+        Ok(())
+    }
+}
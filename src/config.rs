@@ -0,0 +1,488 @@
+use std::borrow::Cow;
+
+use crate::distance::closest_match;
+use crate::errors::ErnError;
+use crate::instrumentation;
+use crate::model::Ern;
+
+/// The character between the root and each part in canonical ERNs (Entity Resource Names).
+pub const DEFAULT_PARTS_SEPARATOR: char = '/';
+
+/// Settings that control how ERN (Entity Resource Name) strings are parsed.
+///
+/// The default configuration reproduces the behavior of [`ErnParser::parse`](crate::ErnParser::parse):
+/// the `ern` scheme, `/` between parts, no normalization, and no limits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErnConfig {
+    /// The scheme expected before the domain, `ern` by default.
+    ///
+    /// Parsed ERNs always display with the canonical `ern` scheme.
+    pub scheme: Cow<'static, str>,
+    /// The character between the root and each part, [`DEFAULT_PARTS_SEPARATOR`] by default.
+    ///
+    /// With any other separator, a `\` or separator character inside a part is escaped with a
+    /// `\`, e.g. `root.v1\.2.eu` for the parts `v1.2` and `eu` with `.`. Parsed ERNs always
+    /// display with `/`; [`Ern::display_with`] renders with the configured separator.
+    pub parts_separator: char,
+    /// Trims surrounding whitespace and lowercases the scheme, domain, category, account, and parts before validation.
+    pub normalize: bool,
+    /// Structural and size limits applied to the parsed result.
+    pub validation: ValidationConfig,
+}
+
+impl Default for ErnConfig {
+    fn default() -> Self {
+        Self {
+            scheme: Cow::Borrowed("ern"),
+            parts_separator: DEFAULT_PARTS_SEPARATOR,
+            normalize: false,
+            validation: ValidationConfig::default(),
+        }
+    }
+}
+
+impl ErnConfig {
+    /// Returns a configuration that normalizes input and enforces [`ValidationConfig::strict`].
+    pub fn strict() -> Self {
+        Self {
+            normalize: true,
+            validation: ValidationConfig::strict(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the default configuration with [`ErnConfig::normalize`] set, which defines the
+    /// canonical form of a component.
+    pub(crate) fn normalizing() -> Self {
+        Self {
+            normalize: true,
+            ..Self::default()
+        }
+    }
+
+    /// Returns `value` as this configuration reads a component: trimmed and lowercased when
+    /// [`ErnConfig::normalize`] is set, and unchanged otherwise.
+    pub(crate) fn normalized<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if !self.normalize {
+            return Cow::Borrowed(value);
+        }
+        let trimmed = value.trim();
+        let lowercase = trimmed.to_lowercase();
+        if lowercase == trimmed {
+            Cow::Borrowed(trimmed)
+        } else {
+            Cow::Owned(lowercase)
+        }
+    }
+
+    /// Returns this configuration with a different parts separator, for systems that cannot carry `/`.
+    ///
+    /// The separator cannot be `:`, `#`, `\`, whitespace, or a character allowed in a root id
+    /// (ASCII letters, digits, and `_`), since those would make the root boundary ambiguous.
+    pub fn with_parts_separator(mut self, separator: char) -> Result<Self, ErnError> {
+        check_parts_separator(separator)?;
+        self.parts_separator = separator;
+        Ok(self)
+    }
+}
+
+pub(crate) fn check_parts_separator(separator: char) -> Result<(), ErnError> {
+    if matches!(separator, ':' | '#' | '\\' | '_')
+        || separator.is_alphanumeric()
+        || separator.is_whitespace()
+        || separator.is_control()
+    {
+        return Err(ErnError::InvalidCharacter("parts separator", separator));
+    }
+    Ok(())
+}
+
+/// Splits the text after the root at each unescaped `separator`, keeping escapes in the pieces.
+///
+/// The canonical `/` is never escaped, since parts cannot contain it.
+pub(crate) fn split_parts(path: &str, separator: char) -> Vec<&str> {
+    if separator == DEFAULT_PARTS_SEPARATOR {
+        return path.split(separator).collect();
+    }
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in path.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == separator {
+            pieces.push(&path[start..index]);
+            start = index + c.len_utf8();
+        }
+    }
+    pieces.push(&path[start..]);
+    pieces
+}
+
+/// Removes the escapes from one piece returned by [`split_parts`].
+pub(crate) fn unescape_part(piece: &str, separator: char) -> Result<Cow<'_, str>, ErnError> {
+    if separator == DEFAULT_PARTS_SEPARATOR || !piece.contains('\\') {
+        return Ok(Cow::Borrowed(piece));
+    }
+    let mut part = String::with_capacity(piece.len());
+    let mut chars = piece.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            let escaped = chars.next().ok_or_else(|| {
+                ErnError::ParseFailure("Part", format!("'{piece}' ends with an unfinished escape"))
+            })?;
+            part.push(escaped);
+        } else {
+            part.push(c);
+        }
+    }
+    Ok(Cow::Owned(part))
+}
+
+/// Escapes a part for [`split_parts`] with a non-canonical `separator`.
+pub(crate) fn escape_part(part: &str, separator: char) -> Cow<'_, str> {
+    if separator == DEFAULT_PARTS_SEPARATOR || !part.contains([separator, '\\']) {
+        return Cow::Borrowed(part);
+    }
+    let mut escaped = String::with_capacity(part.len() + 2);
+    for c in part.chars() {
+        if c == separator || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    Cow::Owned(escaped)
+}
+
+/// Limits and character rules an ERN (Entity Resource Name) must satisfy.
+///
+/// Every limit is optional; `None` means unlimited.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ValidationConfig {
+    /// Restricts domain, category, account, and parts to ASCII letters, digits, `-`, `_`, and `.`.
+    pub strict: bool,
+    /// The maximum length of the full ERN (Entity Resource Name) string.
+    pub max_length: Option<usize>,
+    /// The maximum number of parts after the root.
+    pub max_depth: Option<usize>,
+    /// The maximum length of any single domain, category, account, or part.
+    pub max_component_length: Option<usize>,
+    /// The maximum number of times a sequence of parts may repeat back-to-back.
+    ///
+    /// `Some(1)` rejects adjacent duplicates (`a/a`) and cycles (`a/b/a/b`), which usually indicate
+    /// a restart loop generating a runaway hierarchy. Repetition is only checked for ERNs within
+    /// [`ValidationConfig::max_depth`] and [`ValidationConfig::max_length`], so set those when
+    /// validating untrusted input.
+    pub max_part_repeats: Option<usize>,
+    /// The only domains accepted, if set; rejected values suggest the closest known domain.
+    pub allowed_domains: Option<Vec<Cow<'static, str>>>,
+    /// The only categories accepted, if set; rejected values suggest the closest known category.
+    pub allowed_categories: Option<Vec<Cow<'static, str>>>,
+}
+
+impl ValidationConfig {
+    /// Returns a configuration with strict characters and conservative limits suitable for untrusted input.
+    pub fn strict() -> Self {
+        Self {
+            strict: true,
+            max_length: Some(1024),
+            max_depth: Some(32),
+            max_component_length: Some(128),
+            ..Self::default()
+        }
+    }
+
+    /// Checks an ERN (Entity Resource Name) against these rules, returning the first violation.
+    pub fn validate(&self, ern: &Ern) -> Result<(), ErnError> {
+        match self.violations(ern).into_iter().next() {
+            Some(violation) => Err(violation),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns every rule the ERN (Entity Resource Name) violates, in a stable order.
+    pub fn violations(&self, ern: &Ern) -> Vec<ErnError> {
+        let mut violations = Vec::new();
+        let depth = self
+            .max_depth
+            .and_then(|max_depth| check_limit("depth", max_depth, ern.parts.len()).err());
+        let length = self
+            .max_length
+            .and_then(|max_length| check_limit("length", max_length, ern.to_string().len()).err());
+        // Only look for repetition in ERNs that are otherwise within bounds, and only for
+        // sequences short enough to repeat more than the limit allows.
+        let oversized = depth.is_some() || length.is_some();
+        violations.extend(depth);
+        if let (Some(max_part_repeats), false) = (self.max_part_repeats, oversized) {
+            let max_period = ern.parts.len() / max_part_repeats.saturating_add(1).max(2);
+            if let Some(repetition) = ern.parts.repetition_within(max_period) {
+                violations.extend(
+                    check_limit("part repetition", max_part_repeats, repetition.repeats).err(),
+                );
+            }
+        }
+        violations.extend(length);
+
+        let known = [
+            ("domain", &self.allowed_domains, ern.domain.as_str()),
+            ("category", &self.allowed_categories, ern.category.as_str()),
+        ];
+        for (name, allowed, value) in known {
+            if let Some(allowed) = allowed {
+                if !allowed.iter().any(|candidate| candidate == value) {
+                    let suggestion =
+                        closest_match(value, allowed.iter().map(|candidate| candidate.as_ref()));
+                    violations.push(ErnError::UnknownComponent(
+                        name,
+                        value.to_string(),
+                        suggestion.map(str::to_string),
+                    ));
+                }
+            }
+        }
+
+        let components = [
+            ("domain", ern.domain.as_str()),
+            ("category", ern.category.as_str()),
+            ("account", ern.account.as_str()),
+        ]
+        .into_iter()
+        .chain((&ern.parts).into_iter().map(|part| ("part", part.as_str())));
+
+        for (name, value) in components {
+            if let Some(max_component_length) = self.max_component_length {
+                violations.extend(check_limit(name, max_component_length, value.len()).err());
+            }
+            if self.strict {
+                if let Some(invalid) = value.chars().find(|c| !is_strict_char(*c)) {
+                    violations.push(ErnError::InvalidCharacter(name, invalid));
+                }
+            }
+        }
+        for violation in &violations {
+            instrumentation::increment(
+                instrumentation::VALIDATION_FAILURES,
+                Some(violation.kind()),
+            );
+        }
+        violations
+    }
+}
+
+fn check_limit(name: &'static str, limit: usize, actual: usize) -> Result<(), ErnError> {
+    if actual > limit {
+        Err(ErnError::LimitExceeded(name, limit, actual))
+    } else {
+        Ok(())
+    }
+}
+
+fn is_strict_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_validation_accepts_anything() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.with_parts(["a b", "c!"])?;
+        assert!(ValidationConfig::default().validate(&ern).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_depth() -> anyhow::Result<()> {
+        let config = ValidationConfig {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        let ern = Ern::with_root("root")?.with_parts(["a", "b"])?;
+        assert_eq!(
+            config.validate(&ern),
+            Err(ErnError::LimitExceeded("depth", 1, 2))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_component_length() -> anyhow::Result<()> {
+        let config = ValidationConfig {
+            max_component_length: Some(3),
+            ..Default::default()
+        };
+        let ern = Ern::with_root("root")?.with_parts(["toolong"])?;
+        assert!(matches!(
+            config.validate(&ern),
+            Err(ErnError::LimitExceeded(_, 3, _))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_strict_characters() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.with_parts(["has space"])?;
+        assert_eq!(
+            ValidationConfig::strict().validate(&ern),
+            Err(ErnError::InvalidCharacter("part", ' '))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_part_repeats() -> anyhow::Result<()> {
+        let config = ValidationConfig {
+            max_part_repeats: Some(2),
+            ..Default::default()
+        };
+        let ern = Ern::with_root("root")?;
+        assert!(config
+            .validate(&ern.with_parts(["a", "b", "a", "b"])?)
+            .is_ok());
+        assert_eq!(
+            config.validate(&ern.with_parts(["a", "b", "a", "b", "a", "b"])?),
+            Err(ErnError::LimitExceeded("part repetition", 2, 3))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_repetition_is_skipped_for_oversized_erns() -> anyhow::Result<()> {
+        let config = ValidationConfig {
+            max_depth: Some(32),
+            max_part_repeats: Some(2),
+            ..Default::default()
+        };
+        let ern = Ern::with_root("root")?.with_parts(vec!["a"; 2048])?;
+        assert_eq!(
+            config.violations(&ern),
+            vec![ErnError::LimitExceeded("depth", 32, 2048)]
+        );
+        let unbounded = ValidationConfig {
+            max_part_repeats: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            unbounded.violations(&ern),
+            vec![ErnError::LimitExceeded("part repetition", 2, 2048)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_category_suggests_closest() -> anyhow::Result<()> {
+        let config = ValidationConfig {
+            allowed_categories: Some(vec!["actor".into(), "reactive".into()]),
+            ..Default::default()
+        };
+        let ern = Ern::with_category("actr")?;
+        let error = config.validate(&ern).unwrap_err();
+        assert_eq!(
+            error,
+            ErnError::UnknownComponent("category", "actr".to_string(), Some("actor".to_string()))
+        );
+        assert_eq!(
+            error.to_string(),
+            "Validation Error - unknown category `actr`, did you mean `actor`?"
+        );
+        assert!(config.validate(&Ern::with_category("actor")?).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_domain_without_close_match() -> anyhow::Result<()> {
+        let config = ValidationConfig {
+            allowed_domains: Some(vec!["acton".into()]),
+            ..Default::default()
+        };
+        let error = config.validate(&Ern::with_domain("billing")?).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Validation Error - unknown domain `billing`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_violations_reports_everything() -> anyhow::Result<()> {
+        let config = ValidationConfig {
+            max_depth: Some(1),
+            ..ValidationConfig::strict()
+        };
+        let ern = Ern::with_root("root")?.with_parts(["a b", "c!"])?;
+        assert_eq!(
+            config.violations(&ern),
+            vec![
+                ErnError::LimitExceeded("depth", 1, 2),
+                ErnError::InvalidCharacter("part", ' '),
+                ErnError::InvalidCharacter("part", '!'),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parts_separator() -> anyhow::Result<()> {
+        let config = ErnConfig::default().with_parts_separator('|')?;
+        let ern = Ern::parse_with_config("ern:acton:hr:acct:root|a\\|b|c", &config)?;
+        assert_eq!(ern.parts.to_string(), "a|b/c");
+        assert!(Ern::parse_with_config("ern:acton:hr:acct:root|a\\", &config).is_err());
+        for invalid in [':', '#', '\\', '_', 'a', '7', ' '] {
+            assert_eq!(
+                ErnConfig::default().with_parts_separator(invalid),
+                Err(ErnError::InvalidCharacter("parts separator", invalid))
+            );
+        }
+        let config = ErnConfig {
+            parts_separator: 'x',
+            ..ErnConfig::default()
+        };
+        assert!(Ern::parse_with_config("ern:acton:hr:acct:rootxa", &config).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalized_length_excludes_surrounding_whitespace() -> anyhow::Result<()> {
+        let input =
+            Ern::parse_with_config("ern:acton:hr:acct:root", &ErnConfig::default())?.to_string();
+        let config = ErnConfig {
+            normalize: true,
+            validation: ValidationConfig {
+                max_length: Some(input.len()),
+                ..ValidationConfig::default()
+            },
+            ..ErnConfig::default()
+        };
+        let ern = Ern::parse_with_config(&format!("  {input}\n"), &config)?;
+        assert_eq!(ern.to_string(), input);
+        assert_eq!(
+            Ern::parse_with_config(&format!("{input}/a"), &config),
+            Err(ErnError::LimitExceeded(
+                "length",
+                input.len(),
+                input.len() + 2
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalized_scheme_matches_configured_scheme() -> anyhow::Result<()> {
+        let config = ErnConfig {
+            scheme: "URN".into(),
+            normalize: true,
+            ..ErnConfig::default()
+        };
+        for input in ["urn:acton:hr:acct:root", "URN:acton:hr:acct:root"] {
+            Ern::parse_with_config(input, &config)?;
+        }
+        assert_eq!(
+            Ern::parse_with_config("ern:acton:hr:acct:root", &config),
+            Err(ErnError::InvalidFormat)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+use crate::errors::ErnError;
+use crate::model::{Account, Category, Domain, EntityRoot, Ern, Part, Parts};
+
+thread_local! {
+    static CONTEXT_STACK: RefCell<Vec<ErnContext>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Scoped defaults inherited by ERNs (Entity Resource Names) constructed on the current thread.
+///
+/// Contexts nest: entering a context layers it over any already active one, and each unset field
+/// falls through to the enclosing context. Fields no context sets use the component defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ErnContext {
+    pub domain: Option<Domain>,
+    pub category: Option<Category>,
+    pub account: Option<Account>,
+    pub root: Option<EntityRoot>,
+}
+
+impl ErnContext {
+    /// Activates this context until the returned guard is dropped.
+    pub fn enter(self) -> ErnContextGuard {
+        let depth = CONTEXT_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            stack.push(self);
+            stack.len() - 1
+        });
+        ErnContextGuard {
+            depth,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Runs `f` with this context active.
+    pub fn scope<R>(self, f: impl FnOnce() -> R) -> R {
+        let _guard = self.enter();
+        f()
+    }
+
+    /// Returns the effective context on this thread, with inner scopes overriding outer ones field by field.
+    pub fn current() -> ErnContext {
+        CONTEXT_STACK.with(|stack| {
+            stack
+                .borrow()
+                .iter()
+                .fold(ErnContext::default(), |outer, inner| ErnContext {
+                    domain: inner.domain.clone().or(outer.domain),
+                    category: inner.category.clone().or(outer.category),
+                    account: inner.account.clone().or(outer.account),
+                    root: inner.root.clone().or(outer.root),
+                })
+        })
+    }
+
+    /// Builds an ERN (Entity Resource Name) from this context's defaults and the given parts.
+    ///
+    /// Fails with [`ErnError::MissingPart`] if no root is set.
+    pub fn ern<I>(&self, parts: I) -> Result<Ern, ErnError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let root = self
+            .root
+            .clone()
+            .ok_or(ErnError::MissingPart("root".to_string()))?;
+        let parts = parts
+            .into_iter()
+            .map(|part| Part::new(part.into()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Ern::new(
+            self.domain.clone().unwrap_or_default(),
+            self.category.clone().unwrap_or_default(),
+            self.account.clone().unwrap_or_default(),
+            root,
+            Parts::new(parts),
+        ))
+    }
+}
+
+/// Keeps an [`ErnContext`] active on the current thread; dropping it restores the enclosing context.
+#[derive(Debug)]
+#[must_use = "the context is exited as soon as the guard is dropped"]
+pub struct ErnContextGuard {
+    depth: usize,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ErnContextGuard {
+    fn drop(&mut self) {
+        CONTEXT_STACK.with(|stack| stack.borrow_mut().truncate(self.depth));
+    }
+}
+
+impl Ern {
+    /// Builds an ERN (Entity Resource Name) from the current thread's [`ErnContext`] and the given parts.
+    pub fn from_context<I>(parts: I) -> Result<Self, ErnError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        ErnContext::current().ern(parts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_provides_defaults() -> anyhow::Result<()> {
+        let root = EntityRoot::new("orders".to_string())?;
+        let context = ErnContext {
+            account: Some(Account::new("tenant9")),
+            root: Some(root.clone()),
+            ..Default::default()
+        };
+        let ern = context.scope(|| Ern::from_context(["item1"]))?;
+        assert_eq!(ern.account.as_str(), "tenant9");
+        assert_eq!(ern.domain, Domain::default());
+        assert_eq!(ern.root, root);
+        assert_eq!(ern.parts.to_string(), "item1");
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_scopes_override_per_field() -> anyhow::Result<()> {
+        let outer = ErnContext {
+            domain: Some(Domain::new("outer")?),
+            account: Some(Account::new("tenant1")),
+            ..Default::default()
+        };
+        let inner = ErnContext {
+            account: Some(Account::new("tenant2")),
+            ..Default::default()
+        };
+        outer.scope(|| {
+            inner.scope(|| {
+                let current = ErnContext::current();
+                assert_eq!(current.domain.as_ref().map(Domain::as_str), Some("outer"));
+                assert_eq!(
+                    current.account.as_ref().map(Account::as_str),
+                    Some("tenant2")
+                );
+            });
+            let current = ErnContext::current();
+            assert_eq!(
+                current.account.as_ref().map(Account::as_str),
+                Some("tenant1")
+            );
+        });
+        assert_eq!(ErnContext::current(), ErnContext::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_root_is_an_error() {
+        assert_eq!(
+            Ern::from_context(["a"]),
+            Err(ErnError::MissingPart("root".to_string()))
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::errors::ErnError;
+use crate::model::{EntityRoot, Ern, Parts};
+use crate::parser::ErnParser;
+
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// An opaque, URL-safe pagination cursor positioned after a given ERN (Entity Resource Name).
+///
+/// The cursor records the last ERN returned to a client and compares with the same ordering as
+/// [`Ern`]: by the time-ordered root, then the revision and the remaining components. Listings
+/// sorted by that order can therefore resume exactly where they left off.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    ern: Ern,
+}
+
+impl Cursor {
+    /// Creates a cursor that resumes immediately after `ern`.
+    pub fn after(ern: &Ern) -> Self {
+        Self { ern: ern.clone() }
+    }
+
+    /// Decodes a cursor previously produced by [`Cursor::encode`].
+    pub fn decode(encoded: &str) -> Result<Self, ErnError> {
+        let bytes = decode_base64_url(encoded).ok_or(ErnError::InvalidCursor)?;
+        let payload = String::from_utf8(bytes).map_err(|_| ErnError::InvalidCursor)?;
+        let ern = ErnParser::new(payload.clone())
+            .parse()
+            .map_err(|_| ErnError::InvalidCursor)?;
+        // A plain root name would get a freshly generated id and so a different position
+        if ern.to_string() != payload {
+            return Err(ErnError::InvalidCursor);
+        }
+        Ok(Self { ern })
+    }
+
+    /// Encodes the cursor as an unpadded base64url string.
+    pub fn encode(&self) -> String {
+        encode_base64_url(self.ern.to_string().as_bytes())
+    }
+
+    /// Returns the root of the ERN (Entity Resource Name) the cursor was created from.
+    pub fn root(&self) -> &EntityRoot {
+        &self.ern.root
+    }
+
+    /// Returns the parts of the ERN (Entity Resource Name) the cursor was created from.
+    pub fn parts(&self) -> &Parts {
+        &self.ern.parts
+    }
+
+    /// Returns true if `ern` sorts strictly after the cursor position and belongs on the next page.
+    pub fn precedes(&self, ern: &Ern) -> bool {
+        ern.cmp(&self.ern) == Ordering::Greater
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.encode())
+    }
+}
+
+impl std::str::FromStr for Cursor {
+    type Err = ErnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Cursor::decode(s)
+    }
+}
+
+fn encode_base64_url(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let buffer = chunk.iter().enumerate().fold(0u32, |acc, (i, &byte)| {
+            acc | u32::from(byte) << (16 - 8 * i)
+        });
+        for i in 0..=chunk.len() {
+            let index = (buffer >> (18 - 6 * i)) & 0x3f;
+            encoded.push(BASE64_URL[index as usize] as char);
+        }
+    }
+    encoded
+}
+
+fn decode_base64_url(encoded: &str) -> Option<Vec<u8>> {
+    if encoded.len() % 4 == 1 {
+        return None;
+    }
+    let mut decoded = Vec::with_capacity(encoded.len() * 3 / 4);
+    for chunk in encoded.as_bytes().chunks(4) {
+        let mut buffer = 0u32;
+        for (i, &symbol) in chunk.iter().enumerate() {
+            let value = BASE64_URL.iter().position(|&c| c == symbol)? as u32;
+            buffer |= value << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            decoded.push((buffer >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_url_roundtrip() {
+        for input in [
+            "",
+            "a",
+            "ab",
+            "abc",
+            "abcd",
+            "root_01h455vb4pex5vsknk084sn02q/a/b",
+        ] {
+            let encoded = encode_base64_url(input.as_bytes());
+            assert!(!encoded.contains(['+', '/', '=']));
+            assert_eq!(decode_base64_url(&encoded).unwrap(), input.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.with_parts(["a", "b"])?;
+        let cursor = Cursor::after(&ern);
+        let decoded = Cursor::decode(&cursor.encode())?;
+        assert_eq!(decoded, cursor);
+        assert_eq!(decoded.root(), &ern.root);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cursor_rejects_garbage() {
+        assert_eq!(
+            Cursor::decode("not a cursor!"),
+            Err(ErnError::InvalidCursor)
+        );
+        assert_eq!(
+            Cursor::decode(&encode_base64_url(b"plainroot")),
+            Err(ErnError::InvalidCursor)
+        );
+    }
+
+    #[test]
+    fn test_cursor_precedes() -> anyhow::Result<()> {
+        let first = Ern::with_root("root")?.add_part("a")?;
+        let sibling = first.with_parts(["b"])?;
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let later = Ern::with_root("root")?;
+
+        let cursor = Cursor::after(&first);
+        assert!(!cursor.precedes(&first));
+        assert!(cursor.precedes(&sibling));
+        assert!(cursor.precedes(&later));
+        assert!(!Cursor::after(&later).precedes(&first));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cursor_follows_ern_ordering() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.add_part("a")?;
+        let mut other_account = ern.clone();
+        other_account.account = crate::model::Account::new("other");
+        let mut listing = [
+            ern.with_revision(2),
+            ern.clone(),
+            ern.with_revision(1),
+            other_account,
+            ern.with_parts(["b"])?,
+        ];
+        listing.sort();
+        for (index, position) in listing.iter().enumerate() {
+            let cursor = Cursor::decode(&Cursor::after(position).encode())?;
+            let next_page: Vec<_> = listing.iter().filter(|ern| cursor.precedes(ern)).collect();
+            assert_eq!(next_page, listing[index + 1..].iter().collect::<Vec<_>>());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,332 @@
+use std::ops::Range;
+
+use crate::config::ErnConfig;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+
+/// A problem found by [`Ern::validate_verbose`], located by byte span in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// A human-readable description of the problem.
+    pub message: String,
+    /// The bytes of the input the problem covers.
+    pub span: Range<usize>,
+    /// A machine-applicable fix that replaces `span`, if one exists.
+    pub fix: Option<Fix>,
+}
+
+/// A replacement for the span of a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// What the fix does, e.g. `replace ' ' with '-'` or `lowercase 'HR'`.
+    pub description: String,
+    /// The text to put in place of the diagnostic's span.
+    pub replacement: String,
+}
+
+/// The result of [`Ern::validate_verbose`].
+#[derive(Debug, PartialEq)]
+pub enum ValidationOutcome {
+    /// The input is a canonical ERN (Entity Resource Name) that passes strict validation.
+    Valid(Ern),
+    /// Every problem found, in input order.
+    Invalid(Vec<Diagnostic>),
+}
+
+impl ValidationOutcome {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, ValidationOutcome::Valid(_))
+    }
+
+    /// Returns the problems found, empty if the input is valid.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            ValidationOutcome::Valid(_) => &[],
+            ValidationOutcome::Invalid(diagnostics) => diagnostics,
+        }
+    }
+
+    /// Applies every available fix to `input`, which must be the string that was validated.
+    ///
+    /// Fixes never overlap, so they can all be applied at once; problems without a fix are left
+    /// in place, so the result should be validated again.
+    pub fn apply_fixes(&self, input: &str) -> String {
+        let mut output = input.to_string();
+        let mut fixes: Vec<_> = self
+            .diagnostics()
+            .iter()
+            .filter_map(|diagnostic| diagnostic.fix.as_ref().map(|fix| (&diagnostic.span, fix)))
+            .collect();
+        fixes.sort_by_key(|(span, _)| std::cmp::Reverse(span.start));
+        for (span, fix) in fixes {
+            output.replace_range(span.clone(), &fix.replacement);
+        }
+        output
+    }
+}
+
+impl Ern {
+    /// Checks `input` against the canonical form and [`ErnConfig::strict`], explaining every
+    /// problem found and offering fixes an editor or CLI can apply.
+    ///
+    /// Canonical input has no surrounding whitespace, a lowercase `ern` scheme, components made of
+    /// lowercase ASCII letters, digits, `-`, `_`, and `.`, and no empty parts.
+    pub fn validate_verbose(input: &str) -> ValidationOutcome {
+        let mut diagnostics = Vec::new();
+        let start = input.len() - input.trim_start().len();
+        let end = input.trim_end().len();
+        if start > 0 {
+            diagnostics.push(fixable(
+                "leading whitespace",
+                0..start,
+                "remove leading whitespace",
+                "",
+            ));
+        }
+        let trimmed = &input[start..end.max(start)];
+
+        let mut fields = Vec::with_capacity(5);
+        let mut offset = start;
+        for field in trimmed.splitn(5, ':') {
+            fields.push((offset..offset + field.len(), field));
+            offset += field.len() + 1;
+        }
+        if fields.len() < 5 {
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "expected `ern:domain:category:account:root`, found {} of 5 fields",
+                    fields.len()
+                ),
+                span: start..end.max(start),
+                fix: None,
+            });
+        } else {
+            check_scheme(fields[0].0.clone(), fields[0].1, &mut diagnostics);
+            if fields[1].1.is_empty() {
+                diagnostics.push(Diagnostic {
+                    message: "the domain is empty".to_string(),
+                    span: fields[1].0.clone(),
+                    fix: None,
+                });
+            }
+            for (name, (span, value)) in ["domain", "category", "account"]
+                .into_iter()
+                .zip(&fields[1..4])
+            {
+                check_characters(name, span.start, value, &mut diagnostics);
+            }
+            check_root_and_parts(fields[4].0.start, fields[4].1, &mut diagnostics);
+        }
+        if end < input.len() && end > start {
+            diagnostics.push(fixable(
+                "trailing whitespace",
+                end..input.len(),
+                "remove trailing whitespace",
+                "",
+            ));
+        }
+
+        if !diagnostics.is_empty() {
+            return ValidationOutcome::Invalid(diagnostics);
+        }
+        match ErnParser::new(input.to_string()).parse_with_config(&ErnConfig::strict()) {
+            Ok(ern) => ValidationOutcome::Valid(ern),
+            Err(error) => ValidationOutcome::Invalid(vec![Diagnostic {
+                message: error.to_string(),
+                span: 0..input.len(),
+                fix: None,
+            }]),
+        }
+    }
+}
+
+fn fixable(
+    message: impl Into<String>,
+    span: Range<usize>,
+    description: impl Into<String>,
+    replacement: impl Into<String>,
+) -> Diagnostic {
+    Diagnostic {
+        message: message.into(),
+        span,
+        fix: Some(Fix {
+            description: description.into(),
+            replacement: replacement.into(),
+        }),
+    }
+}
+
+fn check_scheme(span: Range<usize>, scheme: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if scheme == "ern" {
+        return;
+    }
+    let description = if scheme.eq_ignore_ascii_case("ern") {
+        format!("lowercase '{scheme}'")
+    } else {
+        format!("replace '{scheme}' with 'ern'")
+    };
+    diagnostics.push(fixable(
+        format!("unexpected scheme '{scheme}'"),
+        span,
+        description,
+        "ern",
+    ));
+}
+
+/// Flags runs of characters that normalization would lowercase, spaces, and other characters
+/// outside the strict set.
+fn check_characters(name: &str, offset: usize, value: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let config = ErnConfig::normalizing();
+    let changes_case = |c: char| {
+        !c.is_whitespace() && config.normalized(c.encode_utf8(&mut [0; 4])) != c.to_string()
+    };
+    let mut chars = value.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let span = offset + index..offset + index + c.len_utf8();
+        if c.is_whitespace() {
+            diagnostics.push(fixable(
+                format!("{name} contains whitespace"),
+                span,
+                format!("replace {c:?} with '-'"),
+                "-",
+            ));
+        } else if changes_case(c) {
+            let mut end = span.end;
+            while let Some(&(next_index, next)) = chars.peek() {
+                if !changes_case(next) {
+                    break;
+                }
+                end = offset + next_index + next.len_utf8();
+                chars.next();
+            }
+            let run = &value[span.start - offset..end - offset];
+            diagnostics.push(fixable(
+                format!("{name} contains uppercase characters"),
+                span.start..end,
+                format!("lowercase '{run}'"),
+                config.normalized(run),
+            ));
+        } else if !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+            diagnostics.push(fixable(
+                format!("{name} contains invalid character {c:?}"),
+                span,
+                format!("remove {c:?}"),
+                "",
+            ));
+        }
+    }
+}
+
+fn check_root_and_parts(offset: usize, value: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let (head, parts) = match value.split_once('/') {
+        Some((head, parts)) => (head, Some(parts)),
+        None => (value, None),
+    };
+    if let Some((root, revision)) = head.split_once('#') {
+        if revision.parse::<u64>().is_err() {
+            let span = offset + root.len()..offset + head.len();
+            diagnostics.push(fixable(
+                format!("'{revision}' is not a valid revision"),
+                span,
+                "remove the revision",
+                "",
+            ));
+        }
+    }
+    let Some(parts) = parts else {
+        return;
+    };
+    let mut part_offset = offset + head.len() + 1;
+    for part in parts.split('/') {
+        if part.is_empty() {
+            // Remove the separator that introduced the empty part
+            let span = part_offset - 1..part_offset;
+            diagnostics.push(fixable("empty part", span, "remove the extra '/'", ""));
+        } else if part.contains(':') {
+            diagnostics.push(Diagnostic {
+                message: "parts cannot contain ':'".to_string(),
+                span: part_offset..part_offset + part.len(),
+                fix: None,
+            });
+        } else {
+            check_characters("part", part_offset, part, diagnostics);
+        }
+        part_offset += part.len() + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptions(outcome: &ValidationOutcome) -> Vec<&str> {
+        outcome
+            .diagnostics()
+            .iter()
+            .filter_map(|diagnostic| diagnostic.fix.as_ref().map(|fix| fix.description.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_canonical_input_is_valid() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.add_part("eu-west")?;
+        let outcome = Ern::validate_verbose(&ern.to_string());
+        assert_eq!(outcome, ValidationOutcome::Valid(ern));
+        Ok(())
+    }
+
+    #[test]
+    fn test_suggests_fixes() {
+        let input = " ern:acton:HR:my team:root/a//b ";
+        let outcome = Ern::validate_verbose(input);
+        assert!(!outcome.is_valid());
+        assert_eq!(
+            descriptions(&outcome),
+            vec![
+                "remove leading whitespace",
+                "lowercase 'HR'",
+                "replace ' ' with '-'",
+                "remove the extra '/'",
+                "remove trailing whitespace",
+            ]
+        );
+        let fixed = outcome.apply_fixes(input);
+        assert_eq!(fixed, "ern:acton:hr:my-team:root/a/b");
+        assert!(Ern::validate_verbose(&fixed).is_valid());
+    }
+
+    #[test]
+    fn test_scheme_and_characters() {
+        let input = "ERN:acton:orders!:tenant:root/Shard#1";
+        let outcome = Ern::validate_verbose(input);
+        assert_eq!(
+            descriptions(&outcome),
+            vec![
+                "lowercase 'ERN'",
+                "remove '!'",
+                "lowercase 'S'",
+                "remove '#'"
+            ]
+        );
+        assert_eq!(
+            outcome.apply_fixes(input),
+            "ern:acton:orders:tenant:root/shard1"
+        );
+    }
+
+    #[test]
+    fn test_lowercases_like_the_parser() {
+        let input = "ern:acton:ÜBER:tenant:root";
+        let outcome = Ern::validate_verbose(input);
+        assert_eq!(descriptions(&outcome), vec!["lowercase 'ÜBER'"]);
+        assert_eq!(outcome.apply_fixes(input), "ern:acton:über:tenant:root");
+    }
+
+    #[test]
+    fn test_unfixable_problems() {
+        let outcome = Ern::validate_verbose("ern:acton");
+        assert_eq!(outcome.diagnostics().len(), 1);
+        assert_eq!(outcome.diagnostics()[0].fix, None);
+        assert_eq!(outcome.diagnostics()[0].span, 0..9);
+    }
+}
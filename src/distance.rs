@@ -0,0 +1,57 @@
+/// Returns the Levenshtein edit distance between two sequences.
+pub(crate) fn levenshtein<T: PartialEq>(left: &[T], right: &[T]) -> usize {
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    let mut current = vec![0; right.len() + 1];
+    for (i, l) in left.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, r) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(l != r);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[right.len()]
+}
+
+/// Returns the candidate closest to `value`, if any is near enough to be a plausible typo.
+///
+/// A candidate qualifies when its distance is at most a third of the longer string's length,
+/// rounded up; ties go to the earliest candidate.
+pub(crate) fn closest_match<'a>(
+    value: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let value: Vec<char> = value.chars().collect();
+    candidates
+        .into_iter()
+        .map(|candidate| {
+            let chars: Vec<char> = candidate.chars().collect();
+            let limit = value.len().max(chars.len()).div_ceil(3);
+            (levenshtein(&value, &chars), limit, candidate)
+        })
+        .filter(|(distance, limit, _)| distance <= limit)
+        .min_by_key(|(distance, _, _)| *distance)
+        .map(|(_, _, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(levenshtein(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(levenshtein(&chars(""), &chars("abc")), 3);
+        assert_eq!(levenshtein(&chars("actor"), &chars("actor")), 0);
+        assert_eq!(levenshtein(&["a", "b", "c"], &["a", "c"]), 1);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let known = ["actor", "agent", "reactive"];
+        assert_eq!(closest_match("actr", known), Some("actor"));
+        assert_eq!(closest_match("reactiv", known), Some("reactive"));
+        assert_eq!(closest_match("billing", known), None);
+    }
+}
@@ -0,0 +1,232 @@
+use std::borrow::Cow;
+
+use crate::config::ValidationConfig;
+use crate::errors::ErnError;
+use crate::model::{Account, Category, Domain, EntityRoot, Ern, Part, Parts};
+
+/// A single change applied by an [`ErnEditor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditOp {
+    SetDomain(Cow<'static, str>),
+    SetCategory(Cow<'static, str>),
+    SetAccount(Cow<'static, str>),
+    SetRoot(EntityRoot),
+    /// Replaces the part at the given index.
+    ReplacePart(usize, Cow<'static, str>),
+    /// Inserts a part before the given index; an index equal to the depth appends.
+    InsertPartAt(usize, Cow<'static, str>),
+    /// Removes the part at the given index.
+    RemovePart(usize),
+    /// Appends a part.
+    PushPart(Cow<'static, str>),
+    /// Keeps only the first `n` parts.
+    Truncate(usize),
+}
+
+/// Applies a sequence of edits to an ERN (Entity Resource Name) and validates the result once.
+///
+/// Edits operate on raw strings, so intermediate states may be invalid; only the final result is
+/// checked, and every violated constraint is reported together instead of failing on the first.
+#[derive(Debug)]
+pub struct ErnEditor {
+    domain: Cow<'static, str>,
+    category: Cow<'static, str>,
+    account: Cow<'static, str>,
+    root: EntityRoot,
+    parts: Vec<Cow<'static, str>>,
+    revision: Option<u64>,
+    validation: Option<ValidationConfig>,
+    errors: Vec<ErnError>,
+}
+
+impl ErnEditor {
+    /// Starts editing a copy of `ern`.
+    pub fn new(ern: &Ern) -> Self {
+        Self {
+            domain: ern.domain.0.clone(),
+            category: ern.category.0.clone(),
+            account: ern.account.0.clone(),
+            root: ern.root.clone(),
+            parts: (&ern.parts)
+                .into_iter()
+                .map(|part| part.0.clone())
+                .collect(),
+            revision: ern.revision(),
+            validation: None,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Also checks the final result against `config`.
+    pub fn validate_with(mut self, config: ValidationConfig) -> Self {
+        self.validation = Some(config);
+        self
+    }
+
+    /// Applies one edit.
+    pub fn apply(mut self, op: EditOp) -> Self {
+        let depth = self.parts.len();
+        match op {
+            EditOp::SetDomain(domain) => self.domain = domain,
+            EditOp::SetCategory(category) => self.category = category,
+            EditOp::SetAccount(account) => self.account = account,
+            EditOp::SetRoot(root) => self.root = root,
+            EditOp::ReplacePart(index, part) if index < depth => self.parts[index] = part,
+            EditOp::InsertPartAt(index, part) if index <= depth => self.parts.insert(index, part),
+            EditOp::RemovePart(index) if index < depth => {
+                self.parts.remove(index);
+            }
+            EditOp::PushPart(part) => self.parts.push(part),
+            EditOp::Truncate(n) => self.parts.truncate(n),
+            EditOp::ReplacePart(index, _)
+            | EditOp::InsertPartAt(index, _)
+            | EditOp::RemovePart(index) => {
+                self.errors.push(ErnError::InvalidEdit(format!(
+                    "part index {} is out of range for depth {}",
+                    index, depth
+                )));
+            }
+        }
+        self
+    }
+
+    /// Applies every edit in order.
+    pub fn apply_all(self, ops: impl IntoIterator<Item = EditOp>) -> Self {
+        ops.into_iter().fold(self, Self::apply)
+    }
+
+    pub fn set_domain(self, domain: impl Into<Cow<'static, str>>) -> Self {
+        self.apply(EditOp::SetDomain(domain.into()))
+    }
+
+    pub fn set_category(self, category: impl Into<Cow<'static, str>>) -> Self {
+        self.apply(EditOp::SetCategory(category.into()))
+    }
+
+    pub fn set_account(self, account: impl Into<Cow<'static, str>>) -> Self {
+        self.apply(EditOp::SetAccount(account.into()))
+    }
+
+    pub fn set_root(self, root: EntityRoot) -> Self {
+        self.apply(EditOp::SetRoot(root))
+    }
+
+    pub fn replace_part(self, index: usize, part: impl Into<Cow<'static, str>>) -> Self {
+        self.apply(EditOp::ReplacePart(index, part.into()))
+    }
+
+    pub fn insert_part_at(self, index: usize, part: impl Into<Cow<'static, str>>) -> Self {
+        self.apply(EditOp::InsertPartAt(index, part.into()))
+    }
+
+    pub fn remove_part(self, index: usize) -> Self {
+        self.apply(EditOp::RemovePart(index))
+    }
+
+    pub fn push_part(self, part: impl Into<Cow<'static, str>>) -> Self {
+        self.apply(EditOp::PushPart(part.into()))
+    }
+
+    pub fn truncate(self, n: usize) -> Self {
+        self.apply(EditOp::Truncate(n))
+    }
+
+    /// Validates the edited components and returns the new ERN (Entity Resource Name), or every violation found.
+    pub fn finish(self) -> Result<Ern, Vec<ErnError>> {
+        let mut errors = self.errors;
+
+        let domain = Domain::from_cow(self.domain)
+            .map_err(|e| errors.push(e))
+            .ok();
+        let parts: Vec<Part> = self
+            .parts
+            .into_iter()
+            .filter_map(|part| Part::from_cow(part).map_err(|e| errors.push(e)).ok())
+            .collect();
+
+        let Some(domain) = domain.filter(|_| errors.is_empty()) else {
+            return Err(errors);
+        };
+        let ern = Ern::new(
+            domain,
+            Category::from_cow(self.category),
+            Account::from_cow(self.account),
+            self.root,
+            Parts::new(parts),
+        );
+        let ern = match self.revision {
+            Some(revision) => ern.with_revision(revision),
+            None => ern,
+        };
+
+        if let Some(config) = &self.validation {
+            let violations = config.violations(&ern);
+            if !violations.is_empty() {
+                return Err(violations);
+            }
+        }
+        Ok(ern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_sequence() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.with_parts(["a", "b", "c"])?;
+        let edited = ErnEditor::new(&ern)
+            .set_account("tenant9")
+            .replace_part(1, "x")
+            .insert_part_at(0, "top")
+            .remove_part(3)
+            .finish()
+            .map_err(|errors| anyhow::anyhow!("{errors:?}"))?;
+        assert_eq!(edited.account.as_str(), "tenant9");
+        assert_eq!(edited.parts.to_string(), "top/a/x");
+        assert_eq!(edited.root, ern.root);
+        Ok(())
+    }
+
+    #[test]
+    fn test_intermediate_states_may_be_invalid() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?;
+        let edited = ErnEditor::new(&ern)
+            .set_domain("")
+            .set_domain("acton-internal")
+            .finish();
+        assert!(edited.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_all_violations_are_reported() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.with_parts(["a"])?;
+        let errors = ErnEditor::new(&ern)
+            .set_domain("")
+            .push_part("bad/part")
+            .remove_part(7)
+            .finish()
+            .unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0], ErnError::InvalidEdit(_)));
+        assert!(errors.contains(&ErnError::InvalidPartFormat));
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation_config_is_applied() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?;
+        let errors = ErnEditor::new(&ern)
+            .validate_with(ValidationConfig {
+                max_depth: Some(1),
+                ..Default::default()
+            })
+            .apply_all([EditOp::PushPart("a".into()), EditOp::PushPart("b".into())])
+            .finish()
+            .unwrap_err();
+        assert_eq!(errors, vec![ErnError::LimitExceeded("depth", 1, 2)]);
+        Ok(())
+    }
+}
@@ -25,6 +25,54 @@ pub enum ErnError {
     #[error("Ern has invalid format")]
     InvalidFormat,
 
+    #[error("Validation Error - {0} exceeds limit of {1} (got {2})")]
+    LimitExceeded(&'static str, usize, usize),
+
+    #[error("Validation Error - {0} contains invalid character {1:?}")]
+    InvalidCharacter(&'static str, char),
+
+    #[error("Validation Error - expected {0} {1:?}, got {2:?}")]
+    UnexpectedComponent(&'static str, &'static str, String),
+
+    #[error("Validation Error - unknown {0} `{1}`{}", suggestion(.2))]
+    UnknownComponent(&'static str, String, Option<String>),
+
+    #[error("Validation Error - {0} `{1}` is not in canonical form")]
+    NotCanonical(&'static str, String),
+
+    #[error("Validation Error - ERN does not have shape `{0}`: {1}")]
+    ShapeMismatch(String, String),
+
+    #[error("Provenance Error - {0}")]
+    ProvenanceMismatch(String),
+
+    #[error("Edit Error - {0}")]
+    InvalidEdit(String),
+
+    #[error("Wire Error - Unsupported layout version {0}")]
+    UnsupportedWireVersion(u8),
+
+    #[error("Revision counter overflowed")]
+    RevisionOverflow,
+
+    #[error("Cursor is malformed or was not produced by this crate")]
+    InvalidCursor,
+
+    #[error("Reservation Error - `{0}` is reserved by {1}")]
+    PrefixReserved(String, String),
+
+    #[error("Lease Error - `{0}` is leased by {1}")]
+    LeaseHeld(String, String),
+
+    #[error("Lease Error - fencing token {0} is stale or expired")]
+    StaleLease(u64),
+
+    #[error("Namegen Error - no unused name found after {0} attempts")]
+    NamesExhausted(usize),
+
+    #[error("Sketch Error - Cannot merge sketches with precision {0} and {1}")]
+    IncompatibleSketch(u8, u8),
+
     // Converted the Infallible implementation to ErnError
     #[error("Infallible error")]
     InfallibleError,
@@ -38,3 +86,130 @@ impl From<Infallible> for ErnError {
     }
 }
 
+
+fn suggestion(candidate: &Option<String>) -> String {
+    match candidate {
+        Some(candidate) => format!(", did you mean `{}`?", candidate),
+        None => String::new(),
+    }
+}
+
+impl ErnError {
+    /// Creates a [`ErnError::ParseFailure`] for `format` from any displayable message.
+    pub fn parse_failure(format: &'static str, message: impl ToString) -> Self {
+        ErnError::ParseFailure(format, message.to_string())
+    }
+
+    /// Returns a stable, snake_case name for the variant, suitable as a metric label.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ErnError::ParseFailure(..) => "parse_failure",
+            ErnError::IllegalPartFormat => "illegal_part_format",
+            ErnError::InvalidPrefix(_) => "invalid_prefix",
+            ErnError::UnexpectedPart(_) => "unexpected_part",
+            ErnError::InvalidPartFormat => "invalid_part_format",
+            ErnError::IdGenerationFailure(_) => "id_generation_failure",
+            ErnError::MissingPart(_) => "missing_part",
+            ErnError::InvalidFormat => "invalid_format",
+            ErnError::LimitExceeded(..) => "limit_exceeded",
+            ErnError::InvalidCharacter(..) => "invalid_character",
+            ErnError::UnexpectedComponent(..) => "unexpected_component",
+            ErnError::UnknownComponent(..) => "unknown_component",
+            ErnError::NotCanonical(..) => "not_canonical",
+            ErnError::ShapeMismatch(..) => "shape_mismatch",
+            ErnError::ProvenanceMismatch(_) => "provenance_mismatch",
+            ErnError::InvalidEdit(_) => "invalid_edit",
+            ErnError::UnsupportedWireVersion(_) => "unsupported_wire_version",
+            ErnError::RevisionOverflow => "revision_overflow",
+            ErnError::InvalidCursor => "invalid_cursor",
+            ErnError::PrefixReserved(..) => "prefix_reserved",
+            ErnError::LeaseHeld(..) => "lease_held",
+            ErnError::StaleLease(_) => "stale_lease",
+            ErnError::NamesExhausted(_) => "names_exhausted",
+            ErnError::IncompatibleSketch(..) => "incompatible_sketch",
+            ErnError::InfallibleError => "infallible",
+            ErnError::EntityRootError(_) => "entity_root",
+        }
+    }
+
+    /// Returns the HTTP status code that best describes this error.
+    ///
+    /// Malformed input maps to `400 Bad Request`, well-formed input that fails a semantic check maps
+    /// to `422 Unprocessable Entity`, a claim on state held by someone else maps to `409 Conflict`,
+    /// and failures that are not caused by the caller's input map to
+    /// `500 Internal Server Error`.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            ErnError::ParseFailure(..)
+            | ErnError::IllegalPartFormat
+            | ErnError::InvalidPrefix(_)
+            | ErnError::UnexpectedPart(_)
+            | ErnError::InvalidPartFormat
+            | ErnError::MissingPart(_)
+            | ErnError::InvalidFormat
+            | ErnError::InvalidCursor
+            | ErnError::InvalidCharacter(..)
+            | ErnError::InvalidEdit(_)
+            | ErnError::UnsupportedWireVersion(_)
+            | ErnError::EntityRootError(_) => 400,
+            ErnError::LimitExceeded(..)
+            | ErnError::UnexpectedComponent(..)
+            | ErnError::UnknownComponent(..)
+            | ErnError::NotCanonical(..)
+            | ErnError::ShapeMismatch(..)
+            | ErnError::ProvenanceMismatch(_) => 422,
+            ErnError::PrefixReserved(..)
+            | ErnError::LeaseHeld(..)
+            | ErnError::StaleLease(_)
+            | ErnError::NamesExhausted(_) => 409,
+            ErnError::IdGenerationFailure(_)
+            | ErnError::IncompatibleSketch(..)
+            | ErnError::RevisionOverflow
+            | ErnError::InfallibleError => 500,
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+impl axum_core::response::IntoResponse for ErnError {
+    fn into_response(self) -> axum_core::response::Response {
+        let status = http::StatusCode::from_u16(self.http_status())
+            .unwrap_or(http::StatusCode::INTERNAL_SERVER_ERROR);
+        (status, self.to_string()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syntax_errors_map_to_bad_request() {
+        assert_eq!(ErnError::InvalidFormat.http_status(), 400);
+        assert_eq!(ErnError::InvalidPartFormat.http_status(), 400);
+        assert_eq!(ErnError::MissingPart("root".to_string()).http_status(), 400);
+    }
+
+    #[test]
+    fn test_limit_violations_map_to_unprocessable_entity() {
+        assert_eq!(ErnError::LimitExceeded("depth", 1, 2).http_status(), 422);
+    }
+
+    #[test]
+    fn test_internal_errors_map_to_server_error() {
+        assert_eq!(
+            ErnError::IdGenerationFailure("boom".to_string()).http_status(),
+            500
+        );
+        assert_eq!(ErnError::InfallibleError.http_status(), 500);
+    }
+
+    #[test]
+    fn test_kind_is_snake_case_variant_name() {
+        assert_eq!(ErnError::InvalidFormat.kind(), "invalid_format");
+        assert_eq!(
+            ErnError::LimitExceeded("depth", 1, 2).kind(),
+            "limit_exceeded"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::instrumentation;
+use crate::model::Ern;
+use crate::pattern::{CompiledErnPattern, ErnPattern};
+
+/// The number of compiled patterns kept per thread before the cache is cleared.
+const CACHE_CAPACITY: usize = 64;
+
+thread_local! {
+    /// Compiled patterns by their text; `None` records a pattern that does not parse.
+    static PATTERN_CACHE: RefCell<HashMap<String, Option<Rc<CompiledErnPattern>>>> = RefCell::new(HashMap::new());
+}
+
+/// Returns the compiled form of `pattern`, reusing a cached copy when one exists on this thread.
+fn cached(pattern: &str) -> Option<Rc<CompiledErnPattern>> {
+    PATTERN_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(compiled) = cache.get(pattern) {
+            instrumentation::increment(instrumentation::CACHE_HITS, Some("glob"));
+            return compiled.clone();
+        }
+        instrumentation::increment(instrumentation::CACHE_MISSES, Some("glob"));
+        if cache.len() >= CACHE_CAPACITY {
+            cache.clear();
+        }
+        let compiled = ErnPattern::parse(pattern)
+            .ok()
+            .map(|pattern| Rc::new(pattern.compile()));
+        cache.insert(pattern.to_string(), compiled.clone());
+        compiled
+    })
+}
+
+impl Ern {
+    /// Tests the ERN (Entity Resource Name) against an [`ErnPattern`] given as text, such as
+    /// `ern:acton:*:*:orders/**`.
+    ///
+    /// The pattern has the same meaning as with [`ErnPattern::parse`]; a pattern that does not
+    /// parse matches nothing. Compiled patterns are cached per thread, so repeating a pattern at a
+    /// call site is cheap; use a [`CompiledErnPattern`] directly to handle parse errors.
+    pub fn matches_glob(&self, pattern: &str) -> bool {
+        cached(pattern).is_some_and(|compiled| compiled.matches(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_glob_on_ern() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["region1", "shard2"])?;
+        assert!(ern.matches_glob("ern:acton:reactive:*:orders/**"));
+        assert!(ern.matches_glob("ern:*:*:*:*/*/shard2"));
+        assert!(!ern.matches_glob("ern:acton:reactive:*:users/**"));
+        assert!(!ern.matches_glob("ern:acton:billing:*:*/**"));
+        assert!(!ern.matches_glob("ern:acton:reactive:*:*"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_like_the_pattern() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["region1", "shard2"])?;
+        for pattern in [
+            "ern:acton:*:*:orders/**/shard2",
+            "ern:*:*:*:*/*",
+            "ern:*:*:*:*/**/**/*",
+        ] {
+            assert_eq!(
+                ern.matches_glob(pattern),
+                ErnPattern::parse(pattern)?.matches(&ern),
+                "{pattern}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_patterns_match_nothing() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.add_part("region1")?;
+        assert!(!ern.matches_glob("ern:acton:reactive:*:orders_*/**"));
+        assert!(!ern.matches_glob("ern:acton:**"));
+        assert!(!ern.matches_glob("not a pattern"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_reuses_compiled_patterns() {
+        let first = cached("ern:*:*:*:*/**").expect("valid pattern");
+        let second = cached("ern:*:*:*:*/**").expect("valid pattern");
+        assert!(Rc::ptr_eq(&first, &second));
+        assert!(cached("ern:**").is_none());
+    }
+}
@@ -0,0 +1,225 @@
+// `Status` is large, but it is the error type tonic interceptors must return
+#![allow(clippy::result_large_err)]
+
+use std::sync::Arc;
+
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+use crate::errors::ErnError;
+use crate::header::ERN_HEADER_NAME;
+use crate::model::{CanonicalErn, Ern};
+
+/// The metadata header carrying the canonical ERN (Entity Resource Name) of a request's resource.
+///
+/// This is the same header REST services use, so the ERN survives gRPC-to-HTTP transcoding.
+pub const ERN_METADATA_KEY: &str = ERN_HEADER_NAME;
+
+/// The metadata header carrying the signature of [`ERN_METADATA_KEY`], when a signer is configured.
+pub const ERN_SIGNATURE_METADATA_KEY: &str = "x-acton-ern-signature";
+
+/// Signs and verifies propagated ERNs (Entity Resource Names), for example with an HMAC shared between services.
+///
+/// Signatures must be printable ASCII so they fit in a metadata header.
+pub trait ErnSigner: Send + Sync {
+    fn sign(&self, ern: &str) -> String;
+    fn verify(&self, ern: &str, signature: &str) -> bool;
+}
+
+/// Writes `ern`, and its signature if `signer` is given, into the metadata of `request`.
+///
+/// The ERN is encoded with [`Ern::to_header_value`]. Fails with `invalid_argument` if the
+/// signature is not valid ASCII header text.
+pub fn inject_ern<T>(
+    request: &mut Request<T>,
+    ern: &CanonicalErn,
+    signer: Option<&dyn ErnSigner>,
+) -> Result<(), Status> {
+    let text = ern.to_header_value();
+    let metadata = request.metadata_mut();
+    if let Some(signer) = signer {
+        metadata.insert(
+            ERN_SIGNATURE_METADATA_KEY,
+            header_value(signer.sign(&text))?,
+        );
+    }
+    metadata.insert(ERN_METADATA_KEY, header_value(text)?);
+    Ok(())
+}
+
+/// Reads and validates the ERN (Entity Resource Name) in the metadata of `request`, returning `None` if it has none.
+///
+/// Fails with `invalid_argument` if the header is not a canonical ERN, and with `unauthenticated`
+/// if `signer` is given and the signature is missing or does not verify.
+pub fn extract_ern<T>(
+    request: &Request<T>,
+    signer: Option<&dyn ErnSigner>,
+) -> Result<Option<CanonicalErn>, Status> {
+    let metadata = request.metadata();
+    let Some(value) = metadata.get(ERN_METADATA_KEY) else {
+        return Ok(None);
+    };
+    let text = value
+        .to_str()
+        .map_err(|_| Status::invalid_argument(format!("`{ERN_METADATA_KEY}` is not ASCII")))?;
+    if let Some(signer) = signer {
+        let signature = metadata
+            .get(ERN_SIGNATURE_METADATA_KEY)
+            .and_then(|signature| signature.to_str().ok());
+        if !signature.is_some_and(|signature| signer.verify(text, signature)) {
+            return Err(Status::unauthenticated(format!(
+                "`{ERN_METADATA_KEY}` has a missing or invalid signature"
+            )));
+        }
+    }
+    // Canonical form means the sender cannot smuggle alternate spellings of the same resource
+    let ern = Ern::from_header_value(text).map_err(invalid)?;
+    CanonicalErn::new(ern).map(Some).map_err(invalid)
+}
+
+fn header_value(text: String) -> Result<MetadataValue<Ascii>, Status> {
+    text.parse()
+        .map_err(|_| Status::invalid_argument("ERN metadata must be ASCII header text"))
+}
+
+fn invalid(error: ErnError) -> Status {
+    Status::invalid_argument(format!("`{ERN_METADATA_KEY}`: {error}"))
+}
+
+/// A client interceptor that adds one ERN (Entity Resource Name) to every outgoing request.
+#[derive(Clone)]
+pub struct ErnInjector {
+    ern: CanonicalErn,
+    signer: Option<Arc<dyn ErnSigner>>,
+}
+
+impl ErnInjector {
+    pub fn new(ern: CanonicalErn) -> Self {
+        Self { ern, signer: None }
+    }
+
+    /// Signs the propagated ERN with `signer`.
+    pub fn signed(mut self, signer: Arc<dyn ErnSigner>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+}
+
+impl Interceptor for ErnInjector {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        inject_ern(&mut request, &self.ern, self.signer.as_deref())?;
+        Ok(request)
+    }
+}
+
+/// A server interceptor that validates the incoming ERN (Entity Resource Name) and stores it in the request extensions.
+///
+/// Handlers read it with `request.extensions().get::<CanonicalErn>()`.
+#[derive(Clone, Default)]
+pub struct ErnExtractor {
+    signer: Option<Arc<dyn ErnSigner>>,
+    required: bool,
+}
+
+impl ErnExtractor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires a valid signature from `signer` on every incoming ERN.
+    pub fn signed(mut self, signer: Arc<dyn ErnSigner>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Rejects requests without an ERN with `invalid_argument` instead of passing them through.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+}
+
+impl Interceptor for ErnExtractor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        match extract_ern(&request, self.signer.as_deref())? {
+            Some(ern) => {
+                request.extensions_mut().insert(ern);
+            }
+            None if self.required => {
+                return Err(Status::invalid_argument(format!(
+                    "missing `{ERN_METADATA_KEY}`"
+                )));
+            }
+            None => {}
+        }
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    /// Stands in for an HMAC in tests.
+    struct Reversed;
+
+    impl ErnSigner for Reversed {
+        fn sign(&self, ern: &str) -> String {
+            ern.chars().rev().collect()
+        }
+        fn verify(&self, ern: &str, signature: &str) -> bool {
+            self.sign(ern) == signature
+        }
+    }
+
+    #[test]
+    fn test_propagates_signed_ern() -> anyhow::Result<()> {
+        let ern = CanonicalErn::new(Ern::with_root("orders")?.add_part("eu")?)?;
+        let signer: Arc<dyn ErnSigner> = Arc::new(Reversed);
+        let request = ErnInjector::new(ern.clone())
+            .signed(signer.clone())
+            .call(Request::new(()))?;
+        let request = ErnExtractor::new().signed(signer).call(request)?;
+        assert_eq!(request.extensions().get::<CanonicalErn>(), Some(&ern));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_bad_signature_and_non_canonical_erns() -> anyhow::Result<()> {
+        let ern = CanonicalErn::new(Ern::with_root("orders")?)?;
+        let mut request = ErnInjector::new(ern).call(Request::new(()))?;
+        let unsigned = ErnExtractor::new().signed(Arc::new(Reversed)).call(request);
+        assert_eq!(
+            unsigned.map_err(|status| status.code()).err(),
+            Some(Code::Unauthenticated)
+        );
+
+        let text = Ern::with_root("orders")?
+            .to_string()
+            .replace("acton", "Acton");
+        request = Request::new(());
+        request
+            .metadata_mut()
+            .insert(ERN_METADATA_KEY, text.parse()?);
+        assert_eq!(
+            extract_ern(&request, None).map_err(|status| status.code()),
+            Err(Code::InvalidArgument)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_ern_is_optional_unless_required() -> anyhow::Result<()> {
+        let request = ErnExtractor::new().call(Request::new(()))?;
+        assert!(request.extensions().get::<CanonicalErn>().is_none());
+        let required = ErnExtractor::new().required().call(Request::new(()));
+        assert_eq!(
+            required.map_err(|status| status.code()).err(),
+            Some(Code::InvalidArgument)
+        );
+        Ok(())
+    }
+}
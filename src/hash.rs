@@ -0,0 +1,56 @@
+/// Computes a 64-bit hash of `bytes` that is stable across processes, platforms, and releases.
+///
+/// FNV-1a is used for its simplicity, followed by the MurmurHash3 finalizer so that every
+/// output bit depends on every input bit (required by estimators that slice the hash).
+pub(crate) fn stable_hash64(bytes: &[u8]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    fmix64(hash)
+}
+
+fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    k ^= k >> 33;
+    k
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stable_hash_is_deterministic() {
+        assert_eq!(
+            stable_hash64(b"ern:acton:hr:acct:root"),
+            stable_hash64(b"ern:acton:hr:acct:root")
+        );
+        assert_ne!(stable_hash64(b"a"), stable_hash64(b"b"));
+    }
+
+    #[test]
+    fn test_stable_hash_known_value() {
+        // Pinned so an accidental algorithm change is caught; persisted hashes depend on it.
+        assert_eq!(stable_hash64(b""), 0xefd0_1f60_ba99_2926);
+        assert_eq!(stable_hash64(b"a"), 0x82a2_a958_a9be_ce5b);
+        assert_eq!(stable_hash64(b"b"), 0x6e67_3288_764a_d2d0);
+        assert_eq!(
+            stable_hash64(b"ern:acton:hr:acct:root"),
+            0xed8d_31bc_eb8f_49d3
+        );
+        assert_eq!(
+            stable_hash64(
+                b"ern:acton:reactive:component:orders_01h455vb4pex5vsknk084sn02q/region1"
+            ),
+            0x7fb1_6c70_31c0_0e93
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+
+/// The HTTP header that carries the ERN (Entity Resource Name) of the resource a request acts on.
+pub const ERN_HEADER_NAME: &str = "x-acton-ern";
+
+/// Marks a header value holding the base32-encoded UTF-8 of an ERN that is not plain visible ASCII.
+pub const BASE32_HEADER_PREFIX: &str = "b32:";
+
+const BASE32: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+impl Ern {
+    /// Encodes the ERN (Entity Resource Name) as an HTTP header value, for [`ERN_HEADER_NAME`].
+    ///
+    /// ERNs made only of visible ASCII are sent as is, which covers everything built from ASCII
+    /// components. Anything else, including spaces that proxies may fold or trim, is sent as
+    /// [`BASE32_HEADER_PREFIX`] followed by unpadded lowercase base32 (RFC 4648) of its UTF-8.
+    pub fn to_header_value(&self) -> String {
+        let text = self.to_string();
+        if text.bytes().all(|b| b.is_ascii_graphic()) {
+            text
+        } else {
+            format!("{BASE32_HEADER_PREFIX}{}", encode_base32(text.as_bytes()))
+        }
+    }
+
+    /// Decodes a header value produced by [`Ern::to_header_value`], in either form.
+    ///
+    /// Surrounding whitespace, which HTTP does not consider part of the value, is ignored.
+    pub fn from_header_value(value: &str) -> Result<Self, ErnError> {
+        let value = value.trim_matches([' ', '\t']);
+        let text = match value.strip_prefix(BASE32_HEADER_PREFIX) {
+            Some(encoded) => {
+                let bytes = decode_base32(encoded)
+                    .ok_or_else(|| ErnError::parse_failure("header value", "invalid base32"))?;
+                String::from_utf8(bytes).map_err(|_| {
+                    ErnError::parse_failure("header value", "base32 payload is not UTF-8")
+                })?
+            }
+            None => value.to_string(),
+        };
+        ErnParser::new(text).parse()
+    }
+}
+
+fn encode_base32(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32[(buffer >> bits) as usize & 31] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32[(buffer << (5 - bits)) as usize & 31] as char);
+    }
+    encoded
+}
+
+fn decode_base32(encoded: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in encoded.bytes() {
+        let value = BASE32.iter().position(|&b| b == c.to_ascii_lowercase())? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    // Leftover bits are padding and must be zero
+    (buffer & ((1 << bits) - 1) == 0).then_some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_erns_are_sent_as_is() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.add_part("eu")?;
+        assert_eq!(ern.to_header_value(), ern.to_string());
+        assert_eq!(
+            Ern::from_header_value(&format!(" {} ", ern.to_header_value()))?,
+            ern
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_other_erns_fall_back_to_base32() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["zürich", "shard 2"])?;
+        let value = ern.to_header_value();
+        assert!(value.starts_with(BASE32_HEADER_PREFIX));
+        assert!(value.bytes().all(|b| b.is_ascii_graphic()));
+        assert_eq!(Ern::from_header_value(&value)?, ern);
+        assert_eq!(
+            Ern::from_header_value(&value.to_uppercase().replacen("B32", "b32", 1))?,
+            ern
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_base32_matches_rfc_4648() {
+        assert_eq!(encode_base32(b"foobar"), "mzxw6ytboi");
+        assert_eq!(decode_base32("mzxw6ytboi").as_deref(), Some(&b"foobar"[..]));
+        assert!(Ern::from_header_value("b32:!!").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Counters and histograms reported from inside the crate.
+//!
+//! With the `instrumentation` feature, install a [`MetricsSink`] once at startup with
+//! [`set_metrics_sink`] to receive them; without the feature every hook compiles to nothing.
+
+/// Counted once per call to [`ErnParser::parse_with_config`](crate::ErnParser::parse_with_config).
+pub const PARSE_CALLS: &str = "ern.parse.calls";
+/// Counted once per failed parse, labeled with the error's [`kind`](crate::prelude::ErnError::kind).
+pub const PARSE_FAILURES: &str = "ern.parse.failures";
+/// Counted once per violation found by [`ValidationConfig`](crate::ValidationConfig), labeled with its kind.
+pub const VALIDATION_FAILURES: &str = "ern.validation.failures";
+/// Counted when a lookup is served from a cache or pool, labeled `glob` or `builder_pool`.
+pub const CACHE_HITS: &str = "ern.cache.hits";
+/// Counted when a lookup misses a cache or pool, labeled `glob` or `builder_pool`.
+pub const CACHE_MISSES: &str = "ern.cache.misses";
+/// A histogram of the byte length of every parsed input.
+pub const PARSE_INPUT_BYTES: &str = "ern.parse.input_bytes";
+
+/// Receives the crate's metrics; implement it to forward them to Prometheus, StatsD, and so on.
+///
+/// Calls happen inline on hot paths, so implementations should be cheap and must not block.
+#[cfg(feature = "instrumentation")]
+pub trait MetricsSink: Send + Sync {
+    /// Adds one to the counter `name`, optionally split by `label`.
+    fn increment_counter(&self, name: &'static str, label: Option<&'static str>);
+
+    /// Records one observation of `value` in the histogram `name`.
+    fn record_histogram(&self, name: &'static str, value: f64);
+}
+
+#[cfg(feature = "instrumentation")]
+static SINK: std::sync::OnceLock<Box<dyn MetricsSink>> = std::sync::OnceLock::new();
+
+/// Installs the process-wide sink, returning it back if one is already installed.
+#[cfg(feature = "instrumentation")]
+pub fn set_metrics_sink(sink: Box<dyn MetricsSink>) -> Result<(), Box<dyn MetricsSink>> {
+    SINK.set(sink)
+}
+
+#[cfg(feature = "instrumentation")]
+pub(crate) fn increment(name: &'static str, label: Option<&'static str>) {
+    if let Some(sink) = SINK.get() {
+        sink.increment_counter(name, label);
+    }
+}
+
+#[cfg(feature = "instrumentation")]
+pub(crate) fn record(name: &'static str, value: f64) {
+    if let Some(sink) = SINK.get() {
+        sink.record_histogram(name, value);
+    }
+}
+
+#[cfg(not(feature = "instrumentation"))]
+#[inline(always)]
+pub(crate) fn increment(_name: &'static str, _label: Option<&'static str>) {}
+
+#[cfg(not(feature = "instrumentation"))]
+#[inline(always)]
+pub(crate) fn record(_name: &'static str, _value: f64) {}
+
+#[cfg(all(test, feature = "instrumentation"))]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::config::{ErnConfig, ValidationConfig};
+    use crate::model::Ern;
+    use crate::parser::ErnParser;
+
+    #[derive(Default)]
+    struct RecordingSink {
+        counters: Mutex<HashMap<(&'static str, Option<&'static str>), u64>>,
+        histograms: Mutex<Vec<(&'static str, f64)>>,
+    }
+
+    impl MetricsSink for &'static RecordingSink {
+        fn increment_counter(&self, name: &'static str, label: Option<&'static str>) {
+            *self
+                .counters
+                .lock()
+                .unwrap()
+                .entry((name, label))
+                .or_default() += 1;
+        }
+
+        fn record_histogram(&self, name: &'static str, value: f64) {
+            self.histograms.lock().unwrap().push((name, value));
+        }
+    }
+
+    #[test]
+    fn test_sink_receives_crate_metrics() -> anyhow::Result<()> {
+        let sink: &'static RecordingSink = Box::leak(Box::default());
+        assert!(set_metrics_sink(Box::new(sink)).is_ok());
+        assert!(set_metrics_sink(Box::new(sink)).is_err());
+
+        let count = |name, label| {
+            sink.counters
+                .lock()
+                .unwrap()
+                .get(&(name, label))
+                .copied()
+                .unwrap_or(0)
+        };
+        let calls = count(PARSE_CALLS, None);
+        let invalid = count(PARSE_FAILURES, Some("invalid_format"));
+        let limits = count(VALIDATION_FAILURES, Some("limit_exceeded"));
+
+        ErnParser::new("not an ern".to_string())
+            .parse()
+            .unwrap_err();
+        let config = ErnConfig {
+            validation: ValidationConfig {
+                max_depth: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let text = Ern::with_root("root")?.add_part("a")?.to_string();
+        ErnParser::new(text.clone())
+            .parse_with_config(&config)
+            .unwrap_err();
+
+        assert!(count(PARSE_CALLS, None) >= calls + 2);
+        assert!(count(PARSE_FAILURES, Some("invalid_format")) > invalid);
+        assert!(count(VALIDATION_FAILURES, Some("limit_exceeded")) > limits);
+        assert!(sink
+            .histograms
+            .lock()
+            .unwrap()
+            .contains(&(PARSE_INPUT_BYTES, text.len() as f64)));
+        Ok(())
+    }
+}
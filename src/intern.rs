@@ -0,0 +1,129 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use crate::model::{Ern, Parts};
+
+/// Supplies shared, `'static` copies of component strings so many ERNs (Entity Resource Names) can reference one allocation.
+pub trait ErnInterner {
+    /// Returns a `'static` string equal to `value`, reusing an earlier copy when one exists.
+    fn intern(&mut self, value: &str) -> &'static str;
+}
+
+/// An interner that leaks one allocation per distinct string and hands out references to it.
+///
+/// Suitable for long-lived registries whose component vocabulary is bounded; memory given to
+/// the interner is never returned.
+#[derive(Debug, Default)]
+pub struct LeakingInterner {
+    values: HashSet<&'static str>,
+}
+
+impl LeakingInterner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of distinct strings interned so far.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if nothing has been interned.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl ErnInterner for LeakingInterner {
+    fn intern(&mut self, value: &str) -> &'static str {
+        if let Some(existing) = self.values.get(value) {
+            return existing;
+        }
+        let leaked: &'static str = Box::leak(value.to_owned().into_boxed_str());
+        self.values.insert(leaked);
+        leaked
+    }
+}
+
+pub(crate) fn shrink_cow(value: &mut Cow<'static, str>) {
+    if let Cow::Owned(owned) = value {
+        owned.shrink_to_fit();
+    }
+}
+
+fn intern_cow(value: &mut Cow<'static, str>, interner: &mut impl ErnInterner) {
+    if let Cow::Owned(owned) = value {
+        *value = Cow::Borrowed(interner.intern(owned));
+    }
+}
+
+impl Parts {
+    /// Releases excess capacity held by the parts vector and each part.
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit();
+        for part in &mut self.0 {
+            shrink_cow(&mut part.0);
+        }
+    }
+}
+
+impl Ern {
+    /// Releases excess capacity held by the components of this ERN (Entity Resource Name).
+    pub fn shrink_to_fit(&mut self) {
+        shrink_cow(&mut self.domain.0);
+        shrink_cow(&mut self.category.0);
+        shrink_cow(&mut self.account.0);
+        self.parts.shrink_to_fit();
+    }
+
+    /// Replaces every owned domain, category, account, and part string with a shared copy from `interner`.
+    ///
+    /// Components that repeat across many ERNs then cost one allocation in total.
+    pub fn compact(&mut self, interner: &mut impl ErnInterner) {
+        intern_cow(&mut self.domain.0, interner);
+        intern_cow(&mut self.category.0, interner);
+        intern_cow(&mut self.account.0, interner);
+        for part in &mut self.parts.0 {
+            intern_cow(&mut part.0, interner);
+        }
+        self.parts.0.shrink_to_fit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::HeapSize;
+
+    #[test]
+    fn test_shrink_to_fit_releases_capacity() -> anyhow::Result<()> {
+        let mut padded = String::with_capacity(64);
+        padded.push_str("team1");
+        let mut ern = Ern::with_root("root")?.with_parts([padded])?;
+        let before = ern.parts.heap_size();
+        ern.shrink_to_fit();
+        assert!(ern.parts.heap_size() < before);
+        assert_eq!(ern.parts.to_string(), "team1");
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_shares_components() -> anyhow::Result<()> {
+        let mut interner = LeakingInterner::new();
+        let mut erns: Vec<Ern> = (0..10)
+            .map(|_| Ern::with_account("tenant-42".to_string())?.with_parts(["orders".to_string()]))
+            .collect::<Result<_, _>>()?;
+        for ern in &mut erns {
+            ern.compact(&mut interner);
+        }
+        assert_eq!(interner.len(), 2);
+        assert_eq!(erns[0].account.as_str(), "tenant-42");
+        assert!(std::ptr::eq(
+            erns[0].account.as_str(),
+            erns[9].account.as_str()
+        ));
+        assert_eq!(erns[0].account.heap_size(), 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use serde_json::{json, Map, Value};
+
+use crate::errors::ErnError;
+use crate::model::{Ern, Rfc3339};
+use crate::wire::ErnWire;
+
+impl Ern {
+    /// Returns the canonical JSON object for this ERN (Entity Resource Name):
+    ///
+    /// ```json
+    /// {
+    ///   "scheme": "ern",
+    ///   "domain": "acton",
+    ///   "category": "reactive",
+    ///   "account": "component",
+    ///   "root": { "name": "orders_01h455vb4pex5vsknk084sn02q", "ts": "2023-07-01T12:00:00.000Z" },
+    ///   "parts": ["eu", "shard-2"]
+    /// }
+    /// ```
+    ///
+    /// `root.ts` is the RFC 3339 creation time embedded in a time-based root id, or `null`. A
+    /// `revision` number is added only when the ERN has one.
+    pub fn to_json_value(&self) -> Value {
+        let wire = ErnWire::from(self);
+        let ts = self
+            .root
+            .timestamp()
+            .map(|created| Rfc3339(created).to_string());
+        let mut object = json!({
+            "scheme": "ern",
+            "domain": wire.domain,
+            "category": wire.category,
+            "account": wire.account,
+            "root": { "name": wire.root, "ts": ts },
+            "parts": wire.parts,
+        });
+        if let Some(revision) = wire.revision {
+            object["revision"] = json!(revision);
+        }
+        object
+    }
+
+    /// Decodes the object produced by [`Ern::to_json_value`], keeping the root id.
+    ///
+    /// `root.ts` is derived from the root id, so it is not required and is ignored if present.
+    pub fn from_json_value(value: &Value) -> Result<Self, ErnError> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| ErnError::parse_failure("json", "expected an object"))?;
+        let scheme = string_field(object, "scheme")?;
+        if scheme != "ern" {
+            return Err(ErnError::parse_failure(
+                "json",
+                format!("unsupported scheme `{scheme}`"),
+            ));
+        }
+        let root = object
+            .get("root")
+            .and_then(Value::as_object)
+            .ok_or_else(|| ErnError::parse_failure("json", "`root` must be an object"))?;
+        let parts: Vec<String> = match object.get("parts") {
+            None => Vec::new(),
+            Some(parts) => parts
+                .as_array()
+                .ok_or_else(|| ErnError::parse_failure("json", "`parts` must be an array"))?
+                .iter()
+                .map(|part| {
+                    part.as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| ErnError::parse_failure("json", "parts must be strings"))
+                })
+                .collect::<Result<_, _>>()?,
+        };
+        let revision = match object.get("revision") {
+            None | Some(Value::Null) => None,
+            Some(revision) => Some(revision.as_u64().ok_or_else(|| {
+                ErnError::parse_failure("json", "`revision` must be a non-negative integer")
+            })?),
+        };
+        let components = [
+            string_field(object, "domain")?,
+            string_field(object, "category")?,
+            string_field(object, "account")?,
+            string_field(root, "name")?,
+        ];
+        Ern::try_from(ErnWire {
+            revision,
+            ..ErnWire::from_segments("json", components.into_iter().chain(parts))?
+        })
+    }
+}
+
+fn string_field(object: &Map<String, Value>, name: &str) -> Result<String, ErnError> {
+    object
+        .get(name)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| ErnError::parse_failure("json", format!("`{name}` must be a string")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_shape() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["eu", "shard-2"])?;
+        let value = ern.to_json_value();
+        assert_eq!(value["scheme"], "ern");
+        assert_eq!(value["domain"], "acton");
+        assert_eq!(value["category"], "reactive");
+        assert_eq!(value["account"], "component");
+        assert_eq!(value["root"]["name"], ern.root.as_str());
+        assert!(value["root"]["ts"]
+            .as_str()
+            .is_some_and(|ts| ts.ends_with('Z')));
+        assert_eq!(value["parts"], json!(["eu", "shard-2"]));
+        assert!(value.get("revision").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.add_part("eu")?.with_revision(4);
+        let value = ern.to_json_value();
+        assert_eq!(value["revision"], 4);
+        assert_eq!(Ern::from_json_value(&value)?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_rejects_malformed_objects() -> anyhow::Result<()> {
+        let mut value = Ern::with_root("orders")?.to_json_value();
+        value["scheme"] = json!("urn");
+        assert!(matches!(
+            Ern::from_json_value(&value),
+            Err(ErnError::ParseFailure("json", _))
+        ));
+        assert!(Ern::from_json_value(&json!("ern:a:b:c:root")).is_err());
+        assert!(Ern::from_json_value(&json!({"scheme": "ern", "domain": "a"})).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::wire::ErnWire;
+
+const ESCAPE: u8 = 0x00;
+const ESCAPED_ZERO: u8 = 0xFF;
+const TERMINATOR: u8 = 0x01;
+
+/// An order-preserving byte encoding of an ERN (Entity Resource Name) for embedded key-value stores.
+///
+/// Components are encoded in hierarchy order, each terminated so that a parent's key is a byte
+/// prefix of every descendant's and sorts before them; comparing keys bytewise therefore groups
+/// each subtree contiguously, and [`ErnKey::subtree`] yields the range for a hierarchical scan.
+/// The revision is not part of the key.
+///
+/// With the `redb` feature this implements `redb::Key`; for sled, pass [`ErnKey::as_bytes`]
+/// anywhere a key is expected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ErnKey<'a>(Cow<'a, [u8]>);
+
+impl ErnKey<'static> {
+    /// Encodes `ern` as a key.
+    pub fn new(ern: &Ern) -> Self {
+        let wire = ErnWire::from(ern);
+        let mut bytes = Vec::with_capacity(ern.to_string().len() + 2 * (4 + wire.parts.len()));
+        for component in wire.segments() {
+            for &byte in component.as_bytes() {
+                bytes.push(byte);
+                if byte == ESCAPE {
+                    bytes.push(ESCAPED_ZERO);
+                }
+            }
+            bytes.extend_from_slice(&[ESCAPE, TERMINATOR]);
+        }
+        ErnKey(Cow::Owned(bytes))
+    }
+
+    /// Returns the key range holding `ern` and all of its descendants.
+    pub fn subtree(ern: &Ern) -> Range<ErnKey<'static>> {
+        let start = Self::new(ern);
+        let mut end = start.0.to_vec();
+        // Raising the final terminator gives a bound above every key extending this one and below
+        // any sibling, because only the terminator or the larger escaped zero can follow an escape.
+        if let Some(last) = end.last_mut() {
+            *last = TERMINATOR + 1;
+        }
+        start..ErnKey(Cow::Owned(end))
+    }
+}
+
+impl<'a> ErnKey<'a> {
+    /// Wraps encoded bytes without copying or validating them; [`ErnKey::to_ern`] validates.
+    pub fn from_bytes(bytes: &'a [u8]) -> Self {
+        ErnKey(Cow::Borrowed(bytes))
+    }
+
+    /// Returns the encoded bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Detaches the key from the buffer it was decoded from.
+    pub fn into_owned(self) -> ErnKey<'static> {
+        ErnKey(Cow::Owned(self.0.into_owned()))
+    }
+
+    /// Decodes the key back into an ERN (Entity Resource Name), keeping its root id.
+    pub fn to_ern(&self) -> Result<Ern, ErnError> {
+        let mut components = Vec::new();
+        let mut current = Vec::new();
+        let mut bytes = self.0.iter();
+        while let Some(&byte) = bytes.next() {
+            if byte != ESCAPE {
+                current.push(byte);
+                continue;
+            }
+            match bytes.next() {
+                Some(&ESCAPED_ZERO) => current.push(ESCAPE),
+                Some(&TERMINATOR) => {
+                    let component = String::from_utf8(std::mem::take(&mut current))
+                        .map_err(|e| ErnError::ParseFailure("key", e.to_string()))?;
+                    components.push(component);
+                }
+                _ => {
+                    return Err(ErnError::ParseFailure(
+                        "key",
+                        "invalid escape sequence".to_string(),
+                    ))
+                }
+            }
+        }
+        if !current.is_empty() || components.len() < 4 {
+            return Err(ErnError::ParseFailure("key", "truncated key".to_string()));
+        }
+        Ern::try_from(ErnWire::from_segments("key", components)?)
+    }
+}
+
+impl From<&Ern> for ErnKey<'static> {
+    fn from(ern: &Ern) -> Self {
+        Self::new(ern)
+    }
+}
+
+impl AsRef<[u8]> for ErnKey<'_> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "redb")]
+impl redb::Value for ErnKey<'static> {
+    type SelfType<'a> = ErnKey<'a>;
+    type AsBytes<'a> = &'a [u8];
+
+    fn fixed_width() -> Option<usize> {
+        None
+    }
+
+    fn from_bytes<'a>(data: &'a [u8]) -> Self::SelfType<'a>
+    where
+        Self: 'a,
+    {
+        ErnKey::from_bytes(data)
+    }
+
+    fn as_bytes<'a, 'b: 'a>(value: &'a Self::SelfType<'b>) -> Self::AsBytes<'a>
+    where
+        Self: 'b,
+    {
+        value.as_bytes()
+    }
+
+    fn type_name() -> redb::TypeName {
+        redb::TypeName::new("acton_ern::ErnKey")
+    }
+}
+
+#[cfg(feature = "redb")]
+impl redb::Key for ErnKey<'static> {
+    fn compare(data1: &[u8], data2: &[u8]) -> std::cmp::Ordering {
+        data1.cmp(data2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.with_parts(["a", "b c"])?;
+        let key = ErnKey::new(&ern);
+        assert_eq!(ErnKey::from_bytes(key.as_bytes()).to_ern()?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_keys_sort_hierarchically() -> anyhow::Result<()> {
+        let root = Ern::with_root("root")?;
+        let a = root.add_part("a")?;
+        let a_child = a.add_part("z")?;
+        let ab = root.add_part("ab")?;
+        let with_zero = root.add_part("a\0")?;
+
+        let mut keys = [&ab, &a_child, &with_zero, &root, &a].map(ErnKey::new);
+        keys.sort();
+        let expected = [&root, &a, &a_child, &with_zero, &ab].map(ErnKey::new);
+        assert_eq!(keys, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_subtree_range() -> anyhow::Result<()> {
+        let root = Ern::with_root("root")?;
+        let a = root.add_part("a")?;
+        let range = ErnKey::subtree(&a);
+        assert!(range.contains(&ErnKey::new(&a)));
+        assert!(range.contains(&ErnKey::new(&a.add_part("x")?.add_part("y")?)));
+        assert!(!range.contains(&ErnKey::new(&root)));
+        assert!(!range.contains(&ErnKey::new(&root.add_part("ab")?)));
+        assert!(!range.contains(&ErnKey::new(&root.add_part("a\0")?)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_malformed_keys_are_rejected() {
+        assert!(matches!(
+            ErnKey::from_bytes(b"acton\0\x01").to_ern(),
+            Err(ErnError::ParseFailure("key", _))
+        ));
+        assert!(matches!(
+            ErnKey::from_bytes(b"a\0\x07").to_ern(),
+            Err(ErnError::ParseFailure("key", _))
+        ));
+    }
+
+    #[cfg(feature = "redb")]
+    #[test]
+    fn test_redb_range_scan() -> anyhow::Result<()> {
+        use redb::{Database, TableDefinition};
+
+        const TABLE: TableDefinition<ErnKey<'static>, u32> = TableDefinition::new("erns");
+        let db = Database::builder().create_with_backend(redb::backends::InMemoryBackend::new())?;
+        let root = Ern::with_root("root")?;
+        let a = root.add_part("a")?;
+        let txn = db.begin_write()?;
+        {
+            let mut table = txn.open_table(TABLE)?;
+            table.insert(ErnKey::new(&root), 0)?;
+            table.insert(ErnKey::new(&a), 1)?;
+            table.insert(ErnKey::new(&a.add_part("x")?), 2)?;
+            table.insert(ErnKey::new(&root.add_part("b")?), 3)?;
+        }
+        txn.commit()?;
+
+        let txn = db.begin_read()?;
+        let table = txn.open_table(TABLE)?;
+        let range = ErnKey::subtree(&a);
+        let values: Vec<u32> = table
+            .range(range.start..range.end)?
+            .map(|entry| entry.map(|(_, value)| value.value()))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(values, vec![1, 2]);
+        Ok(())
+    }
+}
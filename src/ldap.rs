@@ -0,0 +1,203 @@
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::wire::ErnWire;
+
+/// The attribute types used for each component when converting to and from LDAP distinguished names.
+///
+/// RDNs are written most specific first, so the last part comes first and the domain last:
+/// `ou=b,ou=a,cn=root_…,o=tenant9,ou=orders,dc=acton`. Decoding is positional, so the same
+/// attribute type may be reused for several components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnMapping {
+    pub domain: &'static str,
+    pub category: &'static str,
+    pub account: &'static str,
+    pub root: &'static str,
+    pub part: &'static str,
+}
+
+impl Default for DnMapping {
+    fn default() -> Self {
+        Self {
+            domain: "dc",
+            category: "ou",
+            account: "o",
+            root: "cn",
+            part: "ou",
+        }
+    }
+}
+
+impl DnMapping {
+    /// Returns the attribute type of the segment at `index` in [`ErnWire::segments`] order.
+    fn attribute(&self, index: usize) -> &'static str {
+        [self.domain, self.category, self.account, self.root]
+            .get(index)
+            .copied()
+            .unwrap_or(self.part)
+    }
+}
+
+impl Ern {
+    /// Converts this ERN (Entity Resource Name) into an LDAP distinguished name, escaping values per RFC 4514.
+    pub fn to_ldap_dn(&self, mapping: &DnMapping) -> String {
+        let wire = ErnWire::from(self);
+        let mut rdns: Vec<String> = wire
+            .segments()
+            .enumerate()
+            .map(|(index, value)| format!("{}={}", mapping.attribute(index), escape_value(value)))
+            .collect();
+        rdns.reverse();
+        rdns.join(",")
+    }
+
+    /// Parses a distinguished name produced by [`Ern::to_ldap_dn`] with the same mapping.
+    ///
+    /// Attribute types are compared case-insensitively; multi-valued RDNs are rejected.
+    pub fn from_ldap_dn(dn: &str, mapping: &DnMapping) -> Result<Self, ErnError> {
+        let rdns = split_unescaped(dn, ',')
+            .into_iter()
+            .rev()
+            .map(|rdn| {
+                if split_unescaped(rdn, '+').len() > 1 {
+                    return Err(ErnError::parse_failure(
+                        "dn",
+                        "multi-valued RDNs are not supported",
+                    ));
+                }
+                let (attribute, value) = rdn.split_once('=').ok_or_else(|| {
+                    ErnError::parse_failure(
+                        "dn",
+                        format!("`{}` is not an attribute=value pair", rdn),
+                    )
+                })?;
+                Ok((attribute.trim(), unescape_value(value)?))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter();
+
+        let segments = rdns
+            .enumerate()
+            .map(|(index, (found, value))| {
+                let attribute = mapping.attribute(index);
+                if found.eq_ignore_ascii_case(attribute) {
+                    Ok(value)
+                } else {
+                    Err(ErnError::parse_failure(
+                        "dn",
+                        format!("expected `{}`, found `{}`", attribute, found),
+                    ))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ern::try_from(ErnWire::from_segments("dn", segments)?)
+    }
+}
+
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let last = value.chars().count().saturating_sub(1);
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            '"' | '+' | ',' | ';' | '<' | '>' | '\\' | '=' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\0' => escaped.push_str("\\00"),
+            ' ' if i == 0 || i == last => escaped.push_str("\\ "),
+            '#' if i == 0 => escaped.push_str("\\#"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape_value(value: &str) -> Result<String, ErnError> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut chars = value.char_indices();
+    while let Some((_, c)) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some((i, next)) if next.is_ascii_hexdigit() => {
+                let hex = value
+                    .get(i..i + 2)
+                    .ok_or_else(|| ErnError::parse_failure("dn", "truncated hex escape"))?;
+                let byte = u8::from_str_radix(hex, 16)
+                    .map_err(|_| ErnError::parse_failure("dn", "invalid hex escape"))?;
+                bytes.push(byte);
+                chars.next();
+            }
+            Some((_, next)) => {
+                let mut buf = [0; 4];
+                bytes.extend_from_slice(next.encode_utf8(&mut buf).as_bytes());
+            }
+            None => return Err(ErnError::parse_failure("dn", "trailing backslash")),
+        }
+    }
+    String::from_utf8(bytes).map_err(|error| ErnError::parse_failure("dn", error))
+}
+
+/// Splits on `separator` wherever it is not escaped with a backslash.
+fn split_unescaped(value: &str, separator: char) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == separator => {
+                pieces.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    pieces.push(&value[start..]);
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dn_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.with_parts(["region 1", "a,b+c"])?;
+        let dn = ern.to_ldap_dn(&DnMapping::default());
+        assert!(dn.starts_with(r"ou=a\,b\+c,ou=region 1,cn=root_"));
+        assert!(dn.ends_with(",o=component,ou=reactive,dc=acton"));
+        assert_eq!(Ern::from_ldap_dn(&dn, &DnMapping::default())?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_escape_edges() -> anyhow::Result<()> {
+        assert_eq!(escape_value(" #x "), r"\ #x\ ");
+        assert_eq!(escape_value("#x"), r"\#x");
+        assert_eq!(unescape_value(r"\ #x\ ")?, " #x ");
+        assert_eq!(unescape_value(r"caf\C3\A9")?, "café");
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_mismatched_attributes() -> anyhow::Result<()> {
+        let mapping = DnMapping::default();
+        let dn = Ern::with_root("root")?
+            .to_ldap_dn(&mapping)
+            .replace("o=component", "uid=component");
+        assert!(matches!(
+            Ern::from_ldap_dn(&dn, &mapping),
+            Err(ErnError::ParseFailure("dn", _))
+        ));
+        assert!(matches!(
+            Ern::from_ldap_dn("cn=a+sn=b,dc=acton", &mapping),
+            Err(ErnError::ParseFailure("dn", _))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::errors::ErnError;
+use crate::model::Ern;
+
+/// Exclusive, expiring ownership of one ERN (Entity Resource Name).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lease {
+    /// The node or process holding the lease.
+    pub owner: String,
+    /// A fencing token that increases with every new lease granted by the table.
+    ///
+    /// Storage written on behalf of a lease holder should reject tokens older than the newest one
+    /// it has seen, so a paused former holder cannot overwrite its successor's work.
+    pub token: u64,
+    /// When the lease lapses unless renewed.
+    pub expires_at: Instant,
+}
+
+impl Lease {
+    pub fn is_expired(&self, now: Instant) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// Tracks which owner hosts each ERN (Entity Resource Name), for example which node runs the actor it names.
+///
+/// Leases are held on the ERN without its revision, so every revision of a resource shares one
+/// lease.
+///
+/// Every method takes the current time explicitly, normally `Instant::now()`, so expiry is
+/// deterministic under test. Share the table between threads behind a `Mutex`.
+#[derive(Debug, Default)]
+pub struct LeaseTable {
+    leases: HashMap<Ern, Lease>,
+    next_token: u64,
+}
+
+/// The ERN a lease is held on: `ern` without its revision.
+fn lease_key(ern: &Ern) -> Ern {
+    Ern {
+        revision: None,
+        ..ern.clone()
+    }
+}
+
+/// When a lease granted at `now` for `ttl` lapses, or [`ErnError::LimitExceeded`] if that
+/// instant is not representable.
+fn expiry(now: Instant, ttl: Duration) -> Result<Instant, ErnError> {
+    now.checked_add(ttl).ok_or_else(|| {
+        ErnError::LimitExceeded(
+            "lease ttl",
+            usize::MAX,
+            usize::try_from(ttl.as_secs()).unwrap_or(usize::MAX),
+        )
+    })
+}
+
+impl LeaseTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grants `owner` a lease on `ern` for `ttl`.
+    ///
+    /// Acquiring a lease the owner already holds renews it and keeps its token. Fails with
+    /// [`ErnError::LeaseHeld`] while another owner's lease is unexpired, and with
+    /// [`ErnError::LimitExceeded`] if `now + ttl` overflows.
+    pub fn acquire(
+        &mut self,
+        ern: &Ern,
+        owner: &str,
+        ttl: Duration,
+        now: Instant,
+    ) -> Result<Lease, ErnError> {
+        let key = lease_key(ern);
+        let expires_at = expiry(now, ttl)?;
+        if let Some(lease) = self.leases.get_mut(&key) {
+            if !lease.is_expired(now) {
+                if lease.owner != owner {
+                    return Err(ErnError::LeaseHeld(key.to_string(), lease.owner.clone()));
+                }
+                lease.expires_at = expires_at;
+                return Ok(lease.clone());
+            }
+        }
+        self.next_token += 1;
+        let lease = Lease {
+            owner: owner.to_string(),
+            token: self.next_token,
+            expires_at,
+        };
+        self.leases.insert(key, lease.clone());
+        Ok(lease)
+    }
+
+    /// Extends the lease identified by `token` to `now + ttl`.
+    ///
+    /// Fails with [`ErnError::StaleLease`] if the lease expired or a newer one replaced it, and with
+    /// [`ErnError::LimitExceeded`] if `now + ttl` overflows.
+    pub fn renew(
+        &mut self,
+        ern: &Ern,
+        token: u64,
+        ttl: Duration,
+        now: Instant,
+    ) -> Result<Lease, ErnError> {
+        match self.leases.get_mut(&lease_key(ern)) {
+            Some(lease) if lease.token == token && !lease.is_expired(now) => {
+                lease.expires_at = expiry(now, ttl)?;
+                Ok(lease.clone())
+            }
+            _ => Err(ErnError::StaleLease(token)),
+        }
+    }
+
+    /// Gives up the lease identified by `token`, returning false if it is no longer current.
+    pub fn release(&mut self, ern: &Ern, token: u64) -> bool {
+        let key = lease_key(ern);
+        if self
+            .leases
+            .get(&key)
+            .is_some_and(|lease| lease.token == token)
+        {
+            self.leases.remove(&key);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the unexpired lease on `ern`, if any.
+    pub fn holder(&self, ern: &Ern, now: Instant) -> Option<&Lease> {
+        self.leases
+            .get(&lease_key(ern))
+            .filter(|lease| !lease.is_expired(now))
+    }
+
+    /// Returns true if `token` identifies the current, unexpired lease on `ern`.
+    pub fn is_current(&self, ern: &Ern, token: u64, now: Instant) -> bool {
+        self.holder(ern, now)
+            .is_some_and(|lease| lease.token == token)
+    }
+
+    /// Drops every expired lease and returns how many were removed.
+    pub fn purge_expired(&mut self, now: Instant) -> usize {
+        let before = self.leases.len();
+        self.leases.retain(|_, lease| !lease.is_expired(now));
+        before - self.leases.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn test_single_holder() -> anyhow::Result<()> {
+        let mut table = LeaseTable::new();
+        let ern = Ern::with_root("actor")?;
+        let now = Instant::now();
+        let lease = table.acquire(&ern, "node-a", TTL, now)?;
+        assert_eq!(
+            table.acquire(&ern, "node-b", TTL, now),
+            Err(ErnError::LeaseHeld(ern.to_string(), "node-a".to_string()))
+        );
+        // Re-acquiring as the holder renews without changing the token
+        let again = table.acquire(&ern, "node-a", TTL, now + Duration::from_secs(5))?;
+        assert_eq!(again.token, lease.token);
+        assert_eq!(again.expires_at, now + Duration::from_secs(15));
+        Ok(())
+    }
+
+    #[test]
+    fn test_revisions_share_one_lease() -> anyhow::Result<()> {
+        let mut table = LeaseTable::new();
+        let ern = Ern::with_root("actor")?;
+        let now = Instant::now();
+        let lease = table.acquire(&ern, "node-a", TTL, now)?;
+        assert_eq!(
+            table.acquire(&ern.with_revision(1), "node-b", TTL, now),
+            Err(ErnError::LeaseHeld(ern.to_string(), "node-a".to_string()))
+        );
+        assert!(table.is_current(&ern.with_revision(7), lease.token, now));
+        assert!(table.release(&ern.with_revision(2), lease.token));
+        assert!(table.holder(&ern, now).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_expiry_hands_over_with_a_newer_token() -> anyhow::Result<()> {
+        let mut table = LeaseTable::new();
+        let ern = Ern::with_root("actor")?;
+        let now = Instant::now();
+        let first = table.acquire(&ern, "node-a", TTL, now)?;
+        let later = now + TTL;
+        assert!(table.holder(&ern, later).is_none());
+        let second = table.acquire(&ern, "node-b", TTL, later)?;
+        assert!(second.token > first.token);
+        assert!(!table.is_current(&ern, first.token, later));
+        assert_eq!(
+            table.renew(&ern, first.token, TTL, later),
+            Err(ErnError::StaleLease(first.token))
+        );
+        assert!(!table.release(&ern, first.token));
+        assert!(table.is_current(&ern, second.token, later));
+        Ok(())
+    }
+
+    #[test]
+    fn test_renew_release_and_purge() -> anyhow::Result<()> {
+        let mut table = LeaseTable::new();
+        let (first, second) = (Ern::with_root("a")?, Ern::with_root("b")?);
+        let now = Instant::now();
+        let lease = table.acquire(&first, "node-a", TTL, now)?;
+        table.acquire(&second, "node-a", TTL, now)?;
+        let renewed = table.renew(&first, lease.token, TTL, now + Duration::from_secs(9))?;
+        assert_eq!(renewed.expires_at, now + Duration::from_secs(19));
+        assert_eq!(table.purge_expired(now + TTL), 1);
+        assert!(table.release(&first, lease.token));
+        assert!(table.holder(&first, now).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflowing_ttl_is_rejected() -> anyhow::Result<()> {
+        let mut table = LeaseTable::new();
+        let ern = Ern::with_root("actor")?;
+        let now = Instant::now();
+        assert!(matches!(
+            table.acquire(&ern, "node-a", Duration::MAX, now),
+            Err(ErnError::LimitExceeded("lease ttl", _, _))
+        ));
+        assert!(table.holder(&ern, now).is_none());
+        let lease = table.acquire(&ern, "node-a", TTL, now)?;
+        assert!(matches!(
+            table.renew(&ern, lease.token, Duration::MAX, now),
+            Err(ErnError::LimitExceeded("lease ttl", _, _))
+        ));
+        assert_eq!(table.holder(&ern, now), Some(&lease));
+        Ok(())
+    }
+}
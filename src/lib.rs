@@ -13,7 +13,51 @@
 //! ## Usage
 //! This crate is structured into several modules, each providing distinct functionalities:
 //! - `builder`: Module for building Erns.
+//! - `literal`: The `ern!` macro, which validates hard-coded Erns at compile time.
 //! - `parser`: Module for parsing Erns.
+//! - `pattern`: Wildcard patterns over every component and the parts path, with a compiled form for routing.
+//! - `avro`: Avro schemas and value conversions in string or record form (with the `avro` feature).
+//! - `cbor`: Tagged CBOR encoding in text or structured form (with the `cbor` feature).
+//! - `json`: A documented JSON object form for pipelines that need structure (with the `json` feature).
+//! - `msgpack`: MessagePack encoding as `str` or a dedicated extension type (with the `msgpack` feature).
+//! - `codec`: A `tokio_util` codec for newline- or length-delimited Erns (with the `tokio` feature).
+//! - `grpc`: `tonic` interceptors that propagate a canonical, optionally signed Ern in request metadata (with the `grpc` feature).
+//! - `stream`: Incremental parsing of newline-delimited Erns from framed network input.
+//! - `config`: Parsing configuration (scheme, normalization, and validation limits).
+//! - `context`: Thread-local, scoped defaults for constructing Erns.
+//! - `pool`: A thread-local pool of reusable part buffers for `ErnBuilder::pooled`.
+//! - `diagnostics`: Verbose validation with spans and machine-applicable fixes for editors and CLIs.
+//! - `editor`: Batched, validate-once edits to existing Erns.
+//! - `cursor`: Opaque pagination cursors ordered by Ern root.
+//! - `cardinality`: HyperLogLog sketches for estimating distinct Ern counts.
+//! - `stats`: Exact per-prefix counts, growth by depth, and top accounts and categories for budgeting.
+//! - `audit`: Structured lifecycle events (created, renamed, aliased, deleted) for Erns.
+//! - `newtype`: Strongly typed `Ern` wrappers, derivable with the `derive` feature.
+//! - `lease`: Expiring, fenced ownership of Erns, e.g. which node hosts an actor.
+//! - `migration`: Upgrades of ERN strings stored by older releases via registered rules.
+//! - `quota`: Atomic usage counters and limits per ERN prefix.
+//! - `reservation`: Per-owner ERN prefix reservations that refuse overlapping claims.
+//! - `provenance`: Audit metadata recording who created an Ern, when, and from where.
+//! - `bench`: Shared parse, build, match, and route workloads for Criterion (with the `bench` feature).
+//! - `instrumentation`: Parse, validation, and cache metrics delivered to a `MetricsSink` (with the `instrumentation` feature).
+//! - `testing`: Round-trip and differential checks (with the `test-utils` feature).
+//! - `postgres`: sqlx mappings to `text`, an `ern` composite type, or separate columns (with the `postgres` feature).
+//! - `url`: The `UrlMapper` trait and S3, GCS, and prefix mappers between Erns and asset URLs.
+//! - `header`: HTTP header values for propagating Erns, with a base32 fallback for non-ASCII Erns.
+//! - `baggage`: W3C Baggage entries carrying an Ern, truncated deterministically to fit size limits.
+//! - `amqp`: Deterministic AMQP routing keys, reversible where lossless.
+//! - `mqtt`: Bidirectional mapping between Erns and MQTT topic names, and between patterns and topic filters.
+//! - `namegen`: Pronounceable, collision-checked part names such as `brave-otter-47` (with the `namegen` feature).
+//! - `nats`: Bidirectional mapping between Erns and NATS subjects, and from patterns to wildcard subjects.
+//! - `otel`: Conversion to and from OpenTelemetry entities and resource attributes.
+//! - `spiffe`: Conversion to and from SPIFFE workload identities.
+//! - `ldap`: Conversion to and from LDAP distinguished names.
+//! - `key`: Order-preserving byte keys for embedded databases such as redb and sled.
+//! - `columns`: Parallel per-component columns for Arrow/Parquet pipelines.
+//! - `well_known`: Pre-validated ERNs for framework services such as the system supervisor and dead-letter queue.
+//! - `wire`: A versioned, fixed-layout form for postcard, bincode, and similar binary formats.
+//! - `serde_ern`: `#[serde(with = ...)]` helpers encoding Erns as strings (with the `serde` feature).
+//! - `yaml`: Loading YAML configuration with errors that name the offending path (with the `yaml` feature).
 //! - `model`: Contains the models representing different parts of an ERN (Entity Resource Name).
 //! - `traits`: Traits used across the crate for common functionality.
 //!
@@ -23,27 +67,153 @@
 extern crate core;
 
 // Re-exporting the public API under the root of the crate for direct access
+pub use amqp::*;
+pub use audit::*;
+#[cfg(feature = "avro")]
+pub use avro::*;
+pub use baggage::*;
 pub use builder::*;
+pub use cardinality::*;
+#[cfg(feature = "cbor")]
+pub use cbor::*;
+#[cfg(feature = "tokio")]
+pub use codec::*;
+pub use columns::*;
+pub use config::*;
+pub use context::*;
+pub use cursor::*;
+pub use diagnostics::*;
+pub use editor::*;
+#[cfg(feature = "grpc")]
+pub use grpc::*;
+pub use header::*;
+#[cfg(feature = "instrumentation")]
+pub use instrumentation::*;
+pub use intern::*;
+pub use key::*;
+pub use ldap::*;
+pub use lease::*;
+pub use migration::*;
 pub use model::*;
+pub use mqtt::*;
+#[cfg(feature = "msgpack")]
+pub use msgpack::*;
+#[cfg(feature = "namegen")]
+pub use namegen::*;
+pub use nats::*;
+pub use newtype::{__private, ErnNewtype};
+pub use otel::*;
 pub use parser::*;
+pub use pattern::*;
+pub use pool::*;
+#[cfg(feature = "postgres")]
+pub use postgres::*;
+pub use provenance::*;
+pub use quota::*;
+pub use reservation::*;
+pub use stats::*;
+pub use stream::*;
 pub use traits::*;
+pub use url::*;
+pub use well_known::*;
+pub use wire::*;
 
+#[cfg(feature = "derive")]
+pub use acton_ern_derive::ErnNewtype;
+
+mod amqp;
+mod audit;
+#[cfg(feature = "avro")]
+mod avro;
+mod baggage;
+#[cfg(feature = "bench")]
+pub mod bench;
 mod builder;
+mod cardinality;
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "tokio")]
+mod codec;
+mod columns;
+mod config;
+mod context;
+mod cursor;
+mod diagnostics;
+mod distance;
+mod editor;
 mod errors;
+mod glob;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod hash;
+mod header;
+mod instrumentation;
+mod intern;
+#[cfg(feature = "json")]
+mod json;
+mod key;
+mod ldap;
+mod lease;
+mod literal;
+mod migration;
 mod model;
+mod mqtt;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+#[cfg(feature = "namegen")]
+mod namegen;
+mod nats;
+mod newtype;
+mod otel;
 mod parser;
+mod pattern;
+mod pool;
+#[cfg(feature = "postgres")]
+mod postgres;
+mod provenance;
+mod quota;
+mod reservation;
+#[cfg(feature = "serde")]
+pub mod serde_ern;
+mod spiffe;
+mod stats;
+mod stream;
+#[cfg(feature = "test-utils")]
+pub mod testing;
 mod traits;
+mod url;
+mod well_known;
+mod wire;
+#[cfg(feature = "yaml")]
+pub mod yaml;
 
 pub mod prelude {
     //! The prelude module for `acton-ern`.
     //!
     //! This module re-exports essential traits and structures for easy use by downstream consumers.
 
-    pub use super::builder::ErnBuilder;
+    pub use super::audit::{AuditEvent, AuditRecord};
+    pub use super::builder::{ErnBatchBuilder, ErnBuilder};
+    pub use super::cardinality::ErnCardinality;
+    pub use super::config::{ErnConfig, ValidationConfig};
+    pub use super::context::ErnContext;
+    pub use super::cursor::Cursor;
+    pub use super::editor::{EditOp, ErnEditor};
     pub use super::errors::ErnError;
-    pub use super::model::{Account, Category, Domain, Ern, Part, Parts};
+    pub use super::intern::{ErnInterner, LeakingInterner};
+    pub use super::model::{
+        Account, BoundedErn, CanonicalErn, Category, Domain, Ern, ErnList, ErnMap, ErnPrefix,
+        ErnRef, ErnSet, Format, LogFieldPolicy, MetricSanitizer, Part, Parts, RateScope,
+    };
+    pub use super::newtype::ErnNewtype;
     pub use super::parser::ErnParser;
+    pub use super::pattern::{CompiledErnPattern, ErnPattern};
+    pub use super::provenance::{ErnProvenance, ProvenancedErn};
+    pub use super::stats::ErnStats;
+    pub use super::stream::ErnStreamParser;
     pub use super::traits::*;
+    #[cfg(feature = "derive")]
+    pub use acton_ern_derive::ErnNewtype;
 }
 
 // #[cfg(test)]
@@ -0,0 +1,173 @@
+use crate::model::Ern;
+use crate::parser::ErnParser;
+
+/// Builds an [`Ern`] from a string literal that is validated when the crate using it compiles.
+///
+/// The literal must be accepted by [`ErnParser::parse`]; anything else, such as a missing
+/// component, an empty part, or a malformed revision, fails the build with the reason instead of
+/// panicking at runtime. The expansion parses the literal the same way, so a plain root name
+/// still gets a freshly generated id on each evaluation; write the full root id
+/// (`orders_01h455vb4pex5vsknk084sn02q`) for an ERN that is the same every time.
+///
+/// ```
+/// let ern = acton_ern::ern!("ern:acton:service:acct:root/a/b");
+/// assert_eq!(ern.parts.to_string(), "a/b");
+/// ```
+///
+/// ```compile_fail
+/// let ern = acton_ern::ern!("ern:acton:service:acct:root/a//b");
+/// ```
+#[macro_export]
+macro_rules! ern {
+    ($value:literal) => {{
+        const _: () = $crate::__private::check_ern_literal($value);
+        $crate::__private::ern_from_literal($value)
+    }};
+}
+
+/// Panics, at compile time when evaluated in a `const`, if `value` is not a valid ERN literal.
+///
+/// Mirrors the checks of [`ErnParser::parse`] with the default configuration.
+pub const fn check_ern_literal(value: &str) {
+    if let Err(reason) = validate(value.as_bytes()) {
+        panic!("{}", reason);
+    }
+}
+
+/// Parses a literal already accepted by [`check_ern_literal`].
+pub fn ern_from_literal(value: &'static str) -> Ern {
+    ErnParser::new(value.to_string())
+        .parse()
+        .expect("ern! literals are validated at compile time")
+}
+
+const fn validate(bytes: &[u8]) -> Result<(), &'static str> {
+    if bytes.len() < 4
+        || bytes[0] != b'e'
+        || bytes[1] != b'r'
+        || bytes[2] != b'n'
+        || bytes[3] != b':'
+    {
+        return Err("an ERN must start with `ern:`");
+    }
+    // the domain, category, and account each end at a `:`
+    let mut index = 4;
+    let mut field = 0;
+    let mut field_start = index;
+    while field < 3 {
+        if index == bytes.len() {
+            return Err("an ERN needs a domain, category, account, and root");
+        }
+        if bytes[index] == b':' {
+            if field == 0 && index == field_start {
+                return Err("the domain cannot be empty");
+            }
+            field += 1;
+            field_start = index + 1;
+        }
+        index += 1;
+    }
+
+    // the root runs to the first `/`, with an optional `#revision` suffix
+    let mut revision_start = None;
+    while index < bytes.len() && bytes[index] != b'/' {
+        if bytes[index] == b'#' && revision_start.is_none() {
+            revision_start = Some(index + 1);
+        }
+        index += 1;
+    }
+    if let Some(start) = revision_start {
+        if let Err(reason) = validate_revision(bytes, start, index) {
+            return Err(reason);
+        }
+    }
+    if index == bytes.len() {
+        return Ok(());
+    }
+
+    // each part runs to the next `/`
+    index += 1;
+    let mut part_start = index;
+    while index <= bytes.len() {
+        if index == bytes.len() || bytes[index] == b'/' {
+            if index == part_start {
+                return Err("parts cannot be empty");
+            }
+            part_start = index + 1;
+        } else if bytes[index] == b':' {
+            return Err("parts cannot contain `:`");
+        }
+        index += 1;
+    }
+    Ok(())
+}
+
+/// Accepts what `u64::from_str` does: an optional `+` followed by digits that fit in 64 bits.
+const fn validate_revision(bytes: &[u8], start: usize, end: usize) -> Result<(), &'static str> {
+    let mut index = start;
+    if index < end && bytes[index] == b'+' {
+        index += 1;
+    }
+    if index == end {
+        return Err("the revision must be a number");
+    }
+    let mut revision: u64 = 0;
+    while index < end {
+        let digit = bytes[index];
+        if !digit.is_ascii_digit() {
+            return Err("the revision must be a number");
+        }
+        revision = match revision.checked_mul(10) {
+            Some(shifted) => match shifted.checked_add((digit - b'0') as u64) {
+                Some(revision) => revision,
+                None => return Err("the revision does not fit in 64 bits"),
+            },
+            None => return Err("the revision does not fit in 64 bits"),
+        };
+        index += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_macro_builds_the_parsed_ern() -> anyhow::Result<()> {
+        let ern =
+            crate::ern!("ern:acton:service:acct:orders_01h455vb4pex5vsknk084sn02q#3/eu/shard");
+        assert_eq!(
+            ern,
+            "ern:acton:service:acct:orders_01h455vb4pex5vsknk084sn02q#3/eu/shard".parse()?
+        );
+        assert_eq!(ern.revision(), Some(3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_validation_matches_the_parser() {
+        for value in [
+            "ern:acton:service:acct:root",
+            "ern:acton:service:acct:root/a/b",
+            "ern:acton::acct:root/a",
+            "ern:acton:service:acct:root#+7/a",
+            "ern:acton:service:acct:root#18446744073709551615",
+            "ern:acton:service:acct:root#18446744073709551616",
+            "ern:acton:service:acct:root#x",
+            "ern:acton:service:acct:root#",
+            "ern:acton:service:acct:root/a:b",
+            "ern:acton:service:acct:root/a//b",
+            "ern:acton:service:acct:root/",
+            "ern::service:acct:root",
+            "ern:acton:service:acct",
+            "urn:acton:service:acct:root",
+        ] {
+            assert_eq!(
+                validate(value.as_bytes()).is_ok(),
+                ErnParser::new(value.to_string()).parse().is_ok(),
+                "{value}"
+            );
+        }
+    }
+}
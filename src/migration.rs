@@ -0,0 +1,189 @@
+use crate::errors::ErnError;
+use crate::model::{EntityRoot, Ern, ErnRef};
+
+/// Rewrites one legacy form into a newer one.
+pub trait MigrationRule: Send + Sync {
+    /// A short name for diagnostics.
+    fn name(&self) -> &'static str;
+
+    /// Returns the rewritten input, or `None` if the rule does not apply to it.
+    fn apply(&self, input: &str) -> Option<String>;
+}
+
+/// Converts parts separated by `:` (`ern:d:c:a:root:p1:p2`) to the current `/` separator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ColonSeparatedParts;
+
+impl MigrationRule for ColonSeparatedParts {
+    fn name(&self) -> &'static str {
+        "colon-separated parts"
+    }
+
+    fn apply(&self, input: &str) -> Option<String> {
+        let fields: Vec<&str> = input.split(':').collect();
+        if fields.len() <= 5 || input.contains('/') {
+            return None;
+        }
+        Some(format!(
+            "{}/{}",
+            fields[..5].join(":"),
+            fields[5..].join("/")
+        ))
+    }
+}
+
+/// Gives a root stored without a generated id (`ern:d:c:a:orders/x`) a deterministic one.
+///
+/// The id is derived from the legacy `scheme:domain:category:account:root` rather than generated,
+/// so upgrading the same stored value always yields the same ERN, and children upgraded separately
+/// keep sharing a root with each other and with their parent. It is not time-based, so the upgraded root reports no
+/// [`timestamp`](EntityRoot::timestamp).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LegacyRoot;
+
+impl MigrationRule for LegacyRoot {
+    fn name(&self) -> &'static str {
+        "legacy root"
+    }
+
+    fn apply(&self, input: &str) -> Option<String> {
+        let ern = ErnRef::parse(input).ok()?;
+        if EntityRoot::parse(&ern.root()).is_ok() {
+            return None;
+        }
+        // The root follows the fourth `:`
+        let start = input.match_indices(':').nth(3)?.0 + 1;
+        let identity = &input[..start + ern.root().len()];
+        let root = EntityRoot::derived(&ern.root(), identity.as_bytes());
+
+        let mut upgraded = input.to_string();
+        upgraded.replace_range(start..start + ern.root().len(), root.as_str());
+        Some(upgraded)
+    }
+}
+
+/// An ordered set of migration rules.
+pub struct Migrator {
+    rules: Vec<Box<dyn MigrationRule>>,
+}
+
+impl Default for Migrator {
+    /// Returns a migrator with the built-in rules: [`ColonSeparatedParts`], then [`LegacyRoot`].
+    fn default() -> Self {
+        Self::empty()
+            .register(ColonSeparatedParts)
+            .register(LegacyRoot)
+    }
+}
+
+impl Migrator {
+    /// Returns a migrator without any rules.
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Adds a rule, tried after the rules registered before it.
+    pub fn register(mut self, rule: impl MigrationRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Returns the names of the registered rules, in order.
+    pub fn rule_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.rules.iter().map(|rule| rule.name())
+    }
+
+    /// Upgrades `input` by applying the first matching rule until it is a current ERN (Entity Resource Name).
+    ///
+    /// Current input is returned unchanged. Fails with [`ErnError::ParseFailure`] if no rule
+    /// applies to an intermediate form.
+    pub fn upgrade(&self, input: &str) -> Result<Ern, ErnError> {
+        let mut current = input.to_string();
+        // Every rule may fire at most once per step, which bounds runaway rule sets
+        for _ in 0..=self.rules.len() {
+            if let Ok(ern) = ErnRef::parse(&current) {
+                if EntityRoot::parse(&ern.root()).is_ok() {
+                    return Ok(ern.to_ern());
+                }
+            }
+            match self.rules.iter().find_map(|rule| rule.apply(&current)) {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        Err(ErnError::ParseFailure(
+            "legacy ERN",
+            format!("no migration rule upgrades '{input}'"),
+        ))
+    }
+}
+
+/// Upgrades `input` with the built-in rules of [`Migrator::default`].
+pub fn upgrade_legacy(input: &str) -> Result<Ern, ErnError> {
+    Migrator::default().upgrade(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_input_is_unchanged() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.add_part("eu")?;
+        assert_eq!(upgrade_legacy(&ern.to_string())?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_legacy_root_is_deterministic() -> anyhow::Result<()> {
+        let first = upgrade_legacy("ern:acton:hr:acct:orders/eu")?;
+        let second = upgrade_legacy("ern:acton:hr:acct:orders/eu")?;
+        assert_eq!(first, second);
+        assert_eq!(first.root.base_name(), "orders");
+        assert_eq!(first.root.timestamp(), None);
+        assert_eq!(first.parts.to_string(), "eu");
+        let sibling = upgrade_legacy("ern:acton:hr:acct:orders/us")?;
+        assert_eq!(first.root, sibling.root);
+        let parent = upgrade_legacy("ern:acton:hr:acct:orders")?;
+        assert!(first.is_child_of(&parent));
+        assert!(sibling.is_child_of(&parent));
+        assert_ne!(
+            first.root,
+            upgrade_legacy("ern:acton:hr:acct2:orders/eu")?.root
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rules_chain() -> anyhow::Result<()> {
+        let ern = upgrade_legacy("ern:acton:hr:acct:orders#2:eu:shard")?;
+        assert_eq!(ern.parts.to_string(), "eu/shard");
+        assert_eq!(ern.revision(), Some(2));
+        assert_eq!(crate::ErnParser::new(ern.to_string()).parse()?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_rules() -> anyhow::Result<()> {
+        struct Urn;
+        impl MigrationRule for Urn {
+            fn name(&self) -> &'static str {
+                "urn scheme"
+            }
+            fn apply(&self, input: &str) -> Option<String> {
+                input.strip_prefix("urn:").map(|rest| format!("ern:{rest}"))
+            }
+        }
+        let migrator = Migrator::default().register(Urn);
+        assert_eq!(
+            migrator.rule_names().collect::<Vec<_>>(),
+            vec!["colon-separated parts", "legacy root", "urn scheme"]
+        );
+        assert_eq!(migrator.upgrade("urn:a:b:c:orders")?.domain.as_str(), "a");
+        assert!(matches!(
+            Migrator::empty().upgrade("ern:a:b:c:orders"),
+            Err(ErnError::ParseFailure("legacy ERN", _))
+        ));
+        Ok(())
+    }
+}
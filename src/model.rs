@@ -1,16 +1,55 @@
 pub use account::Account;
+pub use ancestors::Ancestors;
+pub use bounded::BoundedErn;
+pub use canonical::CanonicalErn;
 pub use category::Category;
+pub use component_ref::{AccountRef, CategoryRef, DomainRef, PartRef, RootRef};
+#[cfg(feature = "json")]
+pub(crate) use debug::Rfc3339;
 pub use domain::Domain;
 pub use ern::Ern;
-pub use part::Part;
-pub use parts::Parts;
+pub use ern_ref::ErnRef;
+pub use format::{ErnDisplay, Format};
+pub use list::ErnList;
+pub use log_fields::LogFieldPolicy;
+pub use map::{ErnMap, ErnMapIter, ErnSet, ErnSubtree};
+pub use metric::{MetricSanitizer, MIN_METRIC_NAME_LENGTH};
+pub use part::{Part, MAX_SLUG_LENGTH};
+pub use parts::{PartRepetition, Parts};
+pub use prefix::ErnPrefix;
+pub use rate_limit::RateScope;
 pub use root::EntityRoot;
+pub use root_cmp::{RootByName, RootByTimestamp, RootByValue};
+pub use shape::ErnCaptures;
+pub use similarity::Similarity;
+pub use snapshot::SNAPSHOT_ID_PLACEHOLDER;
+pub use visual::ErnColor;
 
 mod account;
-mod ern;
+mod ancestors;
+mod bounded;
+mod canonical;
 mod category;
+mod component_ref;
+mod debug;
 mod domain;
+mod ern;
+mod ern_ref;
+mod format;
+mod list;
+mod log_fields;
+mod map;
+mod metric;
 mod part;
 mod parts;
+mod prefix;
+mod rate_limit;
 mod root;
+mod root_cmp;
+#[cfg(feature = "serde")]
+mod serialization;
+mod shape;
+mod similarity;
+mod snapshot;
+mod visual;
 
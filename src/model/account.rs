@@ -1,26 +1,35 @@
+use std::borrow::Cow;
 use std::fmt;
 
 use derive_more::{AsRef, From, Into};
 
 /// Represents an account identifier in the ERN (Entity Resource Name) system.
 #[derive(AsRef, From, Into, Eq, Debug, PartialEq, Clone, Hash, PartialOrd)]
-pub struct Account(pub(crate) String);
+pub struct Account(pub(crate) Cow<'static, str>);
 
 impl Account {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+    /// Creates an account from a literal in `const` contexts.
+    pub const fn from_static(value: &'static str) -> Self {
+        Account(Cow::Borrowed(value))
+    }
     pub fn new(value: impl Into<String>) -> Self {
+        Account(Cow::Owned(value.into()))
+    }
+    /// Creates an account from a `&'static str` or an owned `String` without copying it.
+    pub fn from_cow(value: impl Into<Cow<'static, str>>) -> Self {
         Account(value.into())
     }
     pub fn into_owned(self) -> Account {
-        Account(self.0.to_string())
+        Account(Cow::Owned(self.0.into_owned()))
     }
 }
 
 impl Default for Account {
     fn default() -> Self {
-        Account("component".to_string())
+        Account(Cow::Borrowed("component"))
     }
 }
 
@@ -34,15 +43,20 @@ impl std::str::FromStr for Account {
     type Err = std::convert::Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Account(s.to_string()))
+        Ok(Account(Cow::Owned(s.to_string())))
+    }
+}
+impl From<Account> for String {
+    fn from(value: Account) -> Self {
+        value.0.into_owned()
+    }
+}
+
+impl From<String> for Account {
+    fn from(value: String) -> Self {
+        Account(Cow::Owned(value))
     }
 }
-//
-// impl From<Account> for String {
-//     fn from(domain: Account) -> Self {
-//         domain.0
-//     }
-// }
 
 #[cfg(test)]
 mod tests {
@@ -54,6 +68,12 @@ mod tests {
         assert_eq!(account.as_str(), "test123");
     }
 
+    #[test]
+    fn test_account_from_static_in_const() {
+        const ACCOUNT: Account = Account::from_static("tenant9");
+        assert_eq!(ACCOUNT.as_str(), "tenant9");
+    }
+
     #[test]
     fn test_account_default() {
         let account = Account::default();
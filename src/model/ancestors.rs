@@ -0,0 +1,139 @@
+use std::iter::FusedIterator;
+
+use crate::model::{Ern, Parts};
+
+/// Walks up an ERN (Entity Resource Name) hierarchy one part at a time; created by
+/// [`Ern::ancestors`] and [`Ern::self_and_ancestors`].
+///
+/// It keeps a single working copy and drops its last part on each step, so walking `n` levels
+/// costs one clone per yielded ERN rather than rebuilding each parent from its child.
+#[derive(Debug, Clone)]
+pub struct Ancestors {
+    next: Option<Ern>,
+}
+
+impl Iterator for Ancestors {
+    type Item = Ern;
+
+    fn next(&mut self) -> Option<Ern> {
+        let current = self.next.as_mut()?;
+        if current.parts.0.is_empty() {
+            return self.next.take();
+        }
+        let yielded = current.clone();
+        current.parts.0.pop();
+        Some(yielded)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.next.as_ref().map_or(0, |ern| ern.parts.len() + 1);
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Ancestors {}
+
+impl FusedIterator for Ancestors {}
+
+impl Ern {
+    /// Returns the ancestors of this ERN (Entity Resource Name), nearest first, ending with the
+    /// ERN of its root (the one without parts). Empty if this ERN has no parts.
+    ///
+    /// Like [`Ern::parent`], each ancestor keeps this ERN's revision.
+    pub fn ancestors(&self) -> Ancestors {
+        let mut ancestors = self.self_and_ancestors();
+        ancestors.next();
+        ancestors
+    }
+
+    /// Returns this ERN (Entity Resource Name) followed by its [`ancestors`](Ern::ancestors).
+    pub fn self_and_ancestors(&self) -> Ancestors {
+        Ancestors {
+            next: Some(self.clone()),
+        }
+    }
+
+    /// Returns true if `other` is one of this ERN's ancestors, at any depth.
+    ///
+    /// The same as [`Ern::is_child_of`]; an ERN is not a descendant of itself, and revisions are ignored.
+    pub fn descendant_of(&self, other: &Ern) -> bool {
+        self.is_child_of(other)
+    }
+
+    /// Returns the deepest ERN (Entity Resource Name) that is this ERN or one of its ancestors
+    /// and also `other` or one of its ancestors, or `None` if they do not share a root.
+    ///
+    /// The result keeps this ERN's revision, like [`Ern::parent`].
+    pub fn common_ancestor(&self, other: &Ern) -> Option<Ern> {
+        if self.domain != other.domain
+            || self.category != other.category
+            || self.account != other.account
+            || self.root != other.root
+        {
+            return None;
+        }
+        let shared = self
+            .parts
+            .0
+            .iter()
+            .zip(&other.parts.0)
+            .take_while(|(left, right)| left == right)
+            .count();
+        Some(Ern {
+            parts: Parts(self.parts.0[..shared].to_vec()),
+            ..self.clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ancestors_walk_up_to_the_root() -> anyhow::Result<()> {
+        let root = Ern::with_root("supervisor")?;
+        let ern = root
+            .with_parts(["region", "node", "worker"])?
+            .with_revision(2);
+        let ancestors: Vec<String> = ern
+            .ancestors()
+            .map(|ancestor| ancestor.parts.to_string())
+            .collect();
+        assert_eq!(ancestors, vec!["region/node", "region", ""]);
+        assert_eq!(ern.ancestors().len(), 3);
+        assert_eq!(ern.self_and_ancestors().next(), Some(ern.clone()));
+        assert_eq!(ern.self_and_ancestors().count(), 4);
+        assert_eq!(ern.ancestors().last(), Some(root.with_revision(2)));
+        assert!(ern.ancestors().all(|ancestor| ern.descendant_of(&ancestor)));
+        assert!(ern
+            .ancestors()
+            .zip(ern.self_and_ancestors())
+            .all(|(parent, child)| child.parent() == Some(parent)));
+
+        assert_eq!(root.ancestors().count(), 0);
+        assert_eq!(
+            root.self_and_ancestors().collect::<Vec<_>>(),
+            vec![root.clone()]
+        );
+        assert!(!root.descendant_of(&root));
+        Ok(())
+    }
+
+    #[test]
+    fn test_common_ancestor() -> anyhow::Result<()> {
+        let root = Ern::with_root("supervisor")?;
+        let left = root.with_parts(["region", "node", "a"])?;
+        let right = root.with_parts(["region", "node", "b", "c"])?;
+        assert_eq!(
+            left.common_ancestor(&right),
+            Some(root.with_parts(["region", "node"])?)
+        );
+        assert_eq!(left.common_ancestor(&root), Some(root.clone()));
+        assert_eq!(left.common_ancestor(&left), Some(left.clone()));
+        let parent = left.parent().expect("has parts");
+        assert_eq!(left.common_ancestor(&parent), Some(parent));
+        assert_eq!(left.common_ancestor(&Ern::with_root("other")?), None);
+        Ok(())
+    }
+}
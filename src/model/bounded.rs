@@ -0,0 +1,99 @@
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+
+/// An ERN (Entity Resource Name) guaranteed to have at most `MAX_DEPTH` parts.
+///
+/// Use it in registry and router signatures to reject pathologically deep hierarchies at the
+/// boundary; for limits chosen at runtime, see [`ValidationConfig::max_depth`](crate::ValidationConfig::max_depth).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BoundedErn<const MAX_DEPTH: usize>(Ern);
+
+impl<const MAX_DEPTH: usize> BoundedErn<MAX_DEPTH> {
+    /// Wraps `ern`, failing with [`ErnError::LimitExceeded`] if it is deeper than `MAX_DEPTH`.
+    pub fn new(ern: Ern) -> Result<Self, ErnError> {
+        if ern.parts.len() > MAX_DEPTH {
+            return Err(ErnError::LimitExceeded("depth", MAX_DEPTH, ern.parts.len()));
+        }
+        Ok(Self(ern))
+    }
+
+    /// Appends a part, failing if the result would exceed `MAX_DEPTH`.
+    pub fn add_part(&self, part: impl Into<String>) -> Result<Self, ErnError> {
+        Self::new(self.0.add_part(part)?)
+    }
+
+    /// Returns how many more parts can be added.
+    pub fn remaining_depth(&self) -> usize {
+        MAX_DEPTH - self.0.parts.len()
+    }
+
+    /// Returns the wrapped ERN (Entity Resource Name).
+    pub fn into_inner(self) -> Ern {
+        self.0
+    }
+}
+
+impl<const MAX_DEPTH: usize> Deref for BoundedErn<MAX_DEPTH> {
+    type Target = Ern;
+
+    fn deref(&self) -> &Ern {
+        &self.0
+    }
+}
+
+impl<const MAX_DEPTH: usize> TryFrom<Ern> for BoundedErn<MAX_DEPTH> {
+    type Error = ErnError;
+
+    fn try_from(ern: Ern) -> Result<Self, Self::Error> {
+        Self::new(ern)
+    }
+}
+
+impl<const MAX_DEPTH: usize> From<BoundedErn<MAX_DEPTH>> for Ern {
+    fn from(bounded: BoundedErn<MAX_DEPTH>) -> Self {
+        bounded.0
+    }
+}
+
+impl<const MAX_DEPTH: usize> fmt::Display for BoundedErn<MAX_DEPTH> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const MAX_DEPTH: usize> FromStr for BoundedErn<MAX_DEPTH> {
+    type Err = ErnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(ErnParser::new(s.to_string()).parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded_accepts_within_limit() -> anyhow::Result<()> {
+        let bounded = BoundedErn::<2>::new(Ern::with_root("root")?.add_part("a")?)?;
+        assert_eq!(bounded.remaining_depth(), 1);
+        let deeper = bounded.add_part("b")?;
+        assert_eq!(deeper.parts.len(), 2);
+        assert_eq!(
+            deeper.add_part("c"),
+            Err(ErnError::LimitExceeded("depth", 2, 3))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bounded_from_str() {
+        let parsed = "ern:acton:reactive:component:root/a/b".parse::<BoundedErn<1>>();
+        assert_eq!(parsed, Err(ErnError::LimitExceeded("depth", 1, 2)));
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::config::ErnConfig;
+use crate::errors::ErnError;
+use crate::model::{Account, Category, Domain, Ern, Part, Parts};
+use crate::parser::ErnParser;
+
+/// An ERN (Entity Resource Name) guaranteed to be in canonical form.
+///
+/// Canonical ERNs have non-empty components that parsing with [`ErnConfig::normalize`] would leave
+/// unchanged, and no `:` inside the domain, category, or account, so displaying one and parsing the
+/// result with the default configuration always returns an equal ERN. Accept `CanonicalErn` in signatures that would
+/// otherwise normalize their input defensively.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CanonicalErn(Ern);
+
+impl CanonicalErn {
+    /// Wraps `ern`, failing with [`ErnError::NotCanonical`] naming the first non-canonical component.
+    pub fn new(ern: Ern) -> Result<Self, ErnError> {
+        let config = ErnConfig::normalizing();
+        for (name, value) in components(&ern) {
+            if value.is_empty()
+                || config.normalized(value) != value
+                || (name != "part" && value.contains(':'))
+            {
+                return Err(ErnError::NotCanonical(name, value.to_string()));
+            }
+        }
+        Ok(Self(ern))
+    }
+
+    /// Normalizes every component of `ern` as [`ErnConfig::normalize`] does, keeping its root and revision.
+    ///
+    /// Fails if a component becomes empty or still contains `:`.
+    pub fn canonicalize(ern: Ern) -> Result<Self, ErnError> {
+        let config = ErnConfig::normalizing();
+        let parts = (&ern.parts)
+            .into_iter()
+            .map(|part| Part::new(config.normalized(part.as_str()).into_owned()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let canonicalized = Ern {
+            domain: Domain::new(config.normalized(ern.domain.as_str()).into_owned())?,
+            category: Category::new(config.normalized(ern.category.as_str()).into_owned()),
+            account: Account::new(config.normalized(ern.account.as_str()).into_owned()),
+            parts: Parts::new(parts),
+            ..ern
+        };
+        Self::new(canonicalized)
+    }
+
+    /// Returns the wrapped ERN (Entity Resource Name).
+    pub fn into_inner(self) -> Ern {
+        self.0
+    }
+}
+
+fn components(ern: &Ern) -> impl Iterator<Item = (&'static str, &str)> {
+    [
+        ("domain", ern.domain.as_str()),
+        ("category", ern.category.as_str()),
+        ("account", ern.account.as_str()),
+    ]
+    .into_iter()
+    .chain((&ern.parts).into_iter().map(|part| ("part", part.as_str())))
+}
+
+impl Deref for CanonicalErn {
+    type Target = Ern;
+
+    fn deref(&self) -> &Ern {
+        &self.0
+    }
+}
+
+impl TryFrom<Ern> for CanonicalErn {
+    type Error = ErnError;
+
+    fn try_from(ern: Ern) -> Result<Self, Self::Error> {
+        Self::new(ern)
+    }
+}
+
+impl From<CanonicalErn> for Ern {
+    fn from(canonical: CanonicalErn) -> Self {
+        canonical.0
+    }
+}
+
+impl fmt::Display for CanonicalErn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for CanonicalErn {
+    type Err = ErnError;
+
+    /// Parses with normalization, so `" ERN:Acton:HR:acct:root/A "` is accepted and canonicalized.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(ErnParser::new(s.to_string()).parse_with_config(&ErnConfig::normalizing())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_rejects_non_canonical_components() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?;
+        assert!(CanonicalErn::new(ern.add_part("a")?).is_ok());
+        assert_eq!(
+            CanonicalErn::new(ern.add_part("Shard")?),
+            Err(ErnError::NotCanonical("part", "Shard".to_string()))
+        );
+        let mut colon = ern.clone();
+        colon.category = Category::new("a:b");
+        assert_eq!(
+            CanonicalErn::new(colon),
+            Err(ErnError::NotCanonical("category", "a:b".to_string()))
+        );
+        let mut empty = ern.clone();
+        empty.account = Account::new("");
+        assert_eq!(
+            CanonicalErn::new(empty),
+            Err(ErnError::NotCanonical("account", String::new()))
+        );
+        assert!(CanonicalErn::canonicalize(Ern::with_category(" ")?).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_canonicalize_keeps_root_and_revision() -> anyhow::Result<()> {
+        let ern = Ern::with_domain(" Billing ")?
+            .add_part("EU")?
+            .with_revision(3);
+        let canonical = CanonicalErn::canonicalize(ern.clone())?;
+        assert_eq!(canonical.domain.as_str(), "billing");
+        assert_eq!(canonical.parts.to_string(), "eu");
+        assert_eq!(canonical.root, ern.root);
+        assert_eq!(canonical.revision(), Some(3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_roundtrips() -> anyhow::Result<()> {
+        let text = Ern::with_root("root")?.add_part("a")?.to_string();
+        let canonical: CanonicalErn =
+            format!(" {} ", text.replace(":reactive:", ":Reactive:")).parse()?;
+        assert_eq!(canonical.to_string(), text);
+        assert_eq!(canonical.to_string().parse::<CanonicalErn>()?, canonical);
+        Ok(())
+    }
+}
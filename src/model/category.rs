@@ -1,26 +1,35 @@
+use std::borrow::Cow;
 use std::fmt;
 
 use derive_more::{AsRef, Into};
 
 /// Represents a category in the ERN (Entity Resource Name) system, typically indicating the service.
 #[derive(AsRef, Into, Eq, Debug, PartialEq, Clone, Hash, PartialOrd)]
-pub struct Category(pub(crate) String);
+pub struct Category(pub(crate) Cow<'static, str>);
 
 impl Category {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+    /// Creates a category from a literal in `const` contexts.
+    pub const fn from_static(value: &'static str) -> Self {
+        Category(Cow::Borrowed(value))
+    }
     pub fn new(value: impl Into<String>) -> Self {
+        Category(Cow::Owned(value.into()))
+    }
+    /// Creates a category from a `&'static str` or an owned `String` without copying it.
+    pub fn from_cow(value: impl Into<Cow<'static, str>>) -> Self {
         Category(value.into())
     }
     pub fn into_owned(self) -> Category {
-        Category(self.0.to_string())
+        Category(Cow::Owned(self.0.into_owned()))
     }
 }
 
 impl Default for Category {
     fn default() -> Self {
-        Category("reactive".to_string())
+        Category(Cow::Borrowed("reactive"))
     }
 }
 
@@ -34,15 +43,14 @@ impl std::str::FromStr for Category {
     type Err = std::convert::Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Category(s.to_string()))
+        Ok(Category(Cow::Owned(s.to_string())))
+    }
+}
+impl From<Category> for String {
+    fn from(value: Category) -> Self {
+        value.0.into_owned()
     }
 }
-//
-// impl From<Category> for String {
-//     fn from(category: Category) -> Self {
-//         category.0
-//     }
-// }
 
 #[cfg(test)]
 mod tests {
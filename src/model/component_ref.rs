@@ -0,0 +1,84 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::model::{Account, Category, Domain, EntityRoot, Part};
+
+macro_rules! component_ref {
+    ($(#[$meta:meta])* $name:ident => $owned:ident, |$value:ident| $to_owned:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name<'a>(pub(crate) &'a str);
+
+        impl<'a> $name<'a> {
+            pub fn as_str(&self) -> &'a str {
+                self.0
+            }
+        }
+
+        impl Deref for $name<'_> {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.0)
+            }
+        }
+
+        impl PartialEq<str> for $name<'_> {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name<'_> {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<$owned> for $name<'_> {
+            fn eq(&self, other: &$owned) -> bool {
+                self.0 == other.as_str()
+            }
+        }
+
+        impl From<$name<'_>> for $owned {
+            fn from(component: $name<'_>) -> Self {
+                let $value = component.0;
+                $to_owned
+            }
+        }
+    };
+}
+
+component_ref!(
+    /// A domain borrowed from an [`ErnRef`](crate::ErnRef).
+    DomainRef => Domain, |value| Domain(Cow::Owned(value.to_string()))
+);
+component_ref!(
+    /// A category borrowed from an [`ErnRef`](crate::ErnRef).
+    CategoryRef => Category, |value| Category(Cow::Owned(value.to_string()))
+);
+component_ref!(
+    /// An account borrowed from an [`ErnRef`](crate::ErnRef).
+    AccountRef => Account, |value| Account(Cow::Owned(value.to_string()))
+);
+component_ref!(
+    /// A root, without its revision suffix, borrowed from an [`ErnRef`](crate::ErnRef).
+    ///
+    /// Converting to an [`EntityRoot`] keeps an existing id and, like the parser, generates one
+    /// for a plain name.
+    RootRef => EntityRoot,
+    |value| EntityRoot::parse(value).unwrap_or_else(|_| EntityRoot::from_str(value).unwrap_or_default())
+);
+component_ref!(
+    /// One part borrowed from an [`ErnRef`](crate::ErnRef).
+    PartRef => Part, |value| Part(Cow::Owned(value.to_string()))
+);
@@ -0,0 +1,101 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::model::Ern;
+
+/// Shows each component labeled, the parts as a list, and the root's creation time when it is time-based.
+///
+/// The output is stable across releases so it can be relied on in logs, unlike a derived `Debug`
+/// that would expose the internal representation of each component.
+impl fmt::Debug for Ern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<&str> = (&self.parts)
+            .into_iter()
+            .map(|part| part.as_str())
+            .collect();
+        let mut debug = f.debug_struct("Ern");
+        debug
+            .field("domain", &self.domain.as_str())
+            .field("category", &self.category.as_str())
+            .field("account", &self.account.as_str())
+            .field("root", &self.root.as_str());
+        if let Some(created) = self.root.timestamp() {
+            debug.field("created", &format_args!("{}", Rfc3339(created)));
+        }
+        if let Some(revision) = self.revision {
+            debug.field("revision", &revision);
+        }
+        debug.field("parts", &parts).finish()
+    }
+}
+
+/// Formats a time as an RFC 3339 UTC timestamp with millisecond precision.
+pub(crate) struct Rfc3339(pub(crate) SystemTime);
+
+impl fmt::Display for Rfc3339 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let millis = self
+            .0
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+        let (secs, millis) = (millis / 1000, millis % 1000);
+        let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+        let (year, month, day) = civil_from_days(days as i64);
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            year,
+            month,
+            day,
+            secs_of_day / 3600,
+            secs_of_day / 60 % 60,
+            secs_of_day % 60,
+            millis
+        )
+    }
+}
+
+/// Converts days since the Unix epoch into a proleptic Gregorian (year, month, day).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_debug_labels_components() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?
+            .with_parts(["a", "b"])?
+            .with_revision(3);
+        let debug = format!("{:?}", ern);
+        assert!(debug.starts_with(
+            r#"Ern { domain: "acton", category: "reactive", account: "component", root: "root_"#
+        ));
+        assert!(debug.contains(", created: 20"));
+        assert!(debug.ends_with(r#"revision: 3, parts: ["a", "b"] }"#));
+        assert!(!debug.contains("Cow"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rfc3339() {
+        let at = UNIX_EPOCH + Duration::from_millis(1_709_251_199_123);
+        assert_eq!(Rfc3339(at).to_string(), "2024-02-29T23:59:59.123Z");
+        assert_eq!(Rfc3339(UNIX_EPOCH).to_string(), "1970-01-01T00:00:00.000Z");
+    }
+}
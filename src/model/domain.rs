@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt;
 
 use derive_more::{AsRef, From, Into};
@@ -5,7 +6,7 @@ use derive_more::{AsRef, From, Into};
 use crate::errors::ErnError;
 
 #[derive(AsRef, From, Into, Eq, Debug, PartialEq, Clone, Hash, PartialOrd)]
-pub struct Domain(pub(crate) String);
+pub struct Domain(pub(crate) Cow<'static, str>);
 
 impl Domain {
     pub fn as_str(&self) -> &str {
@@ -13,12 +14,31 @@ impl Domain {
     }
 
     pub fn into_owned(self) -> Domain {
-        Domain(self.0)
+        Domain(Cow::Owned(self.0.into_owned()))
     }
+    /// Creates a domain from a literal in `const` contexts.
+    ///
+    /// Applies the same rules as [`Domain::new`], panicking (at compile time when evaluated in a
+    /// `const`) if the literal is empty.
+    pub const fn from_static(value: &'static str) -> Self {
+        if value.is_empty() {
+            panic!("Domain cannot be empty");
+        }
+        Domain(Cow::Borrowed(value))
+    }
+
     pub fn new(value: impl Into<String>) -> Result<Self, ErnError> {
+        Self::from_cow(value.into())
+    }
+
+    /// Creates a domain from a `&'static str` or an owned `String` without copying it.
+    pub fn from_cow(value: impl Into<Cow<'static, str>>) -> Result<Self, ErnError> {
         let val = value.into();
         if val.is_empty() {
-            Err(ErnError::ParseFailure("Domain", "cannot be empty".to_string()))
+            Err(ErnError::ParseFailure(
+                "Domain",
+                "cannot be empty".to_string(),
+            ))
         } else {
             Ok(Domain(val))
         }
@@ -27,7 +47,7 @@ impl Domain {
 
 impl Default for Domain {
     fn default() -> Self {
-        Domain("acton".to_string())
+        Domain(Cow::Borrowed("acton"))
     }
 }
 
@@ -45,6 +65,18 @@ impl std::str::FromStr for Domain {
     }
 }
 
+impl From<Domain> for String {
+    fn from(value: Domain) -> Self {
+        value.0.into_owned()
+    }
+}
+
+impl From<String> for Domain {
+    fn from(value: String) -> Self {
+        Domain(Cow::Owned(value))
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use super::*;
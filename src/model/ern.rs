@@ -1,25 +1,80 @@
 use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Display, Formatter};
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 use std::ops::Add;
 
-use crate::{Account, Category, Domain, EntityRoot, ErnComponent, Part, Parts};
+use crate::config::ErnConfig;
 use crate::errors::ErnError;
+use crate::hash::stable_hash64;
+use crate::parser::ErnParser;
+use crate::{Account, Category, Domain, EntityRoot, ErnComponent, ErnDisplay, Format, Part, Parts};
 
 /// Represents an ERN (Entity Resource Name), which uniquely identifies resources within the Acton framework.
-#[derive(Debug, PartialEq, Clone, Eq, Hash, PartialOrd)]
+#[derive(Clone, Eq)]
 pub struct Ern {
     pub domain: Domain,
     pub category: Category,
     pub account: Account,
     pub root: EntityRoot,
     pub parts: Parts,
+    /// The revision counter, e.g. `7` for `root#7`; `None` for ERNs without one.
+    pub revision: Option<u64>,
+}
+
+impl PartialEq for Ern {
+    /// Compares the cheapest and most distinguishing fields first.
+    ///
+    /// Part counts and revisions are plain integers, and roots carry unique ids, so unequal ERNs
+    /// usually differ before any shared prefix (domain, category, account) is compared. Each
+    /// string comparison checks lengths before comparing bytes.
+    fn eq(&self, other: &Self) -> bool {
+        self.parts.len() == other.parts.len()
+            && self.revision == other.revision
+            && self.root.as_str() == other.root.as_str()
+            && self.parts == other.parts
+            && self.account.as_str() == other.account.as_str()
+            && self.category.as_str() == other.category.as_str()
+            && self.domain.as_str() == other.domain.as_str()
+    }
+}
+
+impl Hash for Ern {
+    /// Feeds each component straight into the hasher instead of formatting the ERN first.
+    ///
+    /// `str` hashing terminates every component and `Parts` hashes its length, so shifting a
+    /// boundary (`a/bc` versus `ab/c`) changes the hash. Consistent with `PartialEq`.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.domain.as_str().hash(state);
+        self.category.as_str().hash(state);
+        self.account.as_str().hash(state);
+        self.root.as_str().hash(state);
+        self.parts.hash(state);
+        self.revision.hash(state);
+    }
 }
 
 impl Ord for Ern {
+    /// Orders by root, then revision, so the revisions of one entity sort together, then by
+    /// parts, account, category, and domain. Consistent with `PartialEq`.
     fn cmp(&self, other: &Self) -> Ordering {
-        self.root.name().cmp(&other.root.name())
+        self.root
+            .name()
+            .cmp(other.root.name())
+            .then_with(|| self.revision.cmp(&other.revision))
+            .then_with(|| {
+                let parts = self.parts.0.iter().map(Part::as_str);
+                parts.cmp(other.parts.0.iter().map(Part::as_str))
+            })
+            .then_with(|| self.account.as_str().cmp(other.account.as_str()))
+            .then_with(|| self.category.as_str().cmp(other.category.as_str()))
+            .then_with(|| self.domain.as_str().cmp(other.domain.as_str()))
+    }
+}
+
+impl PartialOrd for Ern {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -33,6 +88,9 @@ impl Display for Ern {
             self.account,
             self.root
         );
+        if let Some(revision) = self.revision {
+            display = format!("{}#{}", display, revision);
+        }
         if !self.parts.0.is_empty() {
             display = format!("{}/{}", display, self.parts);
         }
@@ -52,6 +110,7 @@ impl Add for Ern {
             account: self.account,
             root: self.root,
             parts: Parts(new_parts),
+            revision: self.revision,
         }
     }
 }
@@ -70,112 +129,193 @@ impl Ern {
             category,
             account,
             root,
+            revision: None,
             parts,
         }
     }
 
-        /// Creates a new ERN (Entity Resource Name) with the given root and default values for other fields
-        pub fn with_root(root: impl Into<String>) -> Result<Self, ErnError> {
-            let root = EntityRoot::new(root.into())?;
-            Ok(Ern {
-                root,
-                ..Default::default()
-            })
-        }
+    /// Parses an ERN (Entity Resource Name) string, applying the scheme, normalization, and validation rules in `config`.
+    pub fn parse_with_config(value: &str, config: &ErnConfig) -> Result<Self, ErnError> {
+        ErnParser::new(value.to_string()).parse_with_config(config)
+    }
 
-        /// Creates a new ERN (Entity Resource Name) based on an existing ERN (Entity Resource Name) but with a new root
-        pub fn with_new_root(&self, new_root: impl Into<String>) -> Result<Self, ErnError> {
-            let new_root = EntityRoot::new(new_root.into())?;
-            Ok(Ern {
-                domain: self.domain.clone(),
-                category: self.category.clone(),
-                account: self.account.clone(),
-                root: new_root,
-                parts: self.parts.clone(),
-            })
-        }
+    /// Creates a new ERN (Entity Resource Name) with the given root and default values for other fields
+    pub fn with_root(root: impl Into<String>) -> Result<Self, ErnError> {
+        let root = EntityRoot::new(root.into())?;
+        Ok(Ern {
+            root,
+            ..Default::default()
+        })
+    }
 
-        pub fn with_domain(domain: impl Into<String>) -> Result<Self, ErnError> {
-            let domain = Domain::new(domain)?;
-            Ok(Ern {
-                domain,
-                category: Category::default(),
-                account: Account::default(),
-                root: EntityRoot::default(),
-                parts: Parts::default(),
-            })
-        }
+    /// Creates a new ERN (Entity Resource Name) based on an existing ERN (Entity Resource Name) but with a new root
+    pub fn with_new_root(&self, new_root: impl Into<String>) -> Result<Self, ErnError> {
+        let new_root = EntityRoot::new(new_root.into())?;
+        Ok(Ern {
+            domain: self.domain.clone(),
+            category: self.category.clone(),
+            account: self.account.clone(),
+            root: new_root,
+            parts: self.parts.clone(),
+            revision: None,
+        })
+    }
 
-        pub fn with_category(category: impl Into<String>) -> Result<Self, ErnError> {
-            let category = Category::new(category);
-            Ok(Ern {
-                domain: Domain::default(),
-                category,
-                account: Account::default(),
-                root: EntityRoot::default(),
-                parts: Parts::default(),
-            })
-        }
+    pub fn with_domain(domain: impl Into<String>) -> Result<Self, ErnError> {
+        let domain = Domain::new(domain)?;
+        Ok(Ern {
+            domain,
+            category: Category::default(),
+            account: Account::default(),
+            root: EntityRoot::default(),
+            parts: Parts::default(),
+            revision: None,
+        })
+    }
 
-        pub fn with_account(account: impl Into<String>) -> Result<Self, ErnError> {
-            let account = Account::new(account);
-            Ok(Ern {
-                domain: Domain::default(),
-                category: Category::default(),
-                account,
-                root: EntityRoot::default(),
-                parts: Parts::default(),
-            })
-        }
+    pub fn with_category(category: impl Into<String>) -> Result<Self, ErnError> {
+        let category = Category::new(category);
+        Ok(Ern {
+            domain: Domain::default(),
+            category,
+            account: Account::default(),
+            root: EntityRoot::default(),
+            parts: Parts::default(),
+            revision: None,
+        })
+    }
 
-        pub fn add_part(&self, part: impl Into<String>) -> Result<Self, ErnError> {
-            let mut new_parts = self.parts.clone();
-            new_parts.0.push(Part::new(part)?);
-            Ok(Ern {
-                domain: self.domain.clone(),
-                category: self.category.clone(),
-                account: self.account.clone(),
-                root: self.root.clone(),
-                parts: new_parts,
-            })
+    pub fn with_account(account: impl Into<String>) -> Result<Self, ErnError> {
+        let account = Account::new(account);
+        Ok(Ern {
+            domain: Domain::default(),
+            category: Category::default(),
+            account,
+            root: EntityRoot::default(),
+            parts: Parts::default(),
+            revision: None,
+        })
+    }
+
+    pub fn add_part(&self, part: impl Into<String>) -> Result<Self, ErnError> {
+        let mut new_parts = self.parts.clone();
+        new_parts.0.push(Part::new(part)?);
+        Ok(Ern {
+            domain: self.domain.clone(),
+            category: self.category.clone(),
+            account: self.account.clone(),
+            root: self.root.clone(),
+            parts: new_parts,
+            revision: self.revision,
+        })
+    }
+
+    pub fn with_parts(
+        &self,
+        parts: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<Self, ErnError> {
+        let new_parts: Result<Vec<Part>, _> = parts.into_iter().map(Part::new).collect();
+        Ok(Ern {
+            domain: self.domain.clone(),
+            category: self.category.clone(),
+            account: self.account.clone(),
+            root: self.root.clone(),
+            parts: Parts(new_parts?),
+            revision: self.revision,
+        })
+    }
+
+    /// Returns an adapter that displays this ERN (Entity Resource Name) in the given layout.
+    pub fn display_as(&self, format: Format) -> ErnDisplay<'_> {
+        ErnDisplay::new(self, format)
+    }
+
+    /// Returns an adapter that displays this ERN (Entity Resource Name) with the scheme and
+    /// parts separator of `config`, in the form [`Ern::parse_with_config`] accepts.
+    pub fn display_with<'a>(&'a self, config: &'a ErnConfig) -> ErnDisplay<'a> {
+        ErnDisplay::with_config(self, config)
+    }
+
+    /// Returns a hash of the canonical ERN (Entity Resource Name) string that is stable across processes and releases.
+    ///
+    /// Unlike [`std::hash::Hash`], the value can be persisted or shared between services.
+    pub fn stable_hash(&self) -> u64 {
+        stable_hash64(self.to_string().as_bytes())
+    }
+
+    /// Returns the revision counter of this ERN (Entity Resource Name), if it carries one.
+    pub fn revision(&self) -> Option<u64> {
+        self.revision
+    }
+
+    /// Creates a copy of this ERN (Entity Resource Name) with the given revision.
+    pub fn with_revision(&self, revision: u64) -> Self {
+        Ern {
+            revision: Some(revision),
+            ..self.clone()
         }
+    }
+
+    /// Creates a copy of this ERN (Entity Resource Name) with the revision incremented by one.
+    ///
+    /// An ERN without a revision is treated as revision 0, so the first bump yields `#1`.
+    pub fn bump_revision(&self) -> Result<Self, ErnError> {
+        let next = self
+            .revision
+            .unwrap_or(0)
+            .checked_add(1)
+            .ok_or(ErnError::RevisionOverflow)?;
+        Ok(self.with_revision(next))
+    }
 
-        pub fn with_parts(
-            &self,
-            parts: impl IntoIterator<Item = impl Into<String>>,
-        ) -> Result<Self, ErnError> {
-            let new_parts: Result<Vec<Part>, _> = parts.into_iter().map(Part::new).collect();
-            Ok(Ern {
+    pub fn is_child_of(&self, other: &Ern) -> bool {
+        self.domain == other.domain
+            && self.category == other.category
+            && self.account == other.account
+            && self.root == other.root
+            && other.parts.0.len() < self.parts.0.len()
+            && self.parts.0.starts_with(&other.parts.0)
+    }
+
+    pub fn parent(&self) -> Option<Self> {
+        if self.parts.0.is_empty() {
+            None
+        } else {
+            Some(Ern {
                 domain: self.domain.clone(),
                 category: self.category.clone(),
                 account: self.account.clone(),
                 root: self.root.clone(),
-                parts: Parts(new_parts?),
+                parts: Parts(self.parts.0[..self.parts.0.len() - 1].to_vec()),
+                revision: self.revision,
             })
         }
+    }
+}
 
-        pub fn is_child_of(&self, other: &Ern) -> bool {
-            self.domain == other.domain
-                && self.category == other.category
-                && self.account == other.account
-                && self.root == other.root
-                && other.parts.0.len() < self.parts.0.len()
-                && self.parts.0.starts_with(&other.parts.0)
-        }
+impl std::str::FromStr for Ern {
+    type Err = ErnError;
 
-        pub fn parent(&self) -> Option<Self> {
-            if self.parts.0.is_empty() {
-                None
-            } else {
-                Some(Ern {
-                    domain: self.domain.clone(),
-                    category: self.category.clone(),
-                    account: self.account.clone(),
-                    root: self.root.clone(),
-                    parts: Parts(self.parts.0[..self.parts.0.len() - 1].to_vec()),
-                })
-            }
-        }
+    /// Parses with the default configuration, as [`ErnParser::parse`] does, so `let ern: Ern = s.parse()?` works.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ErnParser::new(s.to_string()).parse()
+    }
+}
+
+impl TryFrom<&str> for Ern {
+    type Error = ErnError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ErnParser::new(value.to_string()).parse()
+    }
+}
+
+impl TryFrom<String> for Ern {
+    type Error = ErnError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        ErnParser::new(value).parse()
+    }
 }
 
 impl Default for Ern {
@@ -187,6 +327,7 @@ impl Default for Ern {
             account: Account::default(),
             root: EntityRoot::default(),
             parts: Parts::new(Vec::default()),
+            revision: None,
         }
     }
 }
@@ -0,0 +1,316 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+
+use crate::errors::ErnError;
+use crate::model::{AccountRef, CategoryRef, DomainRef, Ern, Part, PartRef, Parts, RootRef};
+
+/// A validated ERN (Entity Resource Name) that borrows its components from the input string.
+///
+/// Parsing checks the same structure as [`ErnParser::parse`](crate::ErnParser::parse) but only
+/// records offsets, so decoding high-volume events does not allocate; [`ErnParser::parse_ref`](crate::ErnParser::parse_ref)
+/// is the same entry point. Components are returned as borrowed views such as [`DomainRef`] and
+/// [`PartRef`]. Convert with [`ErnRef::to_owned`] when an owned [`Ern`] is needed. With the `serde` feature, deserializing borrows from the input where the format allows
+/// (for example JSON strings without escapes) and falls back to an owned copy otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErnRef<'a> {
+    source: Cow<'a, str>,
+    domain: Range<usize>,
+    category: Range<usize>,
+    account: Range<usize>,
+    root: Range<usize>,
+    revision: Option<u64>,
+    parts: Option<Range<usize>>,
+}
+
+impl<'a> ErnRef<'a> {
+    /// Validates `value` and borrows its components without copying.
+    pub fn parse(value: &'a str) -> Result<Self, ErnError> {
+        Self::from_cow(Cow::Borrowed(value))
+    }
+
+    /// Validates an owned string, for inputs that could not be borrowed.
+    pub fn from_string(value: String) -> Result<ErnRef<'static>, ErnError> {
+        ErnRef::from_cow(Cow::Owned(value))
+    }
+
+    fn from_cow(source: Cow<'a, str>) -> Result<Self, ErnError> {
+        let value = source.as_ref();
+        let mut fields = value.splitn(5, ':');
+        if fields.next() != Some("ern") {
+            return Err(ErnError::InvalidFormat);
+        }
+        let mut offset = "ern:".len();
+        let mut next_field = || {
+            let field = fields.next().ok_or(ErnError::InvalidFormat)?;
+            let range = offset..offset + field.len();
+            offset = range.end + 1;
+            Ok::<_, ErnError>(range)
+        };
+        let domain = next_field()?;
+        let category = next_field()?;
+        let account = next_field()?;
+        let rest = next_field()?;
+        if domain.is_empty() {
+            return Err(ErnError::ParseFailure(
+                "Domain",
+                "cannot be empty".to_string(),
+            ));
+        }
+
+        let tail = &value[rest.clone()];
+        let (head, parts) = match tail.find('/') {
+            Some(slash) => (&tail[..slash], Some(rest.start + slash + 1..rest.end)),
+            None => (tail, None),
+        };
+        let (root, revision) = match head.split_once('#') {
+            Some((root, revision)) => {
+                let revision = revision.parse::<u64>().map_err(|_| {
+                    ErnError::ParseFailure(
+                        "revision",
+                        format!("'{}' is not a valid revision", revision),
+                    )
+                })?;
+                (rest.start..rest.start + root.len(), Some(revision))
+            }
+            None => (rest.start..rest.start + head.len(), None),
+        };
+        if let Some(parts) = &parts {
+            for part in value[parts.clone()].split('/') {
+                if part.contains(':') {
+                    return Err(ErnError::InvalidPartFormat);
+                }
+                if part.is_empty() {
+                    return Err(ErnError::ParseFailure(
+                        "Part",
+                        "cannot be empty".to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(ErnRef {
+            source,
+            domain,
+            category,
+            account,
+            root,
+            revision,
+            parts,
+        })
+    }
+
+    /// Returns the full ERN (Entity Resource Name) string.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub fn domain(&self) -> DomainRef<'_> {
+        DomainRef(&self.source[self.domain.clone()])
+    }
+
+    pub fn category(&self) -> CategoryRef<'_> {
+        CategoryRef(&self.source[self.category.clone()])
+    }
+
+    pub fn account(&self) -> AccountRef<'_> {
+        AccountRef(&self.source[self.account.clone()])
+    }
+
+    /// Returns the root without its revision suffix.
+    pub fn root(&self) -> RootRef<'_> {
+        RootRef(&self.source[self.root.clone()])
+    }
+
+    pub fn revision(&self) -> Option<u64> {
+        self.revision
+    }
+
+    /// Returns the parts after the root, in order.
+    pub fn parts(&self) -> impl Iterator<Item = PartRef<'_>> + Clone + '_ {
+        self.parts
+            .clone()
+            .into_iter()
+            .flat_map(move |range| self.source[range].split('/').map(PartRef))
+    }
+
+    /// Returns true if the components borrow from the original input rather than an owned copy.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.source, Cow::Borrowed(_))
+    }
+
+    /// Detaches the view from its input by copying the string if it is borrowed.
+    pub fn into_owned(self) -> ErnRef<'static> {
+        ErnRef {
+            source: Cow::Owned(self.source.into_owned()),
+            domain: self.domain,
+            category: self.category,
+            account: self.account,
+            root: self.root,
+            revision: self.revision,
+            parts: self.parts,
+        }
+    }
+
+    /// Builds an owned [`Ern`] from the already validated components, keeping the root id as written.
+    pub fn to_ern(&self) -> Ern {
+        let ern = Ern::new(
+            self.domain().into(),
+            self.category().into(),
+            self.account().into(),
+            self.root().into(),
+            self.parts().map(Part::from).collect::<Parts>(),
+        );
+        match self.revision {
+            Some(revision) => ern.with_revision(revision),
+            None => ern,
+        }
+    }
+}
+
+impl fmt::Display for ErnRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl AsRef<str> for ErnRef<'_> {
+    fn as_ref(&self) -> &str {
+        &self.source
+    }
+}
+
+impl From<&ErnRef<'_>> for Ern {
+    fn from(value: &ErnRef<'_>) -> Self {
+        value.to_ern()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErnRef<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ErnRef<'de> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ErnRefVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ErnRefVisitor {
+            type Value = ErnRef<'de>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an ERN string")
+            }
+
+            fn visit_borrowed_str<E: serde::de::Error>(
+                self,
+                value: &'de str,
+            ) -> Result<Self::Value, E> {
+                ErnRef::parse(value).map_err(E::custom)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                ErnRef::from_string(value.to_string()).map_err(E::custom)
+            }
+
+            fn visit_string<E: serde::de::Error>(self, value: String) -> Result<Self::Value, E> {
+                ErnRef::from_string(value).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ErnRefVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ErnParser;
+
+    #[test]
+    fn test_borrows_components() -> anyhow::Result<()> {
+        let input = "ern:acton:reactive:component:root_01h455vb4pex5vsknk084sn02q#3/eu/shard";
+        let ern = ErnRef::parse(input)?;
+        assert!(ern.is_borrowed());
+        assert_eq!(ern.domain(), "acton");
+        assert_eq!(ern.category(), "reactive");
+        assert_eq!(ern.account(), "component");
+        assert_eq!(ern.root(), "root_01h455vb4pex5vsknk084sn02q");
+        assert_eq!(ern.revision(), Some(3));
+        assert_eq!(ern.parts().collect::<Vec<_>>(), vec!["eu", "shard"]);
+        assert_eq!(ern.to_string(), input);
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_owned_parse() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["eu"])?;
+        let text = ern.to_string();
+        let borrowed = ErnRef::parse(&text)?;
+        assert_eq!(borrowed.to_ern(), ern);
+        assert_eq!(Ern::from(&borrowed), ern);
+        assert_eq!(borrowed.parts().count(), 1);
+        assert_eq!(
+            ErnRef::parse(&Ern::with_root("orders")?.to_string())?
+                .parts()
+                .count(),
+            0
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_components_convert_to_owned() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?
+            .with_parts(["eu", "shard"])?
+            .with_revision(3);
+        let text = ern.to_string();
+        let borrowed = ErnParser::parse_ref(&text)?;
+        assert_eq!(borrowed.domain(), ern.domain);
+        assert_eq!(borrowed.root(), ern.root);
+        assert_eq!(
+            Part::from(borrowed.parts().last().expect("two parts")),
+            Part::new("shard")?
+        );
+        assert_eq!(borrowed.to_ern(), ern);
+
+        let plain = ErnRef::parse("ern:acton:reactive:component:orders")?;
+        assert_eq!(plain.to_ern().root.base_name(), "orders");
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_invalid_structure() {
+        assert_eq!(
+            ErnRef::parse("urn:a:b:c:root"),
+            Err(ErnError::InvalidFormat)
+        );
+        assert_eq!(ErnRef::parse("ern:a:b:c"), Err(ErnError::InvalidFormat));
+        assert_eq!(
+            ErnRef::parse("ern:a:b:c:root/x:y"),
+            Err(ErnError::InvalidPartFormat)
+        );
+        assert!(ErnRef::parse("ern::b:c:root").is_err());
+        assert!(ErnRef::parse("ern:a:b:c:root/x//y").is_err());
+        assert!(ErnRef::parse("ern:a:b:c:root#x").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_borrows_when_possible() -> anyhow::Result<()> {
+        let json =
+            r#"["ern:acton:reactive:component:root/a", "ern:acton:reactive:component:root\/a"]"#;
+        let erns: Vec<ErnRef<'_>> = serde_json::from_str(json)?;
+        assert!(erns[0].is_borrowed());
+        assert!(!erns[1].is_borrowed());
+        assert_eq!(erns[0], erns[1].clone().into_owned());
+        assert_eq!(
+            serde_json::to_string(&erns[0])?,
+            r#""ern:acton:reactive:component:root/a""#
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt;
+
+use crate::config::{escape_part, ErnConfig, DEFAULT_PARTS_SEPARATOR};
+use crate::Ern;
+
+/// Alternative textual layouts for an ERN (Entity Resource Name).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    /// The canonical form, `ern:acton:hr:acct:root/a/b`.
+    #[default]
+    Colon,
+    /// A slash-separated path without the scheme, `acton/hr/acct/root/a/b`.
+    Path,
+    /// A dot-separated name without the scheme, `acton.hr.acct.root.a.b`.
+    Dotted,
+}
+
+impl Format {
+    fn separator(self) -> char {
+        match self {
+            Format::Colon => ':',
+            Format::Path => '/',
+            Format::Dotted => '.',
+        }
+    }
+}
+
+/// Displays an ERN (Entity Resource Name) in a chosen [`Format`]; created by [`Ern::display_as`]
+/// and [`Ern::display_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct ErnDisplay<'a> {
+    ern: &'a Ern,
+    format: Format,
+    scheme: &'a str,
+    parts_separator: char,
+}
+
+impl<'a> ErnDisplay<'a> {
+    pub(crate) fn new(ern: &'a Ern, format: Format) -> Self {
+        Self {
+            ern,
+            format,
+            scheme: "ern",
+            parts_separator: DEFAULT_PARTS_SEPARATOR,
+        }
+    }
+
+    pub(crate) fn with_config(ern: &'a Ern, config: &'a ErnConfig) -> Self {
+        Self {
+            ern,
+            format: Format::Colon,
+            scheme: &config.scheme,
+            parts_separator: config.parts_separator,
+        }
+    }
+}
+
+impl fmt::Display for ErnDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.format == Format::Colon {
+            if self.scheme == "ern" && self.parts_separator == DEFAULT_PARTS_SEPARATOR {
+                return write!(f, "{}", self.ern);
+            }
+            let ern = self.ern;
+            write!(
+                f,
+                "{}:{}:{}:{}:{}",
+                self.scheme, ern.domain, ern.category, ern.account, ern.root
+            )?;
+            if let Some(revision) = ern.revision() {
+                write!(f, "#{}", revision)?;
+            }
+            for part in &ern.parts {
+                write!(
+                    f,
+                    "{}{}",
+                    self.parts_separator,
+                    escape_part(part.as_str(), self.parts_separator)
+                )?;
+            }
+            return Ok(());
+        }
+
+        let separator = self.format.separator();
+        let ern = self.ern;
+        write!(
+            f,
+            "{}{separator}{}{separator}{}{separator}{}",
+            ern.domain, ern.category, ern.account, ern.root
+        )?;
+        if let Some(revision) = ern.revision() {
+            write!(f, "#{}", revision)?;
+        }
+        for part in &ern.parts {
+            write!(f, "{separator}{}", part)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_formats() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.with_parts(["a", "b"])?;
+        let root = ern.root.to_string();
+        assert_eq!(ern.display_as(Format::Colon).to_string(), ern.to_string());
+        assert_eq!(
+            ern.display_as(Format::Path).to_string(),
+            format!("acton/reactive/component/{root}/a/b")
+        );
+        assert_eq!(
+            ern.display_as(Format::Dotted).to_string(),
+            format!("acton.reactive.component.{root}.a.b")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_without_parts() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?;
+        assert_eq!(
+            ern.display_as(Format::Dotted).to_string(),
+            format!("acton.reactive.component.{}", ern.root)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_with_config() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?
+            .with_parts(["v1.2", "back\\slash"])?
+            .with_revision(3);
+        let root = ern.root.to_string();
+        let config = ErnConfig::default().with_parts_separator('.')?;
+        let text = ern.display_with(&config).to_string();
+        assert_eq!(
+            text,
+            format!("ern:acton:reactive:component:{root}#3.v1\\.2.back\\\\slash")
+        );
+        assert_eq!(Ern::parse_with_config(&text, &config)?, ern);
+        assert_eq!(
+            ern.display_with(&ErnConfig::default()).to_string(),
+            ern.to_string()
+        );
+        Ok(())
+    }
+}
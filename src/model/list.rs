@@ -0,0 +1,315 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+
+/// An ordered set of ERNs (Entity Resource Names), such as a "watch these resources" option.
+///
+/// The text form separates ERNs with commas and/or whitespace, and escapes those separators and
+/// `\` inside an ERN with a backslash, e.g. `ern:a:b:c:x_…, ern:a:b:c:y_…/with\ space`. It
+/// displays as the ERNs joined by `", "`, in insertion order. Duplicates are ignored, so
+/// `ErnList` behaves like a set that remembers the order entries were first added in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ErnList(Vec<Ern>);
+
+impl ErnList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Ern> {
+        self.0.iter()
+    }
+
+    pub fn contains(&self, ern: &Ern) -> bool {
+        self.0.contains(ern)
+    }
+
+    /// Appends `ern` unless it is already present, returning whether it was added.
+    pub fn insert(&mut self, ern: Ern) -> bool {
+        if self.contains(&ern) {
+            return false;
+        }
+        self.0.push(ern);
+        true
+    }
+
+    /// Removes `ern`, returning whether it was present.
+    pub fn remove(&mut self, ern: &Ern) -> bool {
+        let before = self.0.len();
+        self.0.retain(|entry| entry != ern);
+        self.0.len() != before
+    }
+
+    /// Returns the entries of `self` followed by the entries of `other` not already in `self`.
+    pub fn union(&self, other: &ErnList) -> ErnList {
+        self.iter().chain(other).cloned().collect()
+    }
+
+    /// Returns the entries of `self` that are also in `other`, in `self`'s order.
+    pub fn intersection(&self, other: &ErnList) -> ErnList {
+        self.iter()
+            .filter(|ern| other.contains(ern))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the entries of `self` that are not in `other`, in `self`'s order.
+    pub fn difference(&self, other: &ErnList) -> ErnList {
+        self.iter()
+            .filter(|ern| !other.contains(ern))
+            .cloned()
+            .collect()
+    }
+
+    pub fn is_subset(&self, other: &ErnList) -> bool {
+        self.iter().all(|ern| other.contains(ern))
+    }
+
+    /// Returns true if any entry equals `ern` or is one of its ancestors.
+    pub fn covers(&self, ern: &Ern) -> bool {
+        self.iter()
+            .any(|entry| entry == ern || ern.is_child_of(entry))
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    c == ',' || c.is_whitespace()
+}
+
+impl FromStr for ErnList {
+    type Err = ErnError;
+
+    /// Parses the text form, ignoring empty entries; fails naming the first entry that is not a valid ERN.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries = vec![String::new()];
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    let escaped = chars
+                        .next()
+                        .ok_or_else(|| ErnError::parse_failure("ERN list", "trailing `\\`"))?;
+                    entries.last_mut().expect("never empty").push(escaped);
+                }
+                c if is_separator(c) => entries.push(String::new()),
+                c => entries.last_mut().expect("never empty").push(c),
+            }
+        }
+        entries
+            .into_iter()
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                ErnParser::new(entry.clone()).parse().map_err(|error| {
+                    ErnError::parse_failure("ERN list", format!("`{entry}`: {error}"))
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for ErnList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, ern) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            for c in ern.to_string().chars() {
+                if c == '\\' || is_separator(c) {
+                    f.write_str("\\")?;
+                }
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<Ern> for ErnList {
+    fn from_iter<I: IntoIterator<Item = Ern>>(iter: I) -> Self {
+        let mut list = ErnList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl Extend<Ern> for ErnList {
+    fn extend<I: IntoIterator<Item = Ern>>(&mut self, iter: I) {
+        for ern in iter {
+            self.insert(ern);
+        }
+    }
+}
+
+impl IntoIterator for ErnList {
+    type Item = Ern;
+    type IntoIter = std::vec::IntoIter<Ern>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ErnList {
+    type Item = &'a Ern;
+    type IntoIter = std::slice::Iter<'a, Ern>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErnList {
+    /// Serializes as the text form, which fits in environment variables and single-line config values.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ErnList {
+    /// Accepts either the text form or a sequence of ERN strings in self-describing formats, and
+    /// the text form that [`Serialize`](serde::Serialize) writes in binary ones such as postcard.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ErnListVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ErnListVisitor {
+            type Value = ErnList;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a list of ERNs as a string or a sequence of strings")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut list = ErnList::new();
+                while let Some(value) = seq.next_element::<String>()? {
+                    let ern = ErnParser::new(value.clone()).parse().map_err(|error| {
+                        serde::de::Error::custom(format!("invalid ERN `{value}`: {error}"))
+                    })?;
+                    list.insert(ern);
+                }
+                Ok(list)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(ErnListVisitor)
+        } else {
+            deserializer.deserialize_str(ErnListVisitor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_form_roundtrips() -> anyhow::Result<()> {
+        let first = Ern::with_root("orders")?;
+        let second = Ern::with_root("users")?.with_parts(["with space", "a,b", "back\\slash"])?;
+        let list: ErnList = [first.clone(), second.clone(), first.clone()]
+            .into_iter()
+            .collect();
+        assert_eq!(list.len(), 2);
+        let text = list.to_string();
+        assert!(text.ends_with("/with\\ space/a\\,b/back\\\\slash"));
+        assert_eq!(text.parse::<ErnList>()?, list);
+
+        let loose = format!("\n  {}  ", text.replacen(", ", ",,\t", 1));
+        assert_eq!(loose.parse::<ErnList>()?, list);
+        assert_eq!("".parse::<ErnList>()?, ErnList::new());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_entry() -> anyhow::Result<()> {
+        let first = Ern::with_root("orders")?;
+        let error = format!("{first}, urn:x").parse::<ErnList>().unwrap_err();
+        assert!(
+            matches!(error, ErnError::ParseFailure("ERN list", ref message) if message.starts_with("`urn:x`"))
+        );
+        assert!(format!("{first}\\").parse::<ErnList>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_operations() -> anyhow::Result<()> {
+        let (a, b, c) = (
+            Ern::with_root("a")?,
+            Ern::with_root("b")?,
+            Ern::with_root("c")?,
+        );
+        let left: ErnList = [a.clone(), b.clone()].into_iter().collect();
+        let right: ErnList = [b.clone(), c.clone()].into_iter().collect();
+        assert_eq!(
+            left.union(&right).into_iter().collect::<Vec<_>>(),
+            vec![a.clone(), b.clone(), c.clone()]
+        );
+        assert_eq!(
+            left.intersection(&right).into_iter().collect::<Vec<_>>(),
+            vec![b.clone()]
+        );
+        assert_eq!(
+            left.difference(&right).into_iter().collect::<Vec<_>>(),
+            vec![a.clone()]
+        );
+        assert!(left.intersection(&right).is_subset(&left));
+        assert!(left.covers(&a.add_part("child")?));
+        assert!(!left.covers(&c));
+
+        let mut list = left.clone();
+        assert!(!list.insert(a.clone()));
+        assert!(list.remove(&a));
+        assert!(!list.contains(&a));
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_accepts_text_and_sequences() -> anyhow::Result<()> {
+        let list: ErnList = [Ern::with_root("a")?, Ern::with_root("b")?]
+            .into_iter()
+            .collect();
+        let json = serde_json::to_string(&list)?;
+        assert_eq!(serde_json::from_str::<ErnList>(&json)?, list);
+        let strings: Vec<String> = list.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            serde_json::from_value::<ErnList>(serde_json::json!(strings))?,
+            list
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrips_through_postcard() -> anyhow::Result<()> {
+        let list: ErnList = [
+            Ern::with_root("a")?,
+            Ern::with_root("b")?.add_part("with space")?,
+        ]
+        .into_iter()
+        .collect();
+        let bytes = postcard::to_allocvec(&list)?;
+        assert_eq!(postcard::from_bytes::<ErnList>(&bytes)?, list);
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::Ern;
+
+/// Controls which fields [`Ern::log_fields`] emits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LogFieldPolicy {
+    /// Emit every field, including the unique `root_name`.
+    #[default]
+    Full,
+    /// Omit `root_name`, whose value is unique per resource, for sinks that index every field.
+    LowCardinality,
+}
+
+impl Ern {
+    /// Returns the standard structured-logging fields for this ERN (Entity Resource Name).
+    ///
+    /// Keys are always emitted in the order `domain`, `category`, `account`, `root_name`, `depth`;
+    /// `root_name` is dropped under [`LogFieldPolicy::LowCardinality`].
+    pub fn log_fields(&self, policy: LogFieldPolicy) -> Vec<(&'static str, String)> {
+        let mut fields = vec![
+            ("domain", self.domain.to_string()),
+            ("category", self.category.to_string()),
+            ("account", self.account.to_string()),
+        ];
+        if policy == LogFieldPolicy::Full {
+            fields.push(("root_name", self.root.to_string()));
+        }
+        fields.push(("depth", self.parts.len().to_string()));
+        fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_log_fields() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["a", "b"])?;
+        let fields = ern.log_fields(LogFieldPolicy::Full);
+        let keys: Vec<_> = fields.iter().map(|(key, _)| *key).collect();
+        assert_eq!(
+            keys,
+            ["domain", "category", "account", "root_name", "depth"]
+        );
+        assert_eq!(fields[3].1, ern.root.to_string());
+        assert_eq!(fields[4].1, "2");
+        Ok(())
+    }
+
+    #[test]
+    fn test_low_cardinality_drops_root() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?;
+        let fields = ern.log_fields(LogFieldPolicy::LowCardinality);
+        assert!(fields.iter().all(|(key, _)| *key != "root_name"));
+        assert_eq!(fields.len(), 4);
+        Ok(())
+    }
+}
@@ -0,0 +1,472 @@
+use std::collections::btree_map::{self, BTreeMap};
+use std::fmt;
+
+use crate::model::prefix::segments;
+use crate::model::Ern;
+
+/// One component of the hierarchy, holding the ERNs that end here and the components below.
+#[derive(Clone, PartialEq, Eq)]
+struct Node<V> {
+    /// The number of entries at or below this node.
+    len: usize,
+    /// ERNs ending at this node, which can only differ by revision.
+    entries: BTreeMap<Option<u64>, (Ern, V)>,
+    children: BTreeMap<String, Node<V>>,
+}
+
+impl<V> Default for Node<V> {
+    fn default() -> Self {
+        Self {
+            len: 0,
+            entries: BTreeMap::new(),
+            children: BTreeMap::new(),
+        }
+    }
+}
+
+impl<V> Node<V> {
+    fn find<'a>(&self, mut path: impl Iterator<Item = &'a str>) -> Option<&Node<V>> {
+        path.try_fold(self, |node, segment| node.children.get(segment))
+    }
+
+    fn remove(&mut self, path: &[&str], revision: Option<u64>) -> Option<V> {
+        let removed = match path.split_first() {
+            None => self.entries.remove(&revision).map(|(_, value)| value),
+            Some((segment, rest)) => {
+                let child = self.children.get_mut(*segment)?;
+                let removed = child.remove(rest, revision);
+                if child.len == 0 {
+                    self.children.remove(*segment);
+                }
+                removed
+            }
+        };
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+}
+
+/// A map keyed by ERNs (Entity Resource Names), stored as a trie of their components so that
+/// everything beneath a resource can be found without scanning the whole map.
+///
+/// Each level of the trie is one component, from the domain down through the root and each
+/// part. Keys that differ only by revision are distinct entries on the same node; prefix lookups
+/// ignore revisions. Iteration is in hierarchy order: an ERN comes before its descendants, and
+/// siblings are ordered by component, then revision.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ErnMap<V> {
+    root: Node<V>,
+}
+
+impl<V> Default for ErnMap<V> {
+    fn default() -> Self {
+        Self {
+            root: Node::default(),
+        }
+    }
+}
+
+impl<V> ErnMap<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.root.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.len == 0
+    }
+
+    /// Inserts `value` under `ern`, returning the value it replaced, if any.
+    pub fn insert(&mut self, ern: Ern, value: V) -> Option<V> {
+        if let Some(existing) = self.get_mut(&ern) {
+            return Some(std::mem::replace(existing, value));
+        }
+        let mut node = &mut self.root;
+        node.len += 1;
+        for segment in segments(&ern) {
+            node = node.children.entry(segment.to_string()).or_default();
+            node.len += 1;
+        }
+        node.entries.insert(ern.revision(), (ern, value));
+        None
+    }
+
+    pub fn get(&self, ern: &Ern) -> Option<&V> {
+        let node = self.root.find(segments(ern))?;
+        node.entries.get(&ern.revision()).map(|(_, value)| value)
+    }
+
+    pub fn get_mut(&mut self, ern: &Ern) -> Option<&mut V> {
+        let mut node = &mut self.root;
+        for segment in segments(ern) {
+            node = node.children.get_mut(segment)?;
+        }
+        node.entries
+            .get_mut(&ern.revision())
+            .map(|(_, value)| value)
+    }
+
+    pub fn contains_key(&self, ern: &Ern) -> bool {
+        self.get(ern).is_some()
+    }
+
+    /// Removes `ern`, returning its value, and drops any trie levels left empty.
+    pub fn remove(&mut self, ern: &Ern) -> Option<V> {
+        let path: Vec<&str> = segments(ern).collect();
+        self.root.remove(&path, ern.revision())
+    }
+
+    /// Returns every entry in hierarchy order.
+    pub fn iter(&self) -> ErnMapIter<'_, V> {
+        ErnMapIter::new(Some(&self.root))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &Ern> {
+        self.iter().map(|(ern, _)| ern)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Returns the entries for `ern` and all of its descendants, in hierarchy order.
+    pub fn iter_prefix(&self, ern: &Ern) -> ErnMapIter<'_, V> {
+        ErnMapIter::new(self.root.find(segments(ern)))
+    }
+
+    /// Returns a view of the part of the map at or below `ern`, or `None` if nothing is stored there.
+    pub fn subtree(&self, ern: &Ern) -> Option<ErnSubtree<'_, V>> {
+        self.root
+            .find(segments(ern))
+            .map(|node| ErnSubtree { node })
+    }
+
+    /// Returns the entry for the deepest key that is `ern` or one of its ancestors.
+    ///
+    /// Among keys that differ only by revision, the latest revision wins.
+    pub fn longest_prefix_match(&self, ern: &Ern) -> Option<(&Ern, &V)> {
+        let mut node = &self.root;
+        let mut best = node.entries.values().next_back();
+        for segment in segments(ern) {
+            match node.children.get(segment) {
+                Some(child) => node = child,
+                None => break,
+            }
+            best = node.entries.values().next_back().or(best);
+        }
+        best.map(|(ern, value)| (ern, value))
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for ErnMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// A borrowed view of the entries at or below one ERN (Entity Resource Name) in an [`ErnMap`];
+/// created by [`ErnMap::subtree`].
+#[derive(Clone, Copy)]
+pub struct ErnSubtree<'a, V> {
+    node: &'a Node<V>,
+}
+
+impl<'a, V> ErnSubtree<'a, V> {
+    /// Returns the number of entries in the subtree, without walking it.
+    pub fn len(&self) -> usize {
+        self.node.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.node.len == 0
+    }
+
+    /// Returns the entries stored exactly at the subtree's ERN, one per revision.
+    pub fn entries(&self) -> impl Iterator<Item = (&'a Ern, &'a V)> {
+        self.node.entries.values().map(|(ern, value)| (ern, value))
+    }
+
+    /// Returns the next level down, keyed by the component that leads to it.
+    pub fn children(&self) -> impl Iterator<Item = (&'a str, ErnSubtree<'a, V>)> {
+        self.node
+            .children
+            .iter()
+            .map(|(segment, node)| (segment.as_str(), ErnSubtree { node }))
+    }
+
+    pub fn iter(&self) -> ErnMapIter<'a, V> {
+        ErnMapIter::new(Some(self.node))
+    }
+}
+
+/// Walks an [`ErnMap`] or part of one in hierarchy order.
+pub struct ErnMapIter<'a, V> {
+    entries: Option<btree_map::Values<'a, Option<u64>, (Ern, V)>>,
+    stack: Vec<btree_map::Values<'a, String, Node<V>>>,
+    remaining: usize,
+}
+
+impl<'a, V> ErnMapIter<'a, V> {
+    fn new(node: Option<&'a Node<V>>) -> Self {
+        match node {
+            Some(node) => Self {
+                entries: Some(node.entries.values()),
+                stack: vec![node.children.values()],
+                remaining: node.len,
+            },
+            None => Self {
+                entries: None,
+                stack: Vec::new(),
+                remaining: 0,
+            },
+        }
+    }
+}
+
+impl<'a, V> Iterator for ErnMapIter<'a, V> {
+    type Item = (&'a Ern, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((ern, value)) = self.entries.as_mut().and_then(Iterator::next) {
+                self.remaining -= 1;
+                return Some((ern, value));
+            }
+            let children = self.stack.last_mut()?;
+            match children.next() {
+                Some(node) => {
+                    self.entries = Some(node.entries.values());
+                    self.stack.push(node.children.values());
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<V> ExactSizeIterator for ErnMapIter<'_, V> {}
+
+impl<'a, V> IntoIterator for &'a ErnMap<V> {
+    type Item = (&'a Ern, &'a V);
+    type IntoIter = ErnMapIter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<V> FromIterator<(Ern, V)> for ErnMap<V> {
+    fn from_iter<T: IntoIterator<Item = (Ern, V)>>(iter: T) -> Self {
+        let mut map = ErnMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<V> Extend<(Ern, V)> for ErnMap<V> {
+    fn extend<T: IntoIterator<Item = (Ern, V)>>(&mut self, iter: T) {
+        for (ern, value) in iter {
+            self.insert(ern, value);
+        }
+    }
+}
+
+/// A set of ERNs (Entity Resource Names) stored as a trie of their components; see [`ErnMap`].
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ErnSet(ErnMap<()>);
+
+impl ErnSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Adds `ern`, returning whether it was not already present.
+    pub fn insert(&mut self, ern: Ern) -> bool {
+        self.0.insert(ern, ()).is_none()
+    }
+
+    pub fn contains(&self, ern: &Ern) -> bool {
+        self.0.contains_key(ern)
+    }
+
+    /// Removes `ern`, returning whether it was present.
+    pub fn remove(&mut self, ern: &Ern) -> bool {
+        self.0.remove(ern).is_some()
+    }
+
+    /// Returns every ERN in hierarchy order.
+    pub fn iter(&self) -> impl Iterator<Item = &Ern> {
+        self.0.keys()
+    }
+
+    /// Returns `ern`, if present, and all of its descendants in the set.
+    pub fn iter_prefix(&self, ern: &Ern) -> impl Iterator<Item = &Ern> {
+        self.0.iter_prefix(ern).map(|(ern, _)| ern)
+    }
+
+    /// Returns a view of the part of the set at or below `ern`, or `None` if nothing is stored there.
+    pub fn subtree(&self, ern: &Ern) -> Option<ErnSubtree<'_, ()>> {
+        self.0.subtree(ern)
+    }
+
+    /// Returns the deepest ERN in the set that is `ern` or one of its ancestors.
+    pub fn longest_prefix_match(&self, ern: &Ern) -> Option<&Ern> {
+        self.0.longest_prefix_match(ern).map(|(ern, _)| ern)
+    }
+}
+
+impl fmt::Debug for ErnSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<Ern> for ErnSet {
+    fn from_iter<T: IntoIterator<Item = Ern>>(iter: T) -> Self {
+        let mut set = ErnSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Ern> for ErnSet {
+    fn extend<T: IntoIterator<Item = Ern>>(&mut self, iter: T) {
+        for ern in iter {
+            self.insert(ern);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> anyhow::Result<(Ern, ErnMap<u32>)> {
+        let orders = Ern::with_root("orders")?;
+        let map = [
+            (orders.clone(), 0),
+            (orders.add_part("eu")?, 1),
+            (orders.add_part("eu")?.add_part("shard")?, 2),
+            (orders.add_part("us")?, 3),
+            (Ern::with_root("users")?, 4),
+        ]
+        .into_iter()
+        .collect();
+        Ok((orders, map))
+    }
+
+    #[test]
+    fn test_insert_get_remove() -> anyhow::Result<()> {
+        let (orders, mut map) = tree()?;
+        let eu = orders.add_part("eu")?;
+        assert_eq!(map.len(), 5);
+        assert_eq!(map.get(&eu), Some(&1));
+        assert_eq!(map.insert(eu.clone(), 10), Some(1));
+        assert_eq!(map.insert(eu.with_revision(2), 11), None);
+        assert_eq!(map.get(&eu), Some(&10));
+        assert_eq!(map.get(&eu.with_revision(2)), Some(&11));
+        assert_eq!(map.get(&eu.add_part("missing")?), None);
+        *map.get_mut(&orders).expect("present") += 100;
+        assert_eq!(map.get(&orders), Some(&100));
+
+        assert_eq!(map.remove(&eu.add_part("shard")?), Some(2));
+        assert_eq!(map.remove(&eu.add_part("shard")?), None);
+        assert_eq!(map.len(), 5);
+        assert_eq!(
+            map.subtree(&eu).map(|subtree| subtree.children().count()),
+            Some(0)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_iteration_follows_the_hierarchy() -> anyhow::Result<()> {
+        let (orders, map) = tree()?;
+        let values: Vec<u32> = map.iter_prefix(&orders).map(|(_, value)| *value).collect();
+        assert_eq!(values, vec![0, 1, 2, 3]);
+        assert_eq!(map.iter_prefix(&orders.add_part("eu")?).len(), 2);
+        assert_eq!(map.iter_prefix(&orders.add_part("asia")?).count(), 0);
+        assert_eq!(map.iter().count(), 5);
+        assert!(map.iter().all(|(ern, value)| map.get(ern) == Some(value)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_subtree_view() -> anyhow::Result<()> {
+        let (orders, map) = tree()?;
+        let subtree = map.subtree(&orders).expect("present");
+        assert_eq!(subtree.len(), 4);
+        assert_eq!(
+            subtree
+                .entries()
+                .map(|(_, value)| *value)
+                .collect::<Vec<_>>(),
+            vec![0]
+        );
+        let children: Vec<(&str, usize)> = subtree
+            .children()
+            .map(|(segment, child)| (segment, child.len()))
+            .collect();
+        assert_eq!(children, vec![("eu", 2), ("us", 1)]);
+        assert!(map.subtree(&orders.add_part("asia")?).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_longest_prefix_match() -> anyhow::Result<()> {
+        let (orders, map) = tree()?;
+        let deep = orders
+            .add_part("eu")?
+            .add_part("shard")?
+            .add_part("replica")?;
+        assert_eq!(
+            map.longest_prefix_match(&deep).map(|(_, value)| *value),
+            Some(2)
+        );
+        assert_eq!(
+            map.longest_prefix_match(&orders.add_part("asia")?)
+                .map(|(_, value)| *value),
+            Some(0)
+        );
+        assert_eq!(map.longest_prefix_match(&Ern::with_root("billing")?), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_set() -> anyhow::Result<()> {
+        let orders = Ern::with_root("orders")?;
+        let mut set: ErnSet = [orders.clone(), orders.add_part("eu")?]
+            .into_iter()
+            .collect();
+        assert!(!set.insert(orders.clone()));
+        assert!(set.contains(&orders.add_part("eu")?));
+        assert_eq!(set.iter_prefix(&orders).count(), 2);
+        assert_eq!(
+            set.longest_prefix_match(&orders.add_part("us")?),
+            Some(&orders)
+        );
+        assert!(set.remove(&orders));
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.subtree(&orders).map(|subtree| subtree.len()), Some(1));
+        Ok(())
+    }
+}
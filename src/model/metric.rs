@@ -0,0 +1,170 @@
+use crate::hash::stable_hash64;
+use crate::Ern;
+
+/// The shortest `max_len` [`Ern::to_metric_name`] accepts: room for the separator and the 8-digit
+/// hash that end a truncated name.
+pub const MIN_METRIC_NAME_LENGTH: usize = 9;
+
+/// Character rules for metric names derived from an ERN (Entity Resource Name).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetricSanitizer {
+    /// Prometheus names: `[a-zA-Z_][a-zA-Z0-9_]*`, components joined by `_`.
+    Prometheus,
+    /// StatsD names: ASCII letters, digits, `_`, and `-`, components joined by `.`.
+    StatsD,
+}
+
+impl MetricSanitizer {
+    fn separator(self) -> char {
+        match self {
+            MetricSanitizer::Prometheus => '_',
+            MetricSanitizer::StatsD => '.',
+        }
+    }
+
+    fn is_allowed(self, c: char) -> bool {
+        match self {
+            MetricSanitizer::Prometheus => c.is_ascii_alphanumeric() || c == '_',
+            MetricSanitizer::StatsD => c.is_ascii_alphanumeric() || c == '_' || c == '-',
+        }
+    }
+}
+
+impl Ern {
+    /// Derives a metric name that is safe for the given backend and at most `max_len` bytes long.
+    ///
+    /// The root contributes only its base name, not its unique id, so one series covers every
+    /// instance of a kind of resource. Disallowed characters become `_`. Names longer than
+    /// `max_len` are truncated and suffixed with a 32-bit hash of the full name. The same ERN
+    /// always yields the same name, but names are lossy: ERNs that differ only in replaced
+    /// characters share one, and two long names with the same truncated prefix collide with a
+    /// probability of about 1 in 4 billion (2^-32).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is less than [`MIN_METRIC_NAME_LENGTH`], which leaves no room for the
+    /// hash.
+    pub fn to_metric_name(&self, max_len: usize, sanitizer: MetricSanitizer) -> String {
+        assert!(
+            max_len >= MIN_METRIC_NAME_LENGTH,
+            "metric names need at least {MIN_METRIC_NAME_LENGTH} bytes"
+        );
+        let separator = sanitizer.separator();
+        let components = [
+            self.domain.as_str(),
+            self.category.as_str(),
+            self.account.as_str(),
+            self.root.base_name(),
+        ]
+        .into_iter()
+        .chain((&self.parts).into_iter().map(|part| part.as_str()));
+
+        let mut name = String::new();
+        for component in components.filter(|component| !component.is_empty()) {
+            if !name.is_empty() {
+                name.push(separator);
+            }
+            for c in component.chars() {
+                let c = if sanitizer.is_allowed(c) { c } else { '_' };
+                // Collapse runs of replacement characters so names stay readable
+                if !(c == '_' && name.ends_with('_')) {
+                    name.push(c);
+                }
+            }
+        }
+        if name.starts_with(|c: char| c.is_ascii_digit()) {
+            name.insert(0, '_');
+        }
+
+        if name.len() > max_len {
+            let suffix = format!("{separator}{:08x}", stable_hash64(name.as_bytes()) as u32);
+            // Every character is ASCII at this point, so any byte index is a char boundary
+            name.truncate(max_len - suffix.len());
+            name.push_str(&suffix);
+        }
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prometheus_name() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["eu-west", "shard 1"])?;
+        assert_eq!(
+            ern.to_metric_name(200, MetricSanitizer::Prometheus),
+            "acton_reactive_component_orders_eu_west_shard_1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_statsd_name() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["eu-west"])?;
+        assert_eq!(
+            ern.to_metric_name(200, MetricSanitizer::StatsD),
+            "acton.reactive.component.orders.eu-west"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_name_is_independent_of_root_id() -> anyhow::Result<()> {
+        let first = Ern::with_root("orders")?;
+        let second = Ern::with_root("orders")?;
+        assert_ne!(first, second);
+        assert_eq!(
+            first.to_metric_name(64, MetricSanitizer::Prometheus),
+            second.to_metric_name(64, MetricSanitizer::Prometheus)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow_is_hashed() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?;
+        let left = ern
+            .add_part("a".repeat(50))?
+            .to_metric_name(32, MetricSanitizer::Prometheus);
+        let right = ern
+            .add_part("b".repeat(50))?
+            .to_metric_name(32, MetricSanitizer::Prometheus);
+        assert_eq!(left.len(), 32);
+        assert_eq!(right.len(), 32);
+        assert_ne!(left, right);
+        assert_eq!(
+            left,
+            ern.add_part("a".repeat(50))?
+                .to_metric_name(32, MetricSanitizer::Prometheus)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_shortest_names_are_the_whole_hash() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.add_part("eu")?;
+        let name = ern.to_metric_name(MIN_METRIC_NAME_LENGTH, MetricSanitizer::StatsD);
+        assert_eq!(name.len(), MIN_METRIC_NAME_LENGTH);
+        assert!(name.starts_with('.'));
+        assert!(name[1..].chars().all(|c| c.is_ascii_hexdigit()));
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "metric names need at least")]
+    fn test_max_len_below_the_minimum_panics() {
+        let ern = Ern::with_root("orders").expect("valid root");
+        ern.to_metric_name(8, MetricSanitizer::Prometheus);
+    }
+
+    #[test]
+    fn test_leading_digit_is_escaped() -> anyhow::Result<()> {
+        let ern = Ern::with_domain("9lives")?;
+        assert!(ern
+            .to_metric_name(64, MetricSanitizer::Prometheus)
+            .starts_with("_9lives"));
+        Ok(())
+    }
+}
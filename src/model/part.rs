@@ -5,8 +5,11 @@ use derive_more::{AsRef, Into};
 
 use crate::errors::ErnError;
 
+/// The maximum length, in bytes, of a part produced by [`Part::slugify`].
+pub const MAX_SLUG_LENGTH: usize = 64;
+
 #[derive(AsRef, Into, Eq, Debug, PartialEq, Clone, Hash, PartialOrd)]
-pub struct Part(pub(crate) String);
+pub struct Part(pub(crate) Cow<'static, str>);
 
 impl Part {
     pub fn as_str(&self) -> &str {
@@ -14,10 +17,34 @@ impl Part {
     }
 
     pub fn into_owned(self) -> Part {
-        Part(self.0.to_string())
+        Part(Cow::Owned(self.0.into_owned()))
+    }
+
+    /// Creates a part from a literal in `const` contexts.
+    ///
+    /// Applies the same rules as [`Part::new`], panicking (at compile time when evaluated in a
+    /// `const`) if the literal is empty or contains `:` or `/`.
+    pub const fn from_static(value: &'static str) -> Self {
+        if value.is_empty() {
+            panic!("Part cannot be empty");
+        }
+        let bytes = value.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b':' || bytes[i] == b'/' {
+                panic!("Part cannot contain ':' or '/'");
+            }
+            i += 1;
+        }
+        Part(Cow::Borrowed(value))
     }
 
     pub fn new(value: impl Into<String>) -> Result<Part, ErnError> {
+        Self::from_cow(value.into())
+    }
+
+    /// Creates a part from a `&'static str` or an owned `String` without copying it.
+    pub fn from_cow(value: impl Into<Cow<'static, str>>) -> Result<Part, ErnError> {
         let value = value.into();
         if value.contains(':') || value.contains('/') {
             return Err(ErnError::InvalidPartFormat);
@@ -30,6 +57,72 @@ impl Part {
         }
         Ok(Part(value))
     }
+
+    /// Converts arbitrary text, such as a human-entered name, into a valid part.
+    ///
+    /// The result is lowercase ASCII letters and digits with runs of anything else collapsed into
+    /// single `-` separators, e.g. `"Café Déjà Vu!"` becomes `cafe-deja-vu`. Common Latin letters
+    /// with diacritics are transliterated and other characters are treated as separators. The
+    /// output is capped at [`MAX_SLUG_LENGTH`] bytes and is the same for the same input. Fails if
+    /// no letters or digits remain.
+    pub fn slugify(input: &str) -> Result<Part, ErnError> {
+        let mut slug = String::with_capacity(input.len().min(MAX_SLUG_LENGTH));
+        let mut pending_separator = false;
+        let mut ascii = [0; 4];
+        for c in input.chars().flat_map(char::to_lowercase) {
+            let replacement: &str = if c.is_ascii_alphanumeric() {
+                c.encode_utf8(&mut ascii)
+            } else {
+                transliterate(c)
+            };
+            if replacement.is_empty() {
+                pending_separator = true;
+                continue;
+            }
+            if pending_separator && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_separator = false;
+            if slug.len() + replacement.len() > MAX_SLUG_LENGTH {
+                break;
+            }
+            slug.push_str(replacement);
+        }
+        let slug = slug.trim_end_matches('-');
+        if slug.is_empty() {
+            return Err(ErnError::ParseFailure(
+                "Part",
+                format!("no letters or digits to slugify in '{input}'"),
+            ));
+        }
+        Ok(Part(Cow::Owned(slug.to_string())))
+    }
+}
+
+/// Returns the ASCII spelling of a lowercase non-ASCII letter, or "" for anything without one.
+fn transliterate(c: char) -> &'static str {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' | 'ı' => "i",
+        'ł' | 'ľ' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' => "r",
+        'ß' => "ss",
+        'ś' | 'š' | 'ş' => "s",
+        'ť' | 'ţ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' | 'ų' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => "",
+    }
 }
 
 impl fmt::Display for Part {
@@ -45,11 +138,11 @@ impl std::str::FromStr for Part {
     }
 }
 
-// impl From<Part> for String {
-//     fn from(part: Part) -> Self {
-//         part.0
-//     }
-// }
+impl From<Part> for String {
+    fn from(value: Part) -> Self {
+        value.0.into_owned()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -62,6 +155,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_part_accepts_borrowed_and_cow_values() -> anyhow::Result<()> {
+        let owned = String::from("segment");
+        assert_eq!(Part::new(&owned)?, Part::new(owned.as_str())?);
+        let part = Part::from_cow("segment")?;
+        assert!(matches!(part.0, Cow::Borrowed(_)));
+        assert_eq!(part, Part::new(owned)?);
+        assert!(Part::from_cow(String::from("a/b")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_from_static_in_const() {
+        const PART: Part = Part::from_static("static-segment");
+        assert_eq!(PART, Part::new("static-segment").unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot contain")]
+    fn test_part_from_static_rejects_separators() {
+        let _ = Part::from_static(std::hint::black_box("a/b"));
+    }
+
+    #[test]
+    fn test_part_slugify() -> anyhow::Result<()> {
+        assert_eq!(Part::slugify("Café Déjà Vu!")?.as_str(), "cafe-deja-vu");
+        assert_eq!(
+            Part::slugify("  Straße / Øresund -- 2024 ")?.as_str(),
+            "strasse-oresund-2024"
+        );
+        assert_eq!(Part::slugify("a:b/c")?.as_str(), "a-b-c");
+        let long = Part::slugify(&"word ".repeat(40))?;
+        assert!(long.as_str().len() <= MAX_SLUG_LENGTH);
+        assert!(!long.as_str().ends_with('-'));
+        assert!(Part::slugify("!!! 日本 ???").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_part_display() -> anyhow::Result<()> {
         let part = Part::new("example")?;
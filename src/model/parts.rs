@@ -25,7 +25,7 @@ impl Parts {
 
     /// Converts the Parts into an owned version with 'static lifetime
     pub fn into_owned(self) -> Parts {
-        Parts(self.0.into_iter().map(|part| part).collect())
+        Parts(self.0.into_iter().collect())
     }
 
     /// Returns the number of parts in the collection.
@@ -37,6 +37,88 @@ impl Parts {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Returns the first part, or `None` if the collection is empty.
+    pub fn first(&self) -> Option<&Part> {
+        self.0.first()
+    }
+
+    /// Returns the last part, or `None` if the collection is empty.
+    pub fn last(&self) -> Option<&Part> {
+        self.0.last()
+    }
+
+    /// Splits the collection into the first `n` parts and the remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the number of parts, matching [`slice::split_at`].
+    pub fn split_at(&self, n: usize) -> (Parts, Parts) {
+        let (head, tail) = self.0.split_at(n);
+        (Parts(head.to_vec()), Parts(tail.to_vec()))
+    }
+
+    /// Returns a new collection with the parts of `other` appended after these parts.
+    pub fn join(&self, other: &Parts) -> Parts {
+        let mut joined = Vec::with_capacity(self.0.len() + other.0.len());
+        joined.extend_from_slice(&self.0);
+        joined.extend_from_slice(&other.0);
+        Parts(joined)
+    }
+
+    /// Returns the most-repeated back-to-back sequence of parts, if any sequence repeats.
+    ///
+    /// For `a/b/a/b/a/b` this reports period 2 repeated 3 times; for `a/x/x` it reports the
+    /// adjacent duplicate `x`. Ties go to the shortest period, then the earliest start.
+    pub fn repetition(&self) -> Option<PartRepetition> {
+        self.repetition_within(self.0.len() / 2)
+    }
+
+    /// Like [`Parts::repetition`], but only considers sequences of at most `max_period` parts.
+    ///
+    /// Each period is scanned once, counting how many parts in a row equal the part `period`
+    /// places later, so the work is at most `max_period` passes over the parts.
+    pub(crate) fn repetition_within(&self, max_period: usize) -> Option<PartRepetition> {
+        let parts = &self.0;
+        let mut best: Option<PartRepetition> = None;
+        let mut record = |start: usize, period: usize, run: usize| {
+            let repeats = 1 + run / period;
+            if repeats >= 2 && best.is_none_or(|best| repeats > best.repeats) {
+                best = Some(PartRepetition {
+                    start,
+                    period,
+                    repeats,
+                });
+            }
+        };
+        for period in 1..=max_period.min(parts.len() / 2) {
+            let (mut start, mut run) = (0, 0);
+            for index in 0..parts.len() - period {
+                if parts[index] == parts[index + period] {
+                    if run == 0 {
+                        start = index;
+                    }
+                    run += 1;
+                } else {
+                    record(start, period, run);
+                    run = 0;
+                }
+            }
+            record(start, period, run);
+        }
+        best
+    }
+}
+
+/// A run of parts in which one sequence repeats back-to-back, as found by [`Parts::repetition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PartRepetition {
+    /// The index of the first part of the run.
+    pub start: usize,
+    /// The length of the repeated sequence; `1` for adjacent duplicates.
+    pub period: usize,
+    /// How many times the sequence occurs in a row, at least `2`.
+    pub repeats: usize,
 }
 
 impl Hash for Parts {
@@ -140,6 +222,77 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parts_first_and_last() -> anyhow::Result<()> {
+        let parts = Parts::new(vec![Part::new("segment1")?, Part::new("segment2")?]);
+        assert_eq!(parts.first().map(Part::as_str), Some("segment1"));
+        assert_eq!(parts.last().map(Part::as_str), Some("segment2"));
+        assert_eq!(Parts::default().first(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parts_split_at_and_join() -> anyhow::Result<()> {
+        let parts = Parts::new(vec![Part::new("a")?, Part::new("b")?, Part::new("c")?]);
+        let (head, tail) = parts.split_at(1);
+        assert_eq!(head.to_string(), "a");
+        assert_eq!(tail.to_string(), "b/c");
+        assert_eq!(head.join(&tail), parts);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parts_repetition() -> anyhow::Result<()> {
+        let parts = |values: &[&'static str]| {
+            values
+                .iter()
+                .map(|v| Part::new(*v))
+                .collect::<Result<Parts, _>>()
+        };
+        assert_eq!(
+            parts(&["root", "a", "b", "a", "b", "a", "b"])?.repetition(),
+            Some(PartRepetition {
+                start: 1,
+                period: 2,
+                repeats: 3
+            })
+        );
+        assert_eq!(
+            parts(&["a", "x", "x"])?.repetition(),
+            Some(PartRepetition {
+                start: 1,
+                period: 1,
+                repeats: 2
+            })
+        );
+        assert_eq!(
+            parts(&["a", "b", "a", "c", "c", "c"])?.repetition(),
+            Some(PartRepetition {
+                start: 3,
+                period: 1,
+                repeats: 3
+            })
+        );
+        assert_eq!(parts(&["a", "b", "c", "a"])?.repetition(), None);
+        assert_eq!(Parts::default().repetition(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parts_repetition_on_uniform_input() -> anyhow::Result<()> {
+        let parts = Parts::new(vec![Part::new("a")?; 2048]);
+        assert_eq!(
+            parts.repetition(),
+            Some(PartRepetition {
+                start: 0,
+                period: 1,
+                repeats: 2048
+            })
+        );
+        assert_eq!(parts.repetition_within(0), None);
+        Ok(())
+    }
+
     #[test]
     fn test_parts_for_loop() -> anyhow::Result<()> {
         let parts = Parts::new(vec![Part::new("segment1")?, Part::new("segment2")?]);
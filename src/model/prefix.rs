@@ -0,0 +1,139 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::ErnError;
+use crate::model::Ern;
+
+/// The leading components of an ERN (Entity Resource Name) hierarchy, such as `ern:billing:invoices`.
+///
+/// Components are compared whole and in hierarchy order (domain, category, account, root, then
+/// each part), so `ern:billing` covers `ern:billing:invoices:…` but not `ern:billing-eu:…`. A
+/// root component must be the full root id. The empty prefix covers every ERN.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct ErnPrefix(Vec<String>);
+
+impl ErnPrefix {
+    /// Returns the first `depth` components of `ern`, or all of them if it has fewer.
+    pub fn of(ern: &Ern, depth: usize) -> Self {
+        ErnPrefix(segments(ern).take(depth).map(str::to_string).collect())
+    }
+
+    /// Returns the number of components in the prefix.
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if `ern` lies at or below this prefix.
+    pub fn contains(&self, ern: &Ern) -> bool {
+        let mut segments = segments(ern);
+        self.0
+            .iter()
+            .all(|component| segments.next() == Some(component.as_str()))
+    }
+
+    /// Returns true if one prefix contains the other, so both would claim some ERNs.
+    pub fn overlaps(&self, other: &ErnPrefix) -> bool {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .all(|(left, right)| left == right)
+    }
+
+    /// Returns the components in hierarchy order.
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+pub(crate) fn segments(ern: &Ern) -> impl Iterator<Item = &str> {
+    [
+        ern.domain.as_str(),
+        ern.category.as_str(),
+        ern.account.as_str(),
+        ern.root.as_str(),
+    ]
+    .into_iter()
+    .chain((&ern.parts).into_iter().map(|part| part.as_str()))
+}
+
+impl FromStr for ErnPrefix {
+    type Err = ErnError;
+
+    /// Parses `ern:domain:category:account:root/part…` truncated after any component; the `ern:`
+    /// scheme is optional.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.strip_prefix("ern:").unwrap_or(value);
+        if value.is_empty() || value == "ern" {
+            return Ok(ErnPrefix::default());
+        }
+        let mut components: Vec<String> = Vec::new();
+        let mut fields = value.splitn(4, ':');
+        for field in fields.by_ref().take(3) {
+            components.push(field.to_string());
+        }
+        if let Some(rest) = fields.next() {
+            components.extend(rest.split('/').map(str::to_string));
+        }
+        if components.iter().any(String::is_empty) || components.len() < 4 && value.contains('/') {
+            return Err(ErnError::ParseFailure(
+                "prefix",
+                format!("'{value}' is not a valid ERN prefix"),
+            ));
+        }
+        Ok(ErnPrefix(components))
+    }
+}
+
+impl fmt::Display for ErnPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ern")?;
+        for (index, component) in self.0.iter().enumerate() {
+            let separator = if index < 4 { ':' } else { '/' };
+            write!(f, "{separator}{component}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_parse_and_display() -> anyhow::Result<()> {
+        let prefix: ErnPrefix = "billing:invoices".parse()?;
+        assert_eq!(prefix.depth(), 2);
+        assert_eq!(prefix.to_string(), "ern:billing:invoices");
+        assert_eq!("ern:billing:invoices".parse::<ErnPrefix>()?, prefix);
+        let deep: ErnPrefix = "ern:a:b:c:root_01h455vb4pex5vsknk084sn02q/eu".parse()?;
+        assert_eq!(deep.depth(), 5);
+        assert_eq!(
+            deep.to_string(),
+            "ern:a:b:c:root_01h455vb4pex5vsknk084sn02q/eu"
+        );
+        assert!("billing::x".parse::<ErnPrefix>().is_err());
+        assert!("billing/eu".parse::<ErnPrefix>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefix_contains_whole_components() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.add_part("eu")?;
+        assert!("acton:reactive".parse::<ErnPrefix>()?.contains(&ern));
+        assert!(!"acton:react".parse::<ErnPrefix>()?.contains(&ern));
+        assert!(ErnPrefix::of(&ern, 5).contains(&ern));
+        assert!(!ErnPrefix::of(&ern.add_part("x")?, 6).contains(&ern));
+        assert!(ErnPrefix::default().contains(&ern));
+        assert_eq!(ErnPrefix::of(&ern, 9).depth(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefix_overlaps() -> anyhow::Result<()> {
+        let billing: ErnPrefix = "billing".parse()?;
+        assert!(billing.overlaps(&"billing:invoices".parse()?));
+        assert!("billing:invoices".parse::<ErnPrefix>()?.overlaps(&billing));
+        assert!(!billing.overlaps(&"billing-eu".parse()?));
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::Write;
+
+use crate::{Domain, Ern, ErnComponent};
+
+/// The granularity at which requests against an ERN (Entity Resource Name) share a rate limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateScope {
+    /// One bucket per tenant: domain, category, and account.
+    Account,
+    /// One bucket per resource root.
+    Root,
+    /// One bucket per subtree rooted at the first `n` parts below the root.
+    FirstParts(usize),
+}
+
+impl Ern {
+    /// Returns a stable key identifying the rate-limit bucket this ERN (Entity Resource Name) falls into.
+    ///
+    /// Keys are prefixes of the canonical form, so they are readable and two ERNs share a key
+    /// exactly when they agree on every component the scope covers. Revisions never split buckets.
+    pub fn rate_limit_key(&self, scope: RateScope) -> String {
+        let mut key = format!(
+            "{}{}:{}:{}",
+            Domain::prefix(),
+            self.domain,
+            self.category,
+            self.account
+        );
+        let depth = match scope {
+            RateScope::Account => return key,
+            RateScope::Root => 0,
+            RateScope::FirstParts(n) => n,
+        };
+        // Writing to a String cannot fail
+        let _ = write!(key, ":{}", self.root);
+        for part in (&self.parts).into_iter().take(depth) {
+            let _ = write!(key, "/{}", part);
+        }
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_scope() -> anyhow::Result<()> {
+        let ern = Ern::with_account("tenant1")?.with_parts(["a", "b"])?;
+        assert_eq!(
+            ern.rate_limit_key(RateScope::Account),
+            "ern:acton:reactive:tenant1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_root_scope_ignores_parts_and_revision() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["a"])?;
+        let other = ern.with_parts(["b"])?.with_revision(3);
+        assert_eq!(
+            ern.rate_limit_key(RateScope::Root),
+            other.rate_limit_key(RateScope::Root)
+        );
+        assert!(ern
+            .rate_limit_key(RateScope::Root)
+            .ends_with(ern.root.as_str()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_first_parts_scope() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["eu", "shard1", "item9"])?;
+        let sibling = ern.with_parts(["eu", "shard2"])?;
+        assert_eq!(
+            ern.rate_limit_key(RateScope::FirstParts(1)),
+            sibling.rate_limit_key(RateScope::FirstParts(1))
+        );
+        assert_ne!(
+            ern.rate_limit_key(RateScope::FirstParts(2)),
+            sibling.rate_limit_key(RateScope::FirstParts(2))
+        );
+        assert!(ern
+            .rate_limit_key(RateScope::FirstParts(10))
+            .ends_with("eu/shard1/item9"));
+        Ok(())
+    }
+}
@@ -1,12 +1,19 @@
 use std::fmt;
 use std::hash::Hash;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use derive_more::{AsRef, From, Into};
 use mti::prelude::*;
 
 use crate::errors::ErnError;
+use crate::hash::stable_hash64;
 
-
+/// The root of an ERN (Entity Resource Name) hierarchy: a name plus a unique id, e.g. `orders_01h455vb4pex5vsknk084sn02q`.
+///
+/// Roots compare and hash by their full value, so two roots created from the same name are
+/// different. Wrap them in [`RootByName`](crate::RootByName) or
+/// [`RootByTimestamp`](crate::RootByTimestamp) to compare only part of the value instead.
 #[derive(AsRef, From, Into, Eq, Debug, PartialEq, Clone, Hash, Default, PartialOrd)]
 pub struct EntityRoot {
     name: MagicTypeId,
@@ -20,12 +27,49 @@ impl EntityRoot {
         &self.name
     }
 
+    /// Returns the name the root was created from, without the generated id suffix.
+    pub fn base_name(&self) -> &str {
+        self.name.prefix().as_str()
+    }
 
     pub fn new(value: String) -> Result<Self, ErnError> {
         Ok(EntityRoot {
             name: value.create_type_id::<V7>(),
         })
     }
+
+    /// Returns the creation time embedded in the root id, if it is time-based (UUIDv7).
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        let uuid = self.name.suffix().to_uuid();
+        if uuid.get_version_num() != 7 {
+            return None;
+        }
+        // The first 48 bits of a UUIDv7 hold the Unix timestamp in milliseconds
+        let millis = uuid.as_bytes()[..6]
+            .iter()
+            .fold(0u64, |acc, &byte| (acc << 8) | u64::from(byte));
+        Some(UNIX_EPOCH + Duration::from_millis(millis))
+    }
+
+    /// Creates a root whose id is derived from `key` rather than generated, so equal keys always
+    /// produce equal roots. The id is a UUIDv8 and carries no timestamp.
+    pub(crate) fn derived(name: &str, key: &[u8]) -> Self {
+        let high = stable_hash64(key);
+        let low = stable_hash64(&[key, b"#derived-root"].concat());
+        // Mark the UUID as version 8 (custom) with the RFC 9562 variant
+        let bits = (u128::from(high) << 64 | u128::from(low)) & !(0xf << 76) & !(0x3 << 62);
+        let uuid = Uuid::from_u128(bits | 0x8 << 76 | 0x2 << 62);
+        EntityRoot {
+            name: name.create_type_id_with_suffix::<V7>(TypeIdSuffix::from(uuid)),
+        }
+    }
+
+    /// Parses an existing root identifier (e.g. `root_01h455vb4pex5vsknk084sn02q`) without generating a new one.
+    pub fn parse(value: &str) -> Result<Self, ErnError> {
+        Ok(EntityRoot {
+            name: MagicTypeId::from_str(value)?,
+        })
+    }
 }
 
 impl fmt::Display for EntityRoot {
@@ -36,7 +80,7 @@ impl fmt::Display for EntityRoot {
 }
 
 //
-impl std::str::FromStr for EntityRoot {
+impl FromStr for EntityRoot {
     type Err = ErnError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(EntityRoot {
@@ -44,3 +88,47 @@ impl std::str::FromStr for EntityRoot {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_root_new_generates_id() -> anyhow::Result<()> {
+        let root = EntityRoot::new("root".to_string())?;
+        assert!(root.as_str().starts_with("root_"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_root_parse_preserves_id() -> anyhow::Result<()> {
+        let root = EntityRoot::new("root".to_string())?;
+        let parsed = EntityRoot::parse(root.as_str())?;
+        assert_eq!(root, parsed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_root_timestamp() -> anyhow::Result<()> {
+        let before = SystemTime::now() - Duration::from_millis(1);
+        let root = EntityRoot::new("root".to_string())?;
+        let timestamp = root.timestamp().expect("v7 roots carry a timestamp");
+        assert!(timestamp >= before && timestamp <= SystemTime::now());
+        Ok(())
+    }
+
+    #[test]
+    fn test_root_derived_is_deterministic() -> anyhow::Result<()> {
+        let root = EntityRoot::derived("root", b"key");
+        assert_eq!(root, EntityRoot::derived("root", b"key"));
+        assert_ne!(root, EntityRoot::derived("root", b"other"));
+        assert_eq!(EntityRoot::parse(root.as_str())?, root);
+        assert_eq!(root.timestamp(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_root_parse_rejects_plain_name() {
+        assert!(EntityRoot::parse("root").is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::time::SystemTime;
+
+use crate::model::EntityRoot;
+
+macro_rules! root_comparison {
+    ($(#[$meta:meta])* $name:ident, |$root:ident| $key:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        pub struct $name(pub EntityRoot);
+
+        impl $name {
+            pub fn into_inner(self) -> EntityRoot {
+                self.0
+            }
+
+            fn key(&self) -> impl Ord + Hash + '_ {
+                let $root = &self.0;
+                $key
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.key().cmp(&other.key())
+            }
+        }
+
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.key().hash(state);
+            }
+        }
+
+        impl From<EntityRoot> for $name {
+            fn from(root: EntityRoot) -> Self {
+                Self(root)
+            }
+        }
+
+        impl AsRef<EntityRoot> for $name {
+            fn as_ref(&self) -> &EntityRoot {
+                &self.0
+            }
+        }
+    };
+}
+
+root_comparison!(
+    /// Compares roots by their full value, name and id together, e.g. for exact deduplication.
+    ///
+    /// This is how [`EntityRoot`] itself compares; the wrapper makes the choice explicit and adds
+    /// `Ord`, which orders time-based roots with the same name by creation time.
+    RootByValue, |root| root.as_str()
+);
+root_comparison!(
+    /// Compares roots by the name they were created from only, so `orders_01h4…` and
+    /// `orders_01j9…` are equal, e.g. for grouping instances of the same kind of resource.
+    RootByName, |root| root.base_name()
+);
+root_comparison!(
+    /// Compares roots by the creation time embedded in their ids only, to millisecond precision,
+    /// e.g. for ordering events from different resources on one timeline.
+    ///
+    /// Roots that carry no timestamp (derived or content-based ids) sort before every timestamped
+    /// root and fall back to comparing by full value among themselves, so they are never
+    /// collapsed into one.
+    RootByTimestamp, |root| (root.timestamp(), root.timestamp().map_or(root.as_str(), |_| ""))
+);
+
+impl RootByTimestamp {
+    /// Returns the creation time embedded in the root id, if any.
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        self.0.timestamp()
+    }
+}
+
+impl EntityRoot {
+    /// Wraps a copy of the root so it compares by full value; see [`RootByValue`].
+    pub fn by_value(&self) -> RootByValue {
+        RootByValue(self.clone())
+    }
+
+    /// Wraps a copy of the root so it compares by name only; see [`RootByName`].
+    pub fn by_name(&self) -> RootByName {
+        RootByName(self.clone())
+    }
+
+    /// Wraps a copy of the root so it compares by embedded timestamp only; see [`RootByTimestamp`].
+    pub fn by_timestamp(&self) -> RootByTimestamp {
+        RootByTimestamp(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn root(value: &str) -> anyhow::Result<EntityRoot> {
+        Ok(EntityRoot::parse(value)?)
+    }
+
+    #[test]
+    fn test_strategies_disagree_on_the_same_roots() -> anyhow::Result<()> {
+        let first = root("orders_01h455vb4pex5vsknk084sn02q")?;
+        let later = EntityRoot::new("orders".to_string())?;
+        let same_time = root("users_01h455vb4pex5vsknk084sn02q")?;
+
+        assert_ne!(first.by_value(), later.by_value());
+        assert!(first.by_value() < later.by_value());
+        assert_eq!(first.by_name(), later.by_name());
+        assert_ne!(first.by_name(), same_time.by_name());
+        assert_eq!(first.by_timestamp(), same_time.by_timestamp());
+        assert!(first.by_timestamp() < later.by_timestamp());
+
+        let names: HashSet<RootByName> = [first.clone(), later, same_time]
+            .into_iter()
+            .map(RootByName)
+            .collect();
+        assert_eq!(names.len(), 2);
+        assert_eq!(RootByValue::from(first.clone()).into_inner(), first);
+        Ok(())
+    }
+
+    #[test]
+    fn test_roots_without_timestamps_stay_distinct() -> anyhow::Result<()> {
+        let derived = EntityRoot::derived("broker", b"broker");
+        let other = EntityRoot::derived("broker", b"other");
+        let timed = root("orders_01h455vb4pex5vsknk084sn02q")?;
+        assert_eq!(derived.by_timestamp().timestamp(), None);
+        assert_ne!(derived.by_timestamp(), other.by_timestamp());
+        assert_eq!(derived.by_timestamp(), derived.by_timestamp());
+        assert!(derived.by_timestamp() < timed.by_timestamp());
+        assert!(other.by_timestamp() < timed.by_timestamp());
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::Visitor;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::errors::ErnError;
+use crate::model::{Account, Category, Domain, EntityRoot, Ern, Part, Parts};
+use crate::parser::ErnParser;
+
+/// Deserializes a string and converts it with `parse`, the same conversion the ERN parser applies.
+fn deserialize_str<'de, D, T>(
+    deserializer: D,
+    expecting: &'static str,
+    parse: fn(&str) -> Result<T, ErnError>,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct StrVisitor<T> {
+        expecting: &'static str,
+        parse: fn(&str) -> Result<T, ErnError>,
+    }
+
+    impl<T> Visitor<'_> for StrVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.expecting)
+        }
+
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<T, E> {
+            (self.parse)(value).map_err(|error| {
+                E::custom(format!("invalid {} `{value}`: {error}", self.expecting))
+            })
+        }
+    }
+
+    deserializer.deserialize_str(StrVisitor { expecting, parse })
+}
+
+/// Domains, categories, and accounts are `:`-delimited in an ERN, so they cannot contain one.
+fn check_field(name: &'static str, value: &str) -> Result<(), ErnError> {
+    if value.contains(':') {
+        return Err(ErnError::InvalidCharacter(name, ':'));
+    }
+    Ok(())
+}
+
+impl Serialize for Ern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str(deserializer, "ERN", |value| {
+            ErnParser::new(value.to_string()).parse()
+        })
+    }
+}
+
+impl Serialize for Domain {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Domain {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str(deserializer, "domain", |value| {
+            check_field("domain", value)?;
+            Domain::from_str(value)
+        })
+    }
+}
+
+impl Serialize for Category {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Category {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str(deserializer, "category", |value| {
+            check_field("category", value)?;
+            Ok(Category::new(value.to_string()))
+        })
+    }
+}
+
+impl Serialize for Account {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Account {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str(deserializer, "account", |value| {
+            check_field("account", value)?;
+            Ok(Account::new(value.to_string()))
+        })
+    }
+}
+
+impl Serialize for EntityRoot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityRoot {
+    /// Requires an existing root id such as `root_01h455vb4pex5vsknk084sn02q`; unlike the ERN
+    /// parser, a plain name is rejected rather than given a freshly generated id.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str(deserializer, "root", EntityRoot::parse)
+    }
+}
+
+impl Serialize for Part {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Part {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str(deserializer, "part", Part::from_str)
+    }
+}
+
+impl Serialize for Parts {
+    /// Serializes as the parts joined by `/`, as they appear after the root.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Parts {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str(deserializer, "parts", |value| {
+            if value.is_empty() {
+                return Ok(Parts::default());
+            }
+            value.split('/').map(Part::from_str).collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ern_roundtrips_as_a_string() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?
+            .with_parts(["eu", "shard"])?
+            .with_revision(2);
+        let json = serde_json::to_string(&ern)?;
+        assert_eq!(json, format!("\"{ern}\""));
+        assert_eq!(serde_json::from_str::<Ern>(&json)?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_erns_are_rejected() {
+        let error = serde_json::from_str::<Ern>("\"urn:a:b:c:root\"").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("invalid ERN `urn:a:b:c:root`"));
+        assert!(serde_json::from_str::<Ern>("\"ern:a:b:c:root/x//y\"").is_err());
+        assert!(serde_json::from_str::<Ern>("7").is_err());
+    }
+
+    #[test]
+    fn test_components_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["eu", "shard"])?;
+        assert_eq!(
+            serde_json::from_str::<Domain>(&serde_json::to_string(&ern.domain)?)?,
+            ern.domain
+        );
+        assert_eq!(
+            serde_json::from_str::<Category>(&serde_json::to_string(&ern.category)?)?,
+            ern.category
+        );
+        assert_eq!(
+            serde_json::from_str::<Account>(&serde_json::to_string(&ern.account)?)?,
+            ern.account
+        );
+        assert_eq!(
+            serde_json::from_str::<EntityRoot>(&serde_json::to_string(&ern.root)?)?,
+            ern.root
+        );
+        assert_eq!(serde_json::to_string(&ern.parts)?, "\"eu/shard\"");
+        assert_eq!(serde_json::from_str::<Parts>("\"eu/shard\"")?, ern.parts);
+        assert_eq!(serde_json::from_str::<Parts>("\"\"")?, Parts::default());
+        assert_eq!(serde_json::from_str::<Part>("\"eu\"")?.as_str(), "eu");
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_components_are_rejected() {
+        assert!(serde_json::from_str::<Domain>("\"\"").is_err());
+        assert!(serde_json::from_str::<Account>("\"a:b\"").is_err());
+        assert!(serde_json::from_str::<EntityRoot>("\"root\"").is_err());
+        assert!(serde_json::from_str::<Part>("\"a/b\"").is_err());
+        assert!(serde_json::from_str::<Parts>("\"a//b\"").is_err());
+    }
+}
@@ -0,0 +1,274 @@
+use std::ops::Index;
+use std::str::FromStr;
+
+use crate::errors::ErnError;
+use crate::model::Ern;
+
+/// The substrings of an ERN (Entity Resource Name) captured by `{name}` placeholders in a shape;
+/// returned by [`Ern::expect_shape`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ErnCaptures(Vec<(String, String)>);
+
+impl ErnCaptures {
+    /// Returns the value captured by `{name}`, if the shape had that placeholder.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(capture, _)| capture == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Converts the value captured by `{name}`, e.g. `captures.parse::<u64>("id")`.
+    ///
+    /// Fails if the shape had no such placeholder or the value does not convert.
+    pub fn parse<T>(&self, name: &str) -> Result<T, ErnError>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let value = self.get(name).ok_or_else(|| {
+            ErnError::ParseFailure("capture", format!("the shape has no `{{{name}}}`"))
+        })?;
+        value.parse().map_err(|error| {
+            ErnError::ParseFailure("capture", format!("`{name}` = `{value}`: {error}"))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the placeholder names and captured values in shape order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl Index<&str> for ErnCaptures {
+    type Output = str;
+
+    /// Returns the value captured by `{name}`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shape had no such placeholder; use [`ErnCaptures::get`] to check first.
+    fn index(&self, name: &str) -> &str {
+        self.get(name)
+            .unwrap_or_else(|| panic!("the shape has no `{{{name}}}` placeholder"))
+    }
+}
+
+enum Slot<'a> {
+    Capture(&'a str),
+    Literal(&'a str),
+}
+
+fn shape_error(shape: &str, message: impl std::fmt::Display) -> ErnError {
+    ErnError::ParseFailure("shape", format!("`{shape}`: {message}"))
+}
+
+fn parse_slot<'a>(shape: &str, slot: &'a str) -> Result<Slot<'a>, ErnError> {
+    match slot
+        .strip_prefix('{')
+        .and_then(|rest| rest.strip_suffix('}'))
+    {
+        Some(name)
+            if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+        {
+            Ok(Slot::Capture(name))
+        }
+        Some(_) => Err(shape_error(
+            shape,
+            format!("`{slot}` is not a valid placeholder"),
+        )),
+        None if slot.is_empty() => Err(shape_error(shape, "components and parts cannot be empty")),
+        None if slot.contains(['{', '}']) => Err(shape_error(
+            shape,
+            format!("`{slot}` mixes a placeholder with text"),
+        )),
+        None => Ok(Slot::Literal(slot)),
+    }
+}
+
+impl Ern {
+    /// Checks that this ERN (Entity Resource Name) has the given shape and returns the values of
+    /// its `{name}` placeholders, e.g.
+    /// `ern.expect_shape("{domain}:{category}:{account}:{root}/orders/{id}")?.parse::<u64>("id")`.
+    ///
+    /// The shape is `domain:category:account:root/part…`, optionally prefixed with `ern:`, where
+    /// each component and part is either a `{name}` placeholder or literal text that must match
+    /// exactly; a literal root also matches by the name the root was created from. The number of
+    /// parts must match, and revisions are ignored.
+    ///
+    /// Fails with [`ErnError::ShapeMismatch`] naming the first difference, or with
+    /// [`ErnError::ParseFailure`] if the shape itself is malformed.
+    pub fn expect_shape(&self, shape: &str) -> Result<ErnCaptures, ErnError> {
+        let body = shape.strip_prefix("ern:").unwrap_or(shape);
+        let fields: Vec<&str> = body.splitn(4, ':').collect();
+        let [domain, category, account, rest] = fields[..] else {
+            return Err(shape_error(
+                shape,
+                "expected `domain:category:account:root`",
+            ));
+        };
+        let (root, path) = match rest.split_once('/') {
+            Some((root, path)) => (root, path.split('/').collect()),
+            None => (rest, Vec::new()),
+        };
+
+        let mut slots = vec![
+            (
+                "domain".to_string(),
+                parse_slot(shape, domain)?,
+                self.domain.as_str(),
+            ),
+            (
+                "category".to_string(),
+                parse_slot(shape, category)?,
+                self.category.as_str(),
+            ),
+            (
+                "account".to_string(),
+                parse_slot(shape, account)?,
+                self.account.as_str(),
+            ),
+            (
+                "root".to_string(),
+                parse_slot(shape, root)?,
+                self.root.as_str(),
+            ),
+        ];
+        for (index, part) in path.iter().enumerate() {
+            let slot = parse_slot(shape, part)?;
+            let actual = self.parts.0.get(index).map_or("", |part| part.as_str());
+            slots.push((format!("part {}", index + 1), slot, actual));
+        }
+
+        let mut captures = Vec::new();
+        for (_, slot, _) in &slots {
+            if let Slot::Capture(name) = slot {
+                if captures.iter().any(|(capture, _)| capture == name) {
+                    return Err(shape_error(
+                        shape,
+                        format!("`{{{name}}}` appears more than once"),
+                    ));
+                }
+                captures.push((name.to_string(), String::new()));
+            }
+        }
+        let mismatch = |reason: String| ErnError::ShapeMismatch(shape.to_string(), reason);
+        // checked once the components agree, so the error names the most significant difference
+        let check_count = || {
+            if path.len() == self.parts.len() {
+                return Ok(());
+            }
+            let noun = if path.len() == 1 { "part" } else { "parts" };
+            Err(mismatch(format!(
+                "expected {} {noun} after the root, got {} in `{self}`",
+                path.len(),
+                self.parts.len()
+            )))
+        };
+        let mut values = captures.iter_mut();
+        for (index, (component, slot, actual)) in slots.into_iter().enumerate() {
+            if index == 4 {
+                check_count()?;
+            }
+            match slot {
+                Slot::Capture(_) => {
+                    values.next().expect("one value per capture").1 = actual.to_string()
+                }
+                Slot::Literal(literal) if literal == actual => {}
+                Slot::Literal(literal)
+                    if component == "root" && literal == self.root.base_name() => {}
+                Slot::Literal(literal) => {
+                    return Err(mismatch(format!(
+                        "expected {component} `{literal}`, got `{actual}`"
+                    )));
+                }
+            }
+        }
+        check_count()?;
+        Ok(ErnCaptures(captures))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captures_components_and_parts() -> anyhow::Result<()> {
+        let ern: Ern =
+            "ern:acton:billing:tenant9:orders_01h455vb4pex5vsknk084sn02q/orders/42".parse()?;
+        let captures = ern.expect_shape("{domain}:{category}:{account}:{root}/orders/{id}")?;
+        assert_eq!(captures.get("domain"), Some("acton"));
+        assert_eq!(&captures["root"], "orders_01h455vb4pex5vsknk084sn02q");
+        assert_eq!(captures.parse::<u64>("id")?, 42);
+        assert_eq!(captures.len(), 5);
+        assert_eq!(
+            captures.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            ["domain", "category", "account", "root", "id"]
+        );
+
+        let captures = ern
+            .with_revision(3)
+            .expect_shape("ern:acton:billing:{tenant}:orders/orders/{id}")?;
+        assert_eq!(captures.get("tenant"), Some("tenant9"));
+        assert!(captures.parse::<u64>("missing").is_err());
+        assert!(matches!(
+            captures.parse::<bool>("id"),
+            Err(ErnError::ParseFailure("capture", _))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_mismatches_name_the_difference() -> anyhow::Result<()> {
+        let ern: Ern = "ern:acton:billing:tenant9:orders/invoices/42".parse()?;
+        let shape = "{domain}:{category}:{account}:{root}/orders/{id}";
+        assert_eq!(
+            ern.expect_shape(shape),
+            Err(ErnError::ShapeMismatch(
+                shape.to_string(),
+                "expected part 1 `orders`, got `invoices`".to_string()
+            ))
+        );
+        assert_eq!(
+            ern.expect_shape("acton:orders:{account}:{root}/{kind}/{id}").unwrap_err().to_string(),
+            "Validation Error - ERN does not have shape `acton:orders:{account}:{root}/{kind}/{id}`: expected category `orders`, got `billing`"
+        );
+        assert!(matches!(
+            ern.expect_shape("{domain}:{category}:{account}:{root}/{id}"),
+            Err(ErnError::ShapeMismatch(_, reason)) if reason.starts_with("expected 1 part after the root, got 2")
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_malformed_shapes_are_rejected() -> anyhow::Result<()> {
+        let ern: Ern = "ern:acton:billing:tenant9:orders/invoices".parse()?;
+        for shape in [
+            "{domain}:{category}:{account}",
+            "{domain}:{category}:{account}:{root}/",
+            "{domain}:{category}:{account}:{root}/{}",
+            "{domain}:{category}:{account}:{root}/inv{x}",
+            "{a}:{category}:{a}:{root}/invoices",
+        ] {
+            assert!(
+                matches!(
+                    ern.expect_shape(shape),
+                    Err(ErnError::ParseFailure("shape", _))
+                ),
+                "{shape}"
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use crate::distance::levenshtein;
+use crate::Ern;
+
+/// A component-by-component comparison of two ERNs (Entity Resource Names), produced by [`Ern::similarity`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Similarity {
+    /// Whether the domains are equal.
+    pub domain: bool,
+    /// Whether the categories are equal.
+    pub category: bool,
+    /// Whether the accounts are equal.
+    pub account: bool,
+    /// Whether the roots are the same entity (identical ids).
+    pub root: bool,
+    /// Whether the roots were created from the same base name, even if their ids differ.
+    pub root_name: bool,
+    /// The number of part insertions, deletions, or substitutions separating the two paths.
+    pub parts_distance: usize,
+    /// The larger of the two part counts, for normalizing `parts_distance`.
+    pub max_depth: usize,
+}
+
+impl Similarity {
+    /// Returns a score from 0.0 (nothing in common) to 1.0 (same resource name).
+    ///
+    /// Weights favor the account, root name, and path, which are what distinguish resources in
+    /// practice; the root id is ignored so re-registrations of the same name score as duplicates.
+    pub fn score(&self) -> f64 {
+        let flag = |matches: bool, weight: f64| if matches { weight } else { 0.0 };
+        let parts = if self.max_depth == 0 {
+            1.0
+        } else {
+            1.0 - self.parts_distance as f64 / self.max_depth as f64
+        };
+        flag(self.domain, 0.15)
+            + flag(self.category, 0.15)
+            + flag(self.account, 0.2)
+            + flag(self.root_name, 0.2)
+            + parts * 0.3
+    }
+
+    /// Returns true if the score reaches `threshold` but the ERNs are not the same resource.
+    pub fn is_possible_duplicate(&self, threshold: f64) -> bool {
+        !(self.root && self.parts_distance == 0) && self.score() >= threshold
+    }
+}
+
+impl Ern {
+    /// Compares this ERN (Entity Resource Name) with `other` component by component.
+    pub fn similarity(&self, other: &Ern) -> Similarity {
+        let left: Vec<&str> = (&self.parts)
+            .into_iter()
+            .map(|part| part.as_str())
+            .collect();
+        let right: Vec<&str> = (&other.parts)
+            .into_iter()
+            .map(|part| part.as_str())
+            .collect();
+        Similarity {
+            domain: self.domain == other.domain,
+            category: self.category == other.category,
+            account: self.account == other.account,
+            root: self.root == other.root,
+            root_name: self.root.base_name() == other.root.base_name(),
+            parts_distance: levenshtein(&left, &right),
+            max_depth: left.len().max(right.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_ern() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["a", "b"])?;
+        let similarity = ern.similarity(&ern);
+        assert_eq!(similarity.parts_distance, 0);
+        assert!((similarity.score() - 1.0).abs() < f64::EPSILON);
+        assert!(!similarity.is_possible_duplicate(0.9));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reregistered_name_is_possible_duplicate() -> anyhow::Result<()> {
+        let first = Ern::with_root("orders")?.with_parts(["eu", "shard1"])?;
+        let second = Ern::with_root("orders")?.with_parts(["eu", "shard-1"])?;
+        let similarity = first.similarity(&second);
+        assert!(!similarity.root);
+        assert!(similarity.root_name);
+        assert_eq!(similarity.parts_distance, 1);
+        assert!(similarity.is_possible_duplicate(0.8));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unrelated_erns_score_low() -> anyhow::Result<()> {
+        let first = Ern::with_root("orders")?.with_parts(["a"])?;
+        let second = Ern::with_domain("billing")?
+            .with_new_root("invoices")?
+            .with_parts(["x", "y"])?;
+        assert!(first.similarity(&second).score() < 0.5);
+        Ok(())
+    }
+}
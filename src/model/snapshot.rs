@@ -0,0 +1,105 @@
+use crate::model::Ern;
+
+/// Stands in for the generated id of a root in snapshot output.
+pub const SNAPSHOT_ID_PLACEHOLDER: &str = "[id]";
+
+/// The length of the base32 suffix of a generated root id.
+const ID_SUFFIX_LENGTH: usize = 26;
+
+impl Ern {
+    /// Returns the displayed ERN (Entity Resource Name) with the generated root id replaced by
+    /// [`SNAPSHOT_ID_PLACEHOLDER`], e.g. `ern:acton:reactive:component:orders_[id]/a`.
+    ///
+    /// The id also encodes the creation time, so this is the form to use in snapshot tests; it
+    /// is not parseable and should not be stored.
+    pub fn to_snapshot_string(&self) -> String {
+        let mut snapshot = format!(
+            "ern:{}:{}:{}:{}_{}",
+            self.domain,
+            self.category,
+            self.account,
+            self.root.base_name(),
+            SNAPSHOT_ID_PLACEHOLDER
+        );
+        if let Some(revision) = self.revision {
+            snapshot.push_str(&format!("#{revision}"));
+        }
+        for part in &self.parts {
+            snapshot.push('/');
+            snapshot.push_str(part.as_str());
+        }
+        snapshot
+    }
+
+    /// Replaces every generated root id embedded in `text` with [`SNAPSHOT_ID_PLACEHOLDER`].
+    ///
+    /// Useful for snapshotting logs, JSON, or `Debug` output of structures that contain ERNs. An id
+    /// is recognized as `_` followed by 26 lowercase base32 characters that are not part of a longer word.
+    pub fn redact_snapshot_ids(text: &str) -> String {
+        let bytes = text.as_bytes();
+        let mut redacted = String::with_capacity(text.len());
+        let mut copied = 0;
+        let mut index = 0;
+        while index < bytes.len() {
+            let end = index + 1 + ID_SUFFIX_LENGTH;
+            if bytes[index] == b'_'
+                && end <= bytes.len()
+                && bytes[index + 1..end].iter().all(|&b| is_id_char(b))
+                && bytes.get(end).is_none_or(|&b| !b.is_ascii_alphanumeric())
+            {
+                redacted.push_str(&text[copied..=index]);
+                redacted.push_str(SNAPSHOT_ID_PLACEHOLDER);
+                copied = end;
+                index = end;
+            } else {
+                index += 1;
+            }
+        }
+        redacted.push_str(&text[copied..]);
+        redacted
+    }
+}
+
+/// Crockford base32 as used by TypeIDs: digits and lowercase letters except `i`, `l`, `o`, and `u`.
+fn is_id_char(b: u8) -> bool {
+    b.is_ascii_digit() || (b.is_ascii_lowercase() && !matches!(b, b'i' | b'l' | b'o' | b'u'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_string_is_stable() -> anyhow::Result<()> {
+        let first = Ern::with_root("orders")?.with_parts(["eu", "shard-2"])?;
+        let second = Ern::with_root("orders")?.with_parts(["eu", "shard-2"])?;
+        assert_ne!(first, second);
+        assert_eq!(
+            first.to_snapshot_string(),
+            "ern:acton:reactive:component:orders_[id]/eu/shard-2"
+        );
+        assert_eq!(first.to_snapshot_string(), second.to_snapshot_string());
+        assert_eq!(
+            first.with_revision(2).to_snapshot_string(),
+            "ern:acton:reactive:component:orders_[id]#2/eu/shard-2"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_redact_embedded_ids() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.add_part("eu")?;
+        let text = format!("{{\"target\":\"{ern}\",\"note\":\"snake_case_words stay\"}}");
+        assert_eq!(
+            Ern::redact_snapshot_ids(&text),
+            "{\"target\":\"ern:acton:reactive:component:orders_[id]/eu\",\"note\":\"snake_case_words stay\"}"
+        );
+        assert_eq!(
+            Ern::redact_snapshot_ids(&format!("{ern:?}"))
+                .matches("[id]")
+                .count(),
+            1
+        );
+        Ok(())
+    }
+}
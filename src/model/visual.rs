@@ -0,0 +1,108 @@
+use std::fmt;
+
+use crate::hash::stable_hash64;
+use crate::Ern;
+
+/// A color derived deterministically from an ERN (Entity Resource Name).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ErnColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl ErnColor {
+    /// Returns the color as a CSS hex string, e.g. `#3fa7c2`.
+    pub fn to_hex(&self) -> String {
+        self.to_string()
+    }
+
+    /// Converts a hue in degrees with fixed saturation and lightness into RGB.
+    fn from_hue(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let h = hue / 60.0;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = lightness - chroma / 2.0;
+        let channel = |value: f64| ((value + m) * 255.0).round() as u8;
+        Self {
+            r: channel(r),
+            g: channel(g),
+            b: channel(b),
+        }
+    }
+}
+
+impl fmt::Display for ErnColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl Ern {
+    /// Derives a stable badge color for this ERN (Entity Resource Name) from its [stable hash](Ern::stable_hash).
+    ///
+    /// Only the hue varies; saturation and lightness are fixed so every color stays legible on
+    /// both light and dark backgrounds.
+    pub fn to_color(&self) -> ErnColor {
+        let hue = (self.stable_hash() % 360) as f64;
+        ErnColor::from_hue(hue, 0.65, 0.5)
+    }
+
+    /// Returns 16 stable bytes suitable for seeding an identicon or avatar generator.
+    pub fn to_identicon_seed(&self) -> [u8; 16] {
+        let canonical = self.to_string();
+        let high = stable_hash64(canonical.as_bytes());
+        // Hash again with the first half mixed in so the two halves are independent
+        let mut salted = high.to_be_bytes().to_vec();
+        salted.extend_from_slice(canonical.as_bytes());
+        let low = stable_hash64(&salted);
+
+        let mut seed = [0u8; 16];
+        seed[..8].copy_from_slice(&high.to_be_bytes());
+        seed[8..].copy_from_slice(&low.to_be_bytes());
+        seed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_is_stable() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?;
+        assert_eq!(ern.to_color(), ern.clone().to_color());
+        assert_eq!(ern.to_color().to_hex().len(), 7);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hue_conversion() {
+        assert_eq!(
+            ErnColor::from_hue(0.0, 1.0, 0.5),
+            ErnColor { r: 255, g: 0, b: 0 }
+        );
+        assert_eq!(
+            ErnColor::from_hue(120.0, 1.0, 0.5),
+            ErnColor { r: 0, g: 255, b: 0 }
+        );
+        assert_eq!(ErnColor::from_hue(240.0, 1.0, 0.5).to_hex(), "#0000ff");
+    }
+
+    #[test]
+    fn test_identicon_seed_differs_per_ern() -> anyhow::Result<()> {
+        let first = Ern::with_root("orders")?;
+        let second = first.add_part("item")?;
+        assert_eq!(first.to_identicon_seed(), first.to_identicon_seed());
+        assert_ne!(first.to_identicon_seed(), second.to_identicon_seed());
+        Ok(())
+    }
+}
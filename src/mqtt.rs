@@ -0,0 +1,290 @@
+use crate::errors::ErnError;
+use crate::model::{EntityRoot, Ern};
+use crate::pattern::{Component, ErnPattern, FilterLevel, Segment};
+use crate::wire::ErnWire;
+
+/// The first level of every topic produced by [`Ern::to_mqtt_topic`].
+pub const MQTT_TOPIC_PREFIX: &str = "ern";
+
+/// The maximum encoded length of an MQTT topic name.
+const MAX_MQTT_TOPIC_LENGTH: usize = 65_535;
+
+/// Rejects a topic level, or a literal filter level, that is empty or contains a separator,
+/// wildcard, or NUL; `format` names the topic or filter in errors.
+fn check_level(format: &'static str, level: &str) -> Result<(), ErnError> {
+    if level.is_empty() {
+        return Err(ErnError::ParseFailure(
+            format,
+            "empty topic level".to_string(),
+        ));
+    }
+    match level.chars().find(|c| matches!(c, '/' | '+' | '#' | '\0')) {
+        Some(invalid) => Err(ErnError::InvalidCharacter(format, invalid)),
+        None => Ok(()),
+    }
+}
+
+impl Ern {
+    /// Returns the MQTT topic name `ern/<domain>/<category>/<account>/<root>/<parts…>`.
+    ///
+    /// Components containing the level separator, a wildcard (`+`, `#`), or NUL are rejected with
+    /// [`ErnError::InvalidCharacter`].
+    pub fn to_mqtt_topic(&self) -> Result<String, ErnError> {
+        let wire = ErnWire::from(self);
+        let mut topic = MQTT_TOPIC_PREFIX.to_string();
+        for level in wire.segments() {
+            check_level("mqtt topic", level)?;
+            topic.push('/');
+            topic.push_str(level);
+        }
+        if topic.len() > MAX_MQTT_TOPIC_LENGTH {
+            return Err(ErnError::LimitExceeded(
+                "mqtt topic",
+                MAX_MQTT_TOPIC_LENGTH,
+                topic.len(),
+            ));
+        }
+        Ok(topic)
+    }
+
+    /// Parses a topic name produced by [`Ern::to_mqtt_topic`], keeping the root id.
+    pub fn from_mqtt_topic(topic: &str) -> Result<Self, ErnError> {
+        if topic.len() > MAX_MQTT_TOPIC_LENGTH {
+            return Err(ErnError::LimitExceeded(
+                "mqtt topic",
+                MAX_MQTT_TOPIC_LENGTH,
+                topic.len(),
+            ));
+        }
+        let levels = topic
+            .strip_prefix(MQTT_TOPIC_PREFIX)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .ok_or_else(|| {
+                ErnError::ParseFailure(
+                    "mqtt topic",
+                    format!("expected the `{}/` prefix", MQTT_TOPIC_PREFIX),
+                )
+            })?
+            .split('/')
+            .map(|level| check_level("mqtt topic", level).map(|_| level.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ern::try_from(ErnWire::from_segments("mqtt topic", levels)?)
+    }
+}
+
+impl ErnPattern {
+    /// Returns the MQTT topic filter matching the topics [`Ern::to_mqtt_topic`] produces for the
+    /// ERNs (Entity Resource Names) this pattern matches.
+    ///
+    /// `*` becomes the single-level wildcard `+`, and a trailing `**` becomes `#`, which also
+    /// matches the topic without those levels. MQTT compares levels exactly, so a literal root
+    /// must be a full root id rather than a root name, and `**` anywhere else is rejected with
+    /// [`ErnError::ParseFailure`]. Literal levels follow the same rules as topic names.
+    pub fn to_mqtt_filter(&self) -> Result<String, ErnError> {
+        let mut filter = MQTT_TOPIC_PREFIX.to_string();
+        for level in self.filter_levels("mqtt topic filter")? {
+            filter.push('/');
+            match level {
+                FilterLevel::Literal(literal) => {
+                    check_level("mqtt topic filter", literal)?;
+                    filter.push_str(literal);
+                }
+                FilterLevel::One => filter.push('+'),
+                FilterLevel::Rest => filter.push('#'),
+            }
+        }
+        if filter.len() > MAX_MQTT_TOPIC_LENGTH {
+            return Err(ErnError::LimitExceeded(
+                "mqtt topic filter",
+                MAX_MQTT_TOPIC_LENGTH,
+                filter.len(),
+            ));
+        }
+        Ok(filter)
+    }
+
+    /// Parses a topic filter over the topics [`Ern::to_mqtt_topic`] produces, such as
+    /// `ern/acton/+/+/+/#`, into the equivalent pattern.
+    ///
+    /// `+` becomes `*`, and `#`, which must be the last level, becomes `*` for any remaining
+    /// components followed by `**`. As with [`ErnPattern::to_mqtt_filter`], a literal root must be
+    /// a full root id.
+    pub fn from_mqtt_filter(filter: &str) -> Result<Self, ErnError> {
+        if filter.len() > MAX_MQTT_TOPIC_LENGTH {
+            return Err(ErnError::LimitExceeded(
+                "mqtt topic filter",
+                MAX_MQTT_TOPIC_LENGTH,
+                filter.len(),
+            ));
+        }
+        let mut levels = filter
+            .strip_prefix(MQTT_TOPIC_PREFIX)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .ok_or_else(|| {
+                ErnError::ParseFailure(
+                    "mqtt topic filter",
+                    format!("expected the `{}/` prefix", MQTT_TOPIC_PREFIX),
+                )
+            })?
+            .split('/');
+
+        let mut components = Vec::with_capacity(4);
+        let mut rest = false;
+        for name in ["domain", "category", "account", "root"] {
+            if rest {
+                components.push(Component::Any);
+                continue;
+            }
+            let component = match levels.next() {
+                Some("+") => Component::Any,
+                Some("#") => {
+                    rest = true;
+                    Component::Any
+                }
+                Some(level) if name == "root" && EntityRoot::parse(level).is_err() => {
+                    return Err(ErnError::ParseFailure(
+                        "mqtt topic filter",
+                        format!("a root must be `+` or a full root id, not the name `{level}`"),
+                    ));
+                }
+                Some(level) => Component::Literal(filter_literal(level)?),
+                None => {
+                    return Err(ErnError::ParseFailure(
+                        "mqtt topic filter",
+                        format!("missing {}", name),
+                    ))
+                }
+            };
+            components.push(component);
+        }
+
+        let mut parts = Vec::new();
+        if rest {
+            parts.push(Segment::Many);
+        }
+        while !rest {
+            let Some(level) = levels.next() else {
+                break;
+            };
+            parts.push(match level {
+                "+" => Segment::One,
+                "#" => {
+                    rest = true;
+                    Segment::Many
+                }
+                level => Segment::Literal(filter_literal(level)?),
+            });
+        }
+        if levels.next().is_some() {
+            return Err(ErnError::ParseFailure(
+                "mqtt topic filter",
+                "`#` must be the last level".to_string(),
+            ));
+        }
+
+        let [domain, category, account, root] =
+            <[Component; 4]>::try_from(components).expect("four components");
+        Ok(ErnPattern {
+            domain,
+            category,
+            account,
+            root,
+            parts,
+        })
+    }
+}
+
+/// Returns a literal filter level, which must also be a valid literal in an [`ErnPattern`].
+fn filter_literal(level: &str) -> Result<String, ErnError> {
+    check_level("mqtt topic filter", level)?;
+    match level.chars().find(|c| matches!(c, ':' | '*')) {
+        Some(invalid) => Err(ErnError::InvalidCharacter("mqtt topic filter", invalid)),
+        None => Ok(level.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topic_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("sensors")?.with_parts(["building 7", "temp.c"])?;
+        let topic = ern.to_mqtt_topic()?;
+        assert!(topic.starts_with("ern/acton/reactive/component/sensors_"));
+        assert!(topic.ends_with("/building 7/temp.c"));
+        assert_eq!(Ern::from_mqtt_topic(&topic)?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_wildcards_and_malformed_topics() -> anyhow::Result<()> {
+        let ern = Ern::with_root("sensors")?.add_part("a+b")?;
+        assert_eq!(
+            ern.to_mqtt_topic(),
+            Err(ErnError::InvalidCharacter("mqtt topic", '+'))
+        );
+        assert!(Ern::from_mqtt_topic("ern/acton/reactive/component/#").is_err());
+        assert!(Ern::from_mqtt_topic("ern/acton/reactive").is_err());
+        assert!(Ern::from_mqtt_topic("$SYS/acton/reactive/component/root").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_translation() -> anyhow::Result<()> {
+        let root = "orders_01h455vb4pex5vsknk084sn02q";
+        let pattern = ErnPattern::parse(&format!("ern:acton:*:*:{root}/*/shard/**"))?;
+        let filter = pattern.to_mqtt_filter()?;
+        assert_eq!(filter, format!("ern/acton/+/+/{root}/+/shard/#"));
+        assert_eq!(ErnPattern::from_mqtt_filter(&filter)?, pattern);
+
+        assert_eq!(
+            ErnPattern::parse("ern:*:*:*:*")?.to_mqtt_filter()?,
+            "ern/+/+/+/+"
+        );
+        assert_eq!(
+            ErnPattern::from_mqtt_filter("ern/acton/#")?,
+            ErnPattern::parse("ern:acton:*:*:*/**")?
+        );
+        assert_eq!(
+            ErnPattern::from_mqtt_filter("ern/acton/+/+/+/#")?,
+            ErnPattern::parse("ern:acton:*:*:*/**")?
+        );
+
+        // every topic the filter matches is an ERN the pattern matches
+        let ern = Ern::with_root("sensors")?.with_parts(["eu", "shard"])?;
+        let pattern = ErnPattern::from_mqtt_filter("ern/acton/+/+/+/+/shard/#")?;
+        assert!(pattern.matches(&ern));
+        assert!(pattern.matches(&Ern::from_mqtt_topic(&ern.to_mqtt_topic()?)?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_rejects_untranslatable_input() -> anyhow::Result<()> {
+        assert!(ErnPattern::parse("ern:*:*:*:*/**/leaf")?
+            .to_mqtt_filter()
+            .is_err());
+        assert!(ErnPattern::parse("ern:*:*:*:orders/*")?
+            .to_mqtt_filter()
+            .is_err());
+        assert_eq!(
+            ErnPattern::parse("ern:*:*:*:*/a+b")?.to_mqtt_filter(),
+            Err(ErnError::InvalidCharacter("mqtt topic filter", '+'))
+        );
+        for filter in [
+            "ern/acton/#/+",
+            "ern/+/+/+/+/#/leaf",
+            "ern/+/+/+/+/a+",
+            "ern/+/+/+/+/a#",
+            "ern/+/+/+/+/a*",
+            "ern/+/a:b/+/+",
+            "ern/+/+/+/orders",
+            "ern/+/+/+",
+            "ern/+/+/+/+/",
+            "$SYS/+/+/+/+",
+        ] {
+            assert!(ErnPattern::from_mqtt_filter(filter).is_err(), "{filter}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use rmp::Marker;
+
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+
+/// The MessagePack extension type code used by [`MsgpackForm::Ext`].
+pub const ERN_MSGPACK_EXT_TYPE: i8 = 0x45;
+
+/// How an ERN (Entity Resource Name) is written as MessagePack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MsgpackForm {
+    /// A plain `str`, readable by any MessagePack consumer.
+    #[default]
+    Str,
+    /// An extension value of type [`ERN_MSGPACK_EXT_TYPE`] holding the canonical string, so
+    /// consumers can tell ERNs apart from other strings without inspecting them.
+    Ext,
+}
+
+impl Ern {
+    /// Encodes this ERN (Entity Resource Name) as a single MessagePack value.
+    pub fn to_msgpack(&self, form: MsgpackForm) -> Vec<u8> {
+        let value = self.to_string();
+        let mut bytes = Vec::with_capacity(value.len() + 6);
+        // Writing to a Vec cannot fail, and ERN strings are far below the 4 GiB length limit
+        match form {
+            MsgpackForm::Str => {
+                rmp::encode::write_str(&mut bytes, &value).expect("writing to a Vec cannot fail")
+            }
+            MsgpackForm::Ext => {
+                rmp::encode::write_ext_meta(&mut bytes, value.len() as u32, ERN_MSGPACK_EXT_TYPE)
+                    .expect("writing to a Vec cannot fail");
+                bytes.extend_from_slice(value.as_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Decodes a MessagePack value in either [`MsgpackForm`], returning the ERN (Entity Resource Name) and
+    /// the number of bytes consumed so values can be read from a larger buffer.
+    pub fn from_msgpack(bytes: &[u8]) -> Result<(Self, usize), ErnError> {
+        let mut reader = bytes;
+        let marker = bytes
+            .first()
+            .map(|byte| Marker::from_u8(*byte))
+            .ok_or_else(|| ErnError::parse_failure("msgpack", "empty input"))?;
+        let length = match marker {
+            Marker::FixStr(_) | Marker::Str8 | Marker::Str16 | Marker::Str32 => {
+                rmp::decode::read_str_len(&mut reader)
+                    .map_err(|e| ErnError::parse_failure("msgpack", format!("{:?}", e)))?
+            }
+            Marker::FixExt1
+            | Marker::FixExt2
+            | Marker::FixExt4
+            | Marker::FixExt8
+            | Marker::FixExt16
+            | Marker::Ext8
+            | Marker::Ext16
+            | Marker::Ext32 => {
+                let meta = rmp::decode::read_ext_meta(&mut reader)
+                    .map_err(|e| ErnError::parse_failure("msgpack", format!("{:?}", e)))?;
+                if meta.typeid != ERN_MSGPACK_EXT_TYPE {
+                    return Err(ErnError::parse_failure(
+                        "msgpack",
+                        format!("unexpected extension type {}", meta.typeid),
+                    ));
+                }
+                meta.size
+            }
+            other => {
+                return Err(ErnError::parse_failure(
+                    "msgpack",
+                    format!("expected a str or ext value, found {:?}", other),
+                ))
+            }
+        } as usize;
+        let header = bytes.len() - reader.len();
+        let payload = reader
+            .get(..length)
+            .ok_or_else(|| ErnError::parse_failure("msgpack", "truncated value"))?;
+        let value = std::str::from_utf8(payload)
+            .map_err(|error| ErnError::parse_failure("msgpack", error))?;
+        Ok((ErnParser::new(value.to_string()).parse()?, header + length))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_both_forms_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.with_parts(["a", "b"])?;
+        for form in [MsgpackForm::Str, MsgpackForm::Ext] {
+            let bytes = ern.to_msgpack(form);
+            assert_eq!(Ern::from_msgpack(&bytes)?, (ern.clone(), bytes.len()));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_ext_form_carries_type_code() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?;
+        let bytes = ern.to_msgpack(MsgpackForm::Ext);
+        let meta = rmp::decode::read_ext_meta(&mut bytes.as_slice())
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        assert_eq!(meta.typeid, ERN_MSGPACK_EXT_TYPE);
+        assert_eq!(meta.size as usize, ern.to_string().len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_reads_from_larger_buffer() -> anyhow::Result<()> {
+        let first = Ern::with_root("first")?;
+        let second = Ern::with_root("second")?;
+        let mut bytes = first.to_msgpack(MsgpackForm::Ext);
+        bytes.extend(second.to_msgpack(MsgpackForm::Str));
+        let (decoded, used) = Ern::from_msgpack(&bytes)?;
+        assert_eq!(decoded, first);
+        assert_eq!(Ern::from_msgpack(&bytes[used..])?.0, second);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_other_values() {
+        assert!(matches!(
+            Ern::from_msgpack(&[0xc0]),
+            Err(ErnError::ParseFailure("msgpack", _))
+        ));
+        assert!(matches!(
+            Ern::from_msgpack(&[0xd4, 0x01, 0x00]),
+            Err(ErnError::ParseFailure("msgpack", _))
+        ));
+        assert!(matches!(
+            Ern::from_msgpack(&[0xa5, b'e']),
+            Err(ErnError::ParseFailure("msgpack", _))
+        ));
+    }
+}
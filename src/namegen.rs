@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+
+use rand::rngs::{StdRng, ThreadRng};
+use rand::{Rng, SeedableRng};
+
+use crate::errors::ErnError;
+use crate::model::Part;
+
+const ADJECTIVES: &[&str] = &[
+    "amber", "bold", "brave", "bright", "calm", "clever", "cosmic", "crisp", "daring", "eager",
+    "fancy", "gentle", "glad", "golden", "happy", "humble", "jolly", "keen", "lively", "lucky",
+    "mellow", "merry", "nimble", "noble", "proud", "quick", "quiet", "rapid", "shiny", "silver",
+    "swift", "witty",
+];
+
+const NOUNS: &[&str] = &[
+    "badger", "beaver", "bison", "comet", "crane", "falcon", "fox", "gecko", "heron", "ibis",
+    "koala", "lark", "lemur", "lynx", "marten", "meadow", "moose", "orca", "otter", "owl", "panda",
+    "puffin", "raven", "river", "robin", "salmon", "seal", "sparrow", "tiger", "walrus", "willow",
+    "wren",
+];
+
+/// The smallest number appended to generated names.
+const MIN_NUMBER: u32 = 10;
+/// The largest number appended to generated names.
+const MAX_NUMBER: u32 = 99;
+
+/// Generates human-friendly parts such as `brave-otter-47` for ERNs (Entity Resource Names) shown to users.
+///
+/// Names come from fixed word lists, so there are about 92,000 of them; use them for display-facing
+/// children such as sessions or sandboxes, not as globally unique ids.
+#[derive(Debug, Clone)]
+pub struct NameGenerator<R = ThreadRng> {
+    rng: R,
+    max_attempts: usize,
+}
+
+impl NameGenerator<ThreadRng> {
+    /// Creates a generator backed by the thread-local random number generator.
+    pub fn new() -> Self {
+        Self::from_rng(rand::thread_rng())
+    }
+}
+
+impl Default for NameGenerator<ThreadRng> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NameGenerator<StdRng> {
+    /// Creates a generator that produces the same sequence of names for the same seed.
+    pub fn seeded(seed: u64) -> Self {
+        Self::from_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<R: Rng> NameGenerator<R> {
+    /// Creates a generator drawing from `rng`.
+    pub fn from_rng(rng: R) -> Self {
+        Self {
+            rng,
+            max_attempts: 32,
+        }
+    }
+
+    /// Sets how many candidates [`generate_unique`](Self::generate_unique) tries before giving up.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Returns a random name, which may repeat earlier ones.
+    pub fn generate(&mut self) -> Part {
+        let adjective = ADJECTIVES[self.rng.gen_range(0..ADJECTIVES.len())];
+        let noun = NOUNS[self.rng.gen_range(0..NOUNS.len())];
+        let number = self.rng.gen_range(MIN_NUMBER..=MAX_NUMBER);
+        Part(format!("{adjective}-{noun}-{number}").into())
+    }
+
+    /// Returns a random name that is not in `taken`.
+    ///
+    /// Fails with [`ErnError::NamesExhausted`] if every attempt collides, which only becomes
+    /// likely once `taken` holds a large share of the possible names.
+    pub fn generate_unique(&mut self, taken: &HashSet<Part>) -> Result<Part, ErnError> {
+        for _ in 0..self.max_attempts {
+            let name = self.generate();
+            if !taken.contains(&name) {
+                return Ok(name);
+            }
+        }
+        Err(ErnError::NamesExhausted(self.max_attempts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_are_valid_parts() -> anyhow::Result<()> {
+        let mut names = NameGenerator::new();
+        for _ in 0..100 {
+            let name = names.generate();
+            assert_eq!(&Part::new(name.as_str().to_string())?, &name);
+            assert_eq!(name.as_str().split('-').count(), 3);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_seeded_generators_repeat() {
+        let mut first = NameGenerator::seeded(7);
+        let mut second = NameGenerator::seeded(7);
+        assert!((0..10).all(|_| first.generate() == second.generate()));
+    }
+
+    #[test]
+    fn test_generate_unique_avoids_taken_names() -> anyhow::Result<()> {
+        let mut taken = HashSet::new();
+        let mut names = NameGenerator::seeded(1);
+        for _ in 0..500 {
+            let name = names.generate_unique(&taken)?;
+            assert!(taken.insert(name));
+        }
+        // A replay of the same seed draws exactly the names taken so far
+        let mut replay = NameGenerator::seeded(1).with_max_attempts(3);
+        let mut original = NameGenerator::seeded(1);
+        let everything_taken: HashSet<_> = (0..3).map(|_| original.generate()).collect();
+        assert_eq!(
+            replay.generate_unique(&everything_taken),
+            Err(ErnError::NamesExhausted(3))
+        );
+        Ok(())
+    }
+}
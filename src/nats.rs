@@ -0,0 +1,136 @@
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::pattern::{ErnPattern, FilterLevel};
+use crate::wire::ErnWire;
+
+/// The first token of every subject produced by [`Ern::to_nats_subject`].
+pub const NATS_SUBJECT_PREFIX: &str = "ern";
+
+/// Rejects values that cannot be a single NATS subject token.
+fn check_nats_token(token: &str) -> Result<(), ErnError> {
+    if token.is_empty() {
+        return Err(ErnError::ParseFailure(
+            "nats subject",
+            "empty token".to_string(),
+        ));
+    }
+    match token
+        .chars()
+        .find(|c| matches!(c, '.' | '*' | '>') || c.is_whitespace() || c.is_control())
+    {
+        Some(invalid) => Err(ErnError::InvalidCharacter("nats subject", invalid)),
+        None => Ok(()),
+    }
+}
+
+impl Ern {
+    /// Returns the NATS subject `ern.<domain>.<category>.<account>.<root>.<parts…>`.
+    ///
+    /// Subjects cannot escape the token separator or wildcards, so components containing `.`,
+    /// `*`, `>`, or whitespace are rejected with [`ErnError::InvalidCharacter`].
+    pub fn to_nats_subject(&self) -> Result<String, ErnError> {
+        let wire = ErnWire::from(self);
+        let mut subject = NATS_SUBJECT_PREFIX.to_string();
+        for token in wire.segments() {
+            check_nats_token(token)?;
+            subject.push('.');
+            subject.push_str(token);
+        }
+        Ok(subject)
+    }
+
+    /// Parses a subject produced by [`Ern::to_nats_subject`], keeping the root id.
+    pub fn from_nats_subject(subject: &str) -> Result<Self, ErnError> {
+        let mut tokens = subject.split('.');
+        if tokens.next() != Some(NATS_SUBJECT_PREFIX) {
+            return Err(ErnError::ParseFailure(
+                "nats subject",
+                format!("expected the `{}` prefix", NATS_SUBJECT_PREFIX),
+            ));
+        }
+        let tokens = tokens
+            .map(|token| check_nats_token(token).map(|_| token.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ern::try_from(ErnWire::from_segments("nats subject", tokens)?)
+    }
+}
+
+impl ErnPattern {
+    /// Returns the NATS subject, with wildcards, that matches the subjects
+    /// [`Ern::to_nats_subject`] produces for the ERNs (Entity Resource Names) this pattern matches.
+    ///
+    /// `*` stays the single-token wildcard `*`, and a trailing `**` becomes `>`. NATS matches `>`
+    /// against one or more tokens, so unlike `**` it does not cover the ERN without those parts;
+    /// subscribe to the subject without the trailing `.>` as well if those matter. NATS compares
+    /// tokens exactly, so a literal root must be a full root id rather than a root name, and `**`
+    /// anywhere else is rejected with [`ErnError::ParseFailure`].
+    pub fn to_nats_subject(&self) -> Result<String, ErnError> {
+        let mut subject = NATS_SUBJECT_PREFIX.to_string();
+        for level in self.filter_levels("nats subject")? {
+            subject.push('.');
+            match level {
+                FilterLevel::Literal(literal) => {
+                    check_nats_token(literal)?;
+                    subject.push_str(literal);
+                }
+                FilterLevel::One => subject.push('*'),
+                FilterLevel::Rest => subject.push('>'),
+            }
+        }
+        Ok(subject)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subject_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.with_parts(["region1", "shard-2"])?;
+        let subject = ern.to_nats_subject()?;
+        assert!(subject.starts_with("ern.acton.reactive.component.orders_"));
+        assert!(subject.ends_with(".region1.shard-2"));
+        assert_eq!(Ern::from_nats_subject(&subject)?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_unrepresentable_tokens() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?.add_part("v1.2")?;
+        assert_eq!(
+            ern.to_nats_subject(),
+            Err(ErnError::InvalidCharacter("nats subject", '.'))
+        );
+        assert!(Ern::from_nats_subject("ern.acton.reactive.component.*").is_err());
+        assert!(Ern::from_nats_subject("acton.reactive.component.root").is_err());
+        assert!(Ern::from_nats_subject("ern.acton..component.root").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pattern_subjects() -> anyhow::Result<()> {
+        let root = "orders_01h455vb4pex5vsknk084sn02q";
+        let pattern = ErnPattern::parse(&format!("ern:acton:*:*:{root}/*/shard/**"))?;
+        assert_eq!(
+            pattern.to_nats_subject()?,
+            format!("ern.acton.*.*.{root}.*.shard.>")
+        );
+        assert_eq!(
+            ErnPattern::parse("ern:*:*:*:*")?.to_nats_subject()?,
+            "ern.*.*.*.*"
+        );
+
+        assert!(ErnPattern::parse("ern:*:*:*:*/**/leaf")?
+            .to_nats_subject()
+            .is_err());
+        assert!(ErnPattern::parse("ern:*:*:*:orders")?
+            .to_nats_subject()
+            .is_err());
+        assert_eq!(
+            ErnPattern::parse("ern:*:*:*:*/v1.2")?.to_nats_subject(),
+            Err(ErnError::InvalidCharacter("nats subject", '.'))
+        );
+        Ok(())
+    }
+}
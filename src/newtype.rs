@@ -0,0 +1,149 @@
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+
+/// A strongly typed wrapper around an [`Ern`] whose domain, category, or account may be fixed.
+///
+/// Implement it with `#[derive(ErnNewtype)]` (behind the `derive` feature), which also generates
+/// `Display`, `FromStr`, `TryFrom<Ern>`, and, with the `serde` feature, `Serialize`/`Deserialize`.
+pub trait ErnNewtype: Sized {
+    /// The required domain, if any.
+    const DOMAIN: Option<&'static str> = None;
+    /// The required category, if any.
+    const CATEGORY: Option<&'static str> = None;
+    /// The required account, if any.
+    const ACCOUNT: Option<&'static str> = None;
+
+    /// Wraps `ern` without checking it against the constraints.
+    fn from_ern_unchecked(ern: Ern) -> Self;
+
+    /// Returns the wrapped ERN (Entity Resource Name).
+    fn as_ern(&self) -> &Ern;
+
+    /// Checks `ern` against [`DOMAIN`](Self::DOMAIN), [`CATEGORY`](Self::CATEGORY), and [`ACCOUNT`](Self::ACCOUNT).
+    fn validate(ern: &Ern) -> Result<(), ErnError> {
+        let checks = [
+            ("domain", Self::DOMAIN, ern.domain.as_str()),
+            ("category", Self::CATEGORY, ern.category.as_str()),
+            ("account", Self::ACCOUNT, ern.account.as_str()),
+        ];
+        for (name, expected, actual) in checks {
+            match expected {
+                Some(expected) if expected != actual => {
+                    return Err(ErnError::UnexpectedComponent(
+                        name,
+                        expected,
+                        actual.to_string(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Wraps `ern` after validating it.
+    fn try_from_ern(ern: Ern) -> Result<Self, ErnError> {
+        Self::validate(&ern)?;
+        Ok(Self::from_ern_unchecked(ern))
+    }
+
+    /// Parses and validates an ERN (Entity Resource Name) string.
+    fn parse(value: &str) -> Result<Self, ErnError> {
+        Self::try_from_ern(ErnParser::new(value.to_string()).parse()?)
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::literal::{check_ern_literal, ern_from_literal};
+
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    #[cfg(feature = "serde")]
+    pub fn serialize<T: super::ErnNewtype, S: serde::Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        crate::serde_ern::serialize(value.as_ern(), serializer)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn deserialize<'de, T: super::ErnNewtype, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let ern = crate::serde_ern::deserialize(deserializer)?;
+        T::try_from_ern(ern).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ern_newtype_serde {
+    ($name:ident) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S: $crate::__private::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::core::result::Result<S::Ok, S::Error> {
+                $crate::__private::serialize(self, serializer)
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D: $crate::__private::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::core::result::Result<Self, D::Error> {
+                $crate::__private::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ern_newtype_serde {
+    ($name:ident) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct OrderErn(Ern);
+
+    impl ErnNewtype for OrderErn {
+        const CATEGORY: Option<&'static str> = Some("orders");
+
+        fn from_ern_unchecked(ern: Ern) -> Self {
+            OrderErn(ern)
+        }
+
+        fn as_ern(&self) -> &Ern {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn test_matching_category_is_accepted() -> anyhow::Result<()> {
+        let order = OrderErn::parse("ern:acton:orders:tenant9:order1")?;
+        assert_eq!(order.as_ern().category.as_str(), "orders");
+        Ok(())
+    }
+
+    #[test]
+    fn test_mismatched_category_is_rejected() {
+        let result = OrderErn::parse("ern:acton:invoices:tenant9:order1");
+        assert_eq!(
+            result.err(),
+            Some(ErnError::UnexpectedComponent(
+                "category",
+                "orders",
+                "invoices".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+use crate::wire::ErnWire;
+
+/// The OpenTelemetry entity type of every ERN (Entity Resource Name).
+pub const OTEL_ENTITY_TYPE: &str = "acton.resource";
+
+/// The resource attribute holding the full ERN (Entity Resource Name) string.
+pub const OTEL_ERN_ATTRIBUTE: &str = "acton.ern";
+
+/// The identifying attributes, in order, holding the domain, category, account, root, and `/`-joined parts.
+pub const OTEL_ID_ATTRIBUTES: [&str; 5] = [
+    "acton.ern.domain",
+    "acton.ern.category",
+    "acton.ern.account",
+    "acton.ern.root",
+    "acton.ern.path",
+];
+
+/// The descriptive attribute holding the revision, when the ERN has one.
+pub const OTEL_REVISION_ATTRIBUTE: &str = "acton.ern.revision";
+
+/// An OpenTelemetry entity describing an ERN (Entity Resource Name), per the semantic conventions' entity data model.
+///
+/// The identifying attributes never change for the lifetime of the resource, so backends can
+/// correlate telemetry across restarts; the descriptive attributes carry the full ERN string and
+/// revision, which are convenient for display and search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtelEntity {
+    /// The entity type, always [`OTEL_ENTITY_TYPE`].
+    pub entity_type: String,
+    /// The identifying attributes, keyed by [`OTEL_ID_ATTRIBUTES`].
+    pub id: Vec<(String, String)>,
+    /// The descriptive attributes: [`OTEL_ERN_ATTRIBUTE`] and, if set, [`OTEL_REVISION_ATTRIBUTE`].
+    pub description: Vec<(String, String)>,
+}
+
+impl OtelEntity {
+    /// Returns the identifying and descriptive attributes together, as set on an OTel `Resource`.
+    pub fn resource_attributes(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.id
+            .iter()
+            .chain(&self.description)
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+impl Ern {
+    /// Returns the OpenTelemetry entity identifying this ERN (Entity Resource Name).
+    pub fn to_otel_entity(&self) -> OtelEntity {
+        let wire = ErnWire::from(self);
+        let path = wire.parts.join("/");
+        let values = [wire.domain, wire.category, wire.account, wire.root, path];
+        let mut description = vec![(OTEL_ERN_ATTRIBUTE.to_string(), self.to_string())];
+        if let Some(revision) = self.revision {
+            description.push((OTEL_REVISION_ATTRIBUTE.to_string(), revision.to_string()));
+        }
+        OtelEntity {
+            entity_type: OTEL_ENTITY_TYPE.to_string(),
+            id: OTEL_ID_ATTRIBUTES
+                .iter()
+                .map(|key| key.to_string())
+                .zip(values)
+                .collect(),
+            description,
+        }
+    }
+
+    /// Reads an ERN (Entity Resource Name) back from OpenTelemetry resource attributes.
+    ///
+    /// Uses [`OTEL_ERN_ATTRIBUTE`] when present and otherwise rebuilds the ERN from the identifying
+    /// attributes and [`OTEL_REVISION_ATTRIBUTE`], so it also accepts backends that keep only the
+    /// identifying attributes of an entity. Unrelated attributes are ignored.
+    pub fn try_from_otel_attributes<'a>(
+        attributes: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, ErnError> {
+        let mut id: [Option<&str>; 5] = [None; 5];
+        let mut revision = None;
+        for (key, value) in attributes {
+            if key == OTEL_ERN_ATTRIBUTE {
+                return ErnParser::new(value.to_string()).parse();
+            }
+            if key == OTEL_REVISION_ATTRIBUTE {
+                revision = Some(value.parse().map_err(|_| {
+                    ErnError::parse_failure(
+                        "otel attributes",
+                        format!("`{value}` is not a revision"),
+                    )
+                })?);
+            } else if let Some(index) = OTEL_ID_ATTRIBUTES.iter().position(|id_key| *id_key == key)
+            {
+                id[index] = Some(value);
+            }
+        }
+        let take = |index: usize| {
+            id[index].map(str::to_string).ok_or_else(|| {
+                ErnError::parse_failure(
+                    "otel attributes",
+                    format!("missing `{}`", OTEL_ID_ATTRIBUTES[index]),
+                )
+            })
+        };
+        let components = [take(0)?, take(1)?, take(2)?, take(3)?];
+        let path = take(4)?;
+        let parts = path
+            .split('/')
+            .filter(|part| !part.is_empty())
+            .map(str::to_string);
+        Ern::try_from(ErnWire {
+            revision,
+            ..ErnWire::from_segments("otel attributes", components.into_iter().chain(parts))?
+        })
+    }
+}
+
+impl TryFrom<&OtelEntity> for Ern {
+    type Error = ErnError;
+
+    fn try_from(entity: &OtelEntity) -> Result<Self, Self::Error> {
+        if entity.entity_type != OTEL_ENTITY_TYPE {
+            return Err(ErnError::parse_failure(
+                "otel attributes",
+                format!(
+                    "entity type `{}` is not `{OTEL_ENTITY_TYPE}`",
+                    entity.entity_type
+                ),
+            ));
+        }
+        Ern::try_from_otel_attributes(entity.resource_attributes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?
+            .with_parts(["eu", "shard-2"])?
+            .with_revision(4);
+        let entity = ern.to_otel_entity();
+        assert_eq!(
+            entity.id[4],
+            ("acton.ern.path".to_string(), "eu/shard-2".to_string())
+        );
+        assert_eq!(entity.description[1].1, "4");
+        assert_eq!(Ern::try_from(&entity)?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_identifying_attributes_suffice() -> anyhow::Result<()> {
+        let ern = Ern::with_root("orders")?;
+        let entity = ern.to_otel_entity();
+        let attributes = entity
+            .id
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .chain([("service.name", "checkout")]);
+        assert_eq!(Ern::try_from_otel_attributes(attributes)?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_incomplete_attributes() {
+        assert_eq!(
+            Ern::try_from_otel_attributes([("acton.ern.domain", "acton")]),
+            Err(ErnError::ParseFailure(
+                "otel attributes",
+                "missing `acton.ern.category`".to_string()
+            ))
+        );
+    }
+}
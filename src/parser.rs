@@ -1,8 +1,12 @@
 use std::str::FromStr;
 
-use crate::EntityRoot;
+use crate::config::{
+    check_parts_separator, split_parts, unescape_part, ErnConfig, DEFAULT_PARTS_SEPARATOR,
+};
 use crate::errors::ErnError;
-use crate::model::{Account, Category, Domain, Ern, Part, Parts};
+use crate::instrumentation;
+use crate::model::{Account, Category, Domain, Ern, ErnRef, Part, Parts};
+use crate::EntityRoot;
 
 /// A parser for decoding ERN (Entity Resource Name) strings into their constituent components.
 pub struct ErnParser {
@@ -21,9 +25,7 @@ impl ErnParser {
     ///
     /// Returns an `ErnParser` instance initialized with the given ERN (Entity Resource Name) string.
     pub fn new(ern: String) -> Self {
-        Self {
-            ern,
-        }
+        Self { ern }
     }
 
     /// Parses the ERN (Entity Resource Name) into its component parts and returns them as a structured result.
@@ -34,9 +36,51 @@ impl ErnParser {
     /// Returns an `ERN (Entity Resource Name)` instance containing the parsed components.
     /// If parsing fails, returns an error message as a `String`.
     pub fn parse(&self) -> Result<Ern, ErnError> {
-        let parts: Vec<String> = self.ern.splitn(5, ':').map(|s| s.to_string()).collect();
+        self.parse_with_config(&ErnConfig::default())
+    }
+
+    /// Parses `value` into an [`ErnRef`] that borrows its components instead of allocating them.
+    ///
+    /// Use it to decode ERNs off the wire at high volume; the default configuration applies.
+    pub fn parse_ref(value: &str) -> Result<ErnRef<'_>, ErnError> {
+        ErnRef::parse(value)
+    }
+
+    /// Parses the ERN (Entity Resource Name) applying the scheme, normalization, and validation rules in `config`.
+    pub fn parse_with_config(&self, config: &ErnConfig) -> Result<Ern, ErnError> {
+        instrumentation::increment(instrumentation::PARSE_CALLS, None);
+        instrumentation::record(instrumentation::PARSE_INPUT_BYTES, self.ern.len() as f64);
+        let result = self.parse_unrecorded(config);
+        if let Err(error) = &result {
+            instrumentation::increment(instrumentation::PARSE_FAILURES, Some(error.kind()));
+        }
+        result
+    }
 
-        if parts.len() != 5 || parts[0] != "ern" {
+    fn parse_unrecorded(&self, config: &ErnConfig) -> Result<Ern, ErnError> {
+        let input = if config.normalize {
+            self.ern.trim()
+        } else {
+            self.ern.as_str()
+        };
+        if let Some(max_length) = config.validation.max_length {
+            if input.len() > max_length {
+                return Err(ErnError::LimitExceeded("length", max_length, input.len()));
+            }
+        }
+
+        if config.parts_separator != DEFAULT_PARTS_SEPARATOR {
+            check_parts_separator(config.parts_separator)?;
+        }
+
+        let mut parts: Vec<String> = input.splitn(5, ':').map(|s| s.to_string()).collect();
+        if config.normalize {
+            for part in parts.iter_mut().take(4) {
+                *part = config.normalized(part).into_owned();
+            }
+        }
+
+        if parts.len() != 5 || parts[0] != config.normalized(&config.scheme) {
             return Err(ErnError::InvalidFormat);
         }
 
@@ -45,21 +89,47 @@ impl ErnParser {
         let account = Account::from_str(&parts[3])?;
 
         // Split the root and the path part
-        let root_path: Vec<String> = parts[4].splitn(2, '/').map(|s| s.to_string()).collect();
-        let root_str = root_path[0].clone();
-        let root: EntityRoot = EntityRoot::from_str(root_str.as_str())?;
+        let root_path: Vec<String> = parts[4]
+            .splitn(2, config.parts_separator)
+            .map(|s| s.to_string())
+            .collect();
+        // An optional revision counter follows the root, e.g. `root#7`
+        let (root_str, revision) = match root_path[0].split_once('#') {
+            Some((root, revision)) => {
+                let revision = revision.parse::<u64>().map_err(|_| {
+                    ErnError::ParseFailure(
+                        "revision",
+                        format!("'{}' is not a valid revision", revision),
+                    )
+                })?;
+                (root.to_string(), Some(revision))
+            }
+            None => (root_path[0].clone(), None),
+        };
+        // Keep an existing root id intact so parsing a displayed ERN round-trips;
+        // plain names still get a freshly generated id.
+        let root: EntityRoot = match EntityRoot::parse(&root_str) {
+            Ok(root) => root,
+            Err(_) => EntityRoot::from_str(root_str.as_str())?,
+        };
 
         // Continue with the path parts
         let mut ern_parts = Vec::new();
         if root_path.len() > 1 {
-            let path_parts: Vec<String> = root_path[1].split('/').map(|s| s.to_string()).collect();
-            for part in path_parts.iter() {
-                ern_parts.push(Part::from_str(part)?);
+            for piece in split_parts(&root_path[1], config.parts_separator) {
+                let part = unescape_part(piece, config.parts_separator)?;
+                ern_parts.push(Part::from_str(&config.normalized(&part))?);
             }
         }
 
         let parts = Parts::new(ern_parts);
-        Ok(Ern::new(domain, category, account, root, parts))
+        let ern = Ern::new(domain, category, account, root, parts);
+        let ern = match revision {
+            Some(revision) => ern.with_revision(revision),
+            None => ern,
+        };
+        config.validation.validate(&ern)?;
+        Ok(ern)
     }
 }
 
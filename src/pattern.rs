@@ -0,0 +1,648 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::config::{
+    check_parts_separator, split_parts, unescape_part, ErnConfig, DEFAULT_PARTS_SEPARATOR,
+};
+use crate::errors::ErnError;
+use crate::model::{EntityRoot, Ern, ErnRef};
+
+/// One of the `:`-separated components of a pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Component {
+    /// `*`: any value.
+    Any,
+    Literal(String),
+}
+
+/// One `/`-separated segment of a pattern's parts path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Segment {
+    Literal(String),
+    /// `*`: exactly one part.
+    One,
+    /// `**`: zero or more parts.
+    Many,
+}
+
+impl Component {
+    fn accepts(&self, value: &str) -> bool {
+        match self {
+            Component::Any => true,
+            Component::Literal(literal) => literal == value,
+        }
+    }
+
+    /// Roots also match by the name they were created from, since their ids are generated.
+    fn accepts_root(&self, root: &str) -> bool {
+        match self {
+            Component::Any => true,
+            Component::Literal(literal) => literal == root || literal == base_name(root),
+        }
+    }
+}
+
+impl Segment {
+    fn accepts(&self, part: &str) -> bool {
+        match self {
+            Segment::Literal(literal) => literal == part,
+            Segment::One | Segment::Many => true,
+        }
+    }
+}
+
+/// Returns the TypeID prefix of a root, i.e. everything before the generated id suffix.
+fn base_name(root: &str) -> &str {
+    root.rsplit_once('_').map_or(root, |(name, _)| name)
+}
+
+/// A pattern over ERNs (Entity Resource Names) for topic-style subscriptions, such as
+/// `ern:*:service:*:orders/*/leaf` or `ern:acton:*:*:*/**`.
+///
+/// Each of the domain, category, account, and root components is either a literal or `*`, which
+/// matches any value. A literal root matches either the full root id or the name the root was
+/// created from, so `orders` matches `orders_01h455vb4pex5vsknk084sn02q`. In the parts path, `*`
+/// matches exactly one part and `**` matches zero or more; a pattern without parts only matches
+/// ERNs without parts. Revisions are ignored.
+///
+/// Matching walks the path with a small table per call; for hot routing paths, [`compile`] the
+/// pattern once and match with the [`CompiledErnPattern`].
+///
+/// [`compile`]: ErnPattern::compile
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErnPattern {
+    pub(crate) domain: Component,
+    pub(crate) category: Component,
+    pub(crate) account: Component,
+    pub(crate) root: Component,
+    pub(crate) parts: Vec<Segment>,
+}
+
+/// One level of an [`ErnPattern`] translated for a message broker's topic filter.
+pub(crate) enum FilterLevel<'a> {
+    Literal(&'a str),
+    /// `*`: exactly one level.
+    One,
+    /// A trailing `**`: every remaining level.
+    Rest,
+}
+
+impl ErnPattern {
+    /// Parses `ern:domain:category:account:root/part…` where any component or part may be `*`,
+    /// and any part may be `**`. Wildcards must fill a whole component or part; a literal `*` or
+    /// `\` in a part is written `\*` or `\\`, as [`Display`](fmt::Display) does.
+    pub fn parse(pattern: &str) -> Result<Self, ErnError> {
+        Self::parse_with_config(pattern, &ErnConfig::default())
+    }
+
+    /// Parses a pattern written with the scheme and parts separator of `config`, the form
+    /// [`Ern::display_with`] renders. Escaped parts such as `\*` are literals, not wildcards.
+    pub fn parse_with_config(pattern: &str, config: &ErnConfig) -> Result<Self, ErnError> {
+        let separator = config.parts_separator;
+        if separator != DEFAULT_PARTS_SEPARATOR {
+            check_parts_separator(separator)?;
+        }
+        let body = pattern
+            .strip_prefix(config.scheme.as_ref())
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| {
+                ErnError::parse_failure(
+                    "pattern",
+                    format!("`{pattern}` must start with `{}:`", config.scheme),
+                )
+            })?;
+        let mut fields = body.splitn(4, ':');
+        let mut component = |name: &str| {
+            let field = fields.next().ok_or_else(|| {
+                ErnError::parse_failure("pattern", format!("`{pattern}` is missing the {name}"))
+            })?;
+            parse_component(pattern, name, field)
+        };
+        let domain = component("domain")?;
+        let category = component("category")?;
+        let account = component("account")?;
+        let rest = fields.next().ok_or_else(|| {
+            ErnError::parse_failure("pattern", format!("`{pattern}` is missing the root"))
+        })?;
+        let (root, path) = match rest.split_once(separator) {
+            Some((root, path)) => (root, Some(path)),
+            None => (rest, None),
+        };
+        if root.contains('#') {
+            return Err(ErnError::parse_failure(
+                "pattern",
+                format!("`{pattern}`: revisions cannot be matched"),
+            ));
+        }
+        let root = parse_component(pattern, "root", root)?;
+
+        let mut parts = Vec::new();
+        for piece in path
+            .into_iter()
+            .flat_map(|path| split_parts(path, separator))
+        {
+            let segment = match piece {
+                "" => {
+                    return Err(ErnError::parse_failure(
+                        "pattern",
+                        format!("`{pattern}` has an empty part"),
+                    ))
+                }
+                "*" => Segment::One,
+                "**" => Segment::Many,
+                piece => match unescape_literal(piece, separator)? {
+                    part if part.contains(':') || part.contains('/') => {
+                        return Err(ErnError::InvalidPartFormat)
+                    }
+                    part if has_unescaped_star(piece, separator) => {
+                        return Err(ErnError::parse_failure(
+                            "pattern",
+                            format!("`{part}`: wildcards must fill a whole part"),
+                        ))
+                    }
+                    part => Segment::Literal(part.into_owned()),
+                },
+            };
+            // `**/**` matches exactly what `**` does
+            if !(segment == Segment::Many && parts.last() == Some(&Segment::Many)) {
+                parts.push(segment);
+            }
+        }
+
+        Ok(ErnPattern {
+            domain,
+            category,
+            account,
+            root,
+            parts,
+        })
+    }
+
+    /// Returns true if `ern` matches the pattern.
+    pub fn matches(&self, ern: &Ern) -> bool {
+        self.domain.accepts(ern.domain.as_str())
+            && self.category.accepts(ern.category.as_str())
+            && self.account.accepts(ern.account.as_str())
+            && self.root.accepts_root(ern.root.as_str())
+            && self.matches_parts(
+                &ern.parts
+                    .0
+                    .iter()
+                    .map(|part| part.as_str())
+                    .collect::<Vec<_>>(),
+            )
+    }
+
+    fn matches_parts(&self, parts: &[&str]) -> bool {
+        // matched[j] is true when the segments seen so far match the first j parts
+        let mut matched = vec![false; parts.len() + 1];
+        matched[0] = true;
+        for segment in &self.parts {
+            let mut next = vec![false; parts.len() + 1];
+            for j in 0..=parts.len() {
+                next[j] = match segment {
+                    Segment::Many => matched[j] || (j > 0 && next[j - 1]),
+                    segment => j > 0 && matched[j - 1] && segment.accepts(parts[j - 1]),
+                };
+            }
+            matched = next;
+        }
+        matched[parts.len()]
+    }
+
+    /// Returns the levels of a broker topic filter equivalent to the pattern, for `format`.
+    ///
+    /// Brokers compare levels exactly and only have a trailing multi-level wildcard, so a literal
+    /// root must be a full root id and `**` may only end the pattern.
+    pub(crate) fn filter_levels(
+        &self,
+        format: &'static str,
+    ) -> Result<Vec<FilterLevel<'_>>, ErnError> {
+        if let Component::Literal(root) = &self.root {
+            if EntityRoot::parse(root).is_err() {
+                return Err(ErnError::ParseFailure(
+                    format,
+                    format!(
+                        "`{self}`: a root must be `*` or a full root id, not the name `{root}`"
+                    ),
+                ));
+            }
+        }
+        let components = [&self.domain, &self.category, &self.account, &self.root]
+            .into_iter()
+            .map(|component| match component {
+                Component::Any => FilterLevel::One,
+                Component::Literal(literal) => FilterLevel::Literal(literal),
+            });
+        let mut levels: Vec<FilterLevel<'_>> = components.collect();
+        for (index, segment) in self.parts.iter().enumerate() {
+            levels.push(match segment {
+                Segment::Literal(literal) => FilterLevel::Literal(literal),
+                Segment::One => FilterLevel::One,
+                Segment::Many if index + 1 == self.parts.len() => FilterLevel::Rest,
+                Segment::Many => {
+                    return Err(ErnError::ParseFailure(
+                        format,
+                        format!("`{self}`: `**` can only be translated at the end of the pattern"),
+                    ))
+                }
+            });
+        }
+        Ok(levels)
+    }
+
+    /// Precomputes the pattern's anchors and part-count bounds for allocation-free matching.
+    pub fn compile(&self) -> CompiledErnPattern {
+        let mut groups = self.parts.split(|segment| *segment == Segment::Many);
+        let head = groups.next().unwrap_or_default().to_vec();
+        let mut middle: Vec<Vec<Segment>> = groups.map(<[Segment]>::to_vec).collect();
+        let tail = match middle.pop() {
+            Some(tail) => tail,
+            None => {
+                return CompiledErnPattern {
+                    pattern: self.clone(),
+                    fixed: head.len(),
+                    head,
+                    middle: None,
+                    tail: Vec::new(),
+                }
+            }
+        };
+        let middle: Vec<Vec<Segment>> = middle
+            .into_iter()
+            .filter(|group| !group.is_empty())
+            .collect();
+        CompiledErnPattern {
+            pattern: self.clone(),
+            fixed: head.len() + tail.len() + middle.iter().map(Vec::len).sum::<usize>(),
+            head,
+            middle: Some(middle),
+            tail,
+        }
+    }
+}
+
+/// Returns true if `piece` contains a `*` that is not escaped, which only custom separators allow.
+fn has_unescaped_star(piece: &str, separator: char) -> bool {
+    if separator == DEFAULT_PARTS_SEPARATOR && !piece.contains('\\') {
+        return piece.contains('*');
+    }
+    let mut escaped = false;
+    for c in piece.chars() {
+        match c {
+            '*' if !escaped => return true,
+            '\\' if !escaped => escaped = true,
+            _ => escaped = false,
+        }
+    }
+    false
+}
+
+/// Removes the escapes from a literal part: those of [`unescape_part`] with a custom separator,
+/// or the `\\` and `\*` that `Display` writes with the default one.
+fn unescape_literal(piece: &str, separator: char) -> Result<Cow<'_, str>, ErnError> {
+    if separator != DEFAULT_PARTS_SEPARATOR || !piece.contains('\\') {
+        return unescape_part(piece, separator);
+    }
+    let mut part = String::with_capacity(piece.len());
+    let mut chars = piece.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(&escaped @ ('\\' | '*'))) => {
+                part.push(escaped);
+                chars.next();
+            }
+            _ => part.push(c),
+        }
+    }
+    Ok(Cow::Owned(part))
+}
+
+fn parse_component(pattern: &str, name: &str, field: &str) -> Result<Component, ErnError> {
+    match field {
+        "" => Err(ErnError::parse_failure(
+            "pattern",
+            format!("`{pattern}` has an empty {name}"),
+        )),
+        "*" => Ok(Component::Any),
+        field if field.contains('*') => Err(ErnError::parse_failure(
+            "pattern",
+            format!("`{field}`: wildcards must fill the whole {name}"),
+        )),
+        field => Ok(Component::Literal(field.to_string())),
+    }
+}
+
+impl FromStr for ErnPattern {
+    type Err = ErnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ErnPattern::parse(s)
+    }
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Component::Any => f.write_str("*"),
+            Component::Literal(literal) => f.write_str(literal),
+        }
+    }
+}
+
+impl fmt::Display for ErnPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ern:{}:{}:{}:{}",
+            self.domain, self.category, self.account, self.root
+        )?;
+        for segment in &self.parts {
+            match segment {
+                Segment::Literal(literal) if literal.contains(['*', '\\']) => {
+                    f.write_str("/")?;
+                    for c in literal.chars() {
+                        if matches!(c, '*' | '\\') {
+                            f.write_str("\\")?;
+                        }
+                        write!(f, "{c}")?;
+                    }
+                }
+                Segment::Literal(literal) => write!(f, "/{literal}")?,
+                Segment::One => f.write_str("/*")?,
+                Segment::Many => f.write_str("/**")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An [`ErnPattern`] laid out for matching on hot routing paths.
+///
+/// The parts path is split at each `**` into a head anchored at the start, a tail anchored at the
+/// end, and the groups in between, which are found left to right. Part counts outside the
+/// pattern's bounds are rejected before any part is compared, and matching never allocates, even
+/// for raw strings, which are validated and borrowed rather than parsed into an [`Ern`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompiledErnPattern {
+    pattern: ErnPattern,
+    head: Vec<Segment>,
+    /// The groups between `**`s, or `None` when the pattern has no `**` and so a fixed length.
+    middle: Option<Vec<Vec<Segment>>>,
+    tail: Vec<Segment>,
+    /// The number of parts matched by segments other than `**`.
+    fixed: usize,
+}
+
+impl CompiledErnPattern {
+    /// Returns the pattern this was compiled from.
+    pub fn pattern(&self) -> &ErnPattern {
+        &self.pattern
+    }
+
+    pub fn matches(&self, ern: &Ern) -> bool {
+        self.matches_components(
+            ern.domain.as_str(),
+            ern.category.as_str(),
+            ern.account.as_str(),
+            ern.root.as_str(),
+        ) && self.matches_parts(ern.parts.0.iter().map(|part| part.as_str()))
+    }
+
+    pub fn matches_ref(&self, ern: &ErnRef<'_>) -> bool {
+        self.matches_components(
+            ern.domain().as_str(),
+            ern.category().as_str(),
+            ern.account().as_str(),
+            ern.root().as_str(),
+        ) && self.matches_parts(ern.parts().map(|part| part.as_str()))
+    }
+
+    /// Returns true if `value` is a valid ERN (Entity Resource Name) that matches the pattern.
+    pub fn matches_str(&self, value: &str) -> bool {
+        ErnRef::parse(value).is_ok_and(|ern| self.matches_ref(&ern))
+    }
+
+    fn matches_components(&self, domain: &str, category: &str, account: &str, root: &str) -> bool {
+        self.pattern.domain.accepts(domain)
+            && self.pattern.category.accepts(category)
+            && self.pattern.account.accepts(account)
+            && self.pattern.root.accepts_root(root)
+    }
+
+    fn matches_parts<'a>(&self, parts: impl Iterator<Item = &'a str> + Clone) -> bool {
+        let len = parts.clone().count();
+        if len < self.fixed || (self.middle.is_none() && len > self.fixed) {
+            return false;
+        }
+        let mut rest = parts;
+        for segment in &self.head {
+            if !rest.next().is_some_and(|part| segment.accepts(part)) {
+                return false;
+            }
+        }
+        let Some(middle) = &self.middle else {
+            return true;
+        };
+        // the parts between the head and the tail, which the middle groups must fit into in order
+        let mut available = len - self.head.len() - self.tail.len();
+        if !self
+            .tail
+            .iter()
+            .zip(rest.clone().skip(available))
+            .all(|(segment, part)| segment.accepts(part))
+        {
+            return false;
+        }
+        for group in middle {
+            loop {
+                if group.len() > available {
+                    return false;
+                }
+                if group
+                    .iter()
+                    .zip(rest.clone())
+                    .all(|(segment, part)| segment.accepts(part))
+                {
+                    rest.nth(group.len() - 1);
+                    available -= group.len();
+                    break;
+                }
+                rest.next();
+                available -= 1;
+            }
+        }
+        true
+    }
+}
+
+impl From<ErnPattern> for CompiledErnPattern {
+    fn from(pattern: ErnPattern) -> Self {
+        pattern.compile()
+    }
+}
+
+impl fmt::Display for CompiledErnPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pattern.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErnPattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ErnPattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        ErnPattern::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ern(value: &str) -> anyhow::Result<Ern> {
+        Ok(value.parse()?)
+    }
+
+    #[test]
+    fn test_component_wildcards() -> anyhow::Result<()> {
+        let pattern = ErnPattern::parse("ern:*:service:*:orders/*/leaf")?;
+        assert!(pattern.matches(&ern(
+            "ern:acton:service:tenant-a:orders_01h455vb4pex5vsknk084sn02q/eu/leaf"
+        )?));
+        assert!(pattern.matches(&ern("ern:x:service:y:orders/us/leaf")?.with_revision(4)));
+        assert!(!pattern.matches(&ern("ern:acton:billing:tenant-a:orders/eu/leaf")?));
+        assert!(!pattern.matches(&ern("ern:acton:service:tenant-a:users/eu/leaf")?));
+        assert!(!pattern.matches(&ern("ern:acton:service:tenant-a:orders/leaf")?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_wildcards() -> anyhow::Result<()> {
+        let subject = ern("ern:a:b:c:orders/eu/shard/leaf")?;
+        for (pattern, expected) in [
+            ("ern:a:b:c:orders/**", true),
+            ("ern:a:b:c:orders/**/leaf", true),
+            ("ern:a:b:c:orders/eu/**/shard/**/leaf", true),
+            ("ern:a:b:c:orders/eu/shard/leaf/**", true),
+            ("ern:a:b:c:orders/*/*/*", true),
+            ("ern:a:b:c:orders/*/*", false),
+            ("ern:a:b:c:orders/**/eu/**/eu", false),
+            ("ern:a:b:c:orders", false),
+        ] {
+            let pattern = ErnPattern::parse(pattern)?;
+            assert_eq!(pattern.matches(&subject), expected, "{pattern}");
+            assert_eq!(
+                pattern.compile().matches(&subject),
+                expected,
+                "compiled {pattern}"
+            );
+            assert_eq!(
+                pattern.compile().matches_str(&subject.to_string()),
+                expected,
+                "str {pattern}"
+            );
+        }
+        assert!(ErnPattern::parse("ern:a:b:c:orders")?.matches(&ern("ern:a:b:c:orders")?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_with_parts_separator() -> anyhow::Result<()> {
+        let config = ErnConfig::default().with_parts_separator('.')?;
+        let subject = ern("ern:a:b:c:orders/v1.2/*/leaf")?;
+        let pattern = ErnPattern::parse_with_config("ern:a:b:c:orders.v1\\.2.\\*.*", &config)?;
+        assert!(pattern.matches(&subject));
+        assert!(!pattern.matches(&ern("ern:a:b:c:orders/v1.2/x/leaf")?));
+        assert!(
+            ErnPattern::parse_with_config("ern:*:*:*:*.**.leaf", &config)?
+                .compile()
+                .matches(&subject)
+        );
+        assert!(ErnPattern::parse_with_config("ern:*:*:*:*.a\\*b", &config).is_ok());
+        assert!(ErnPattern::parse_with_config("ern:*:*:*:*.a*b", &config).is_err());
+        assert!(ErnPattern::parse_with_config("ern:*:*:*:*.a\\**", &config).is_err());
+        assert!(ErnPattern::parse_with_config("ern:*:*:*:*.a/b", &config).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_literal_stars_survive_display() -> anyhow::Result<()> {
+        let config = ErnConfig::default().with_parts_separator('.')?;
+        for text in [
+            r"ern:a:b:c:root.\*",
+            r"ern:a:b:c:root.\*\*.a\*b",
+            r"ern:a:b:c:root.a\\b.*",
+        ] {
+            let pattern = ErnPattern::parse_with_config(text, &config)?;
+            let displayed = pattern.to_string();
+            assert_eq!(ErnPattern::parse(&displayed)?, pattern, "{displayed}");
+        }
+        let star = ErnPattern::parse_with_config(r"ern:a:b:c:root.\*", &config)?;
+        assert_eq!(star.to_string(), r"ern:a:b:c:root/\*");
+        assert!(star.matches(&ern("ern:a:b:c:root/*")?));
+        assert!(!ErnPattern::parse(&star.to_string())?.matches(&ern("ern:a:b:c:root/other")?));
+        assert!(ErnPattern::parse(r"ern:a:b:c:root/a\**").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compiled_matches_borrowed_input() -> anyhow::Result<()> {
+        let compiled: CompiledErnPattern = ErnPattern::parse("ern:acton:*:*:*/**/shard/*")?.into();
+        let value = "ern:acton:orders:tenant9:root_01h455vb4pex5vsknk084sn02q/eu/shard/7";
+        assert!(compiled.matches_ref(&ErnRef::parse(value)?));
+        assert!(compiled.matches_str(value));
+        assert!(!compiled.matches_str("ern:acton:orders:tenant9:root/eu/shard"));
+        assert!(!compiled.matches_str("not an ern"));
+        assert_eq!(compiled.to_string(), "ern:acton:*:*:*/**/shard/*");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_and_display() -> anyhow::Result<()> {
+        let pattern: ErnPattern = "ern:*:service:*:root/**/**/leaf".parse()?;
+        assert_eq!(pattern.to_string(), "ern:*:service:*:root/**/leaf");
+        assert_eq!(pattern.to_string().parse::<ErnPattern>()?, pattern);
+        for invalid in [
+            "urn:a:b:c:d",
+            "ern:a:b:c",
+            "ern::b:c:d",
+            "ern:a:b:c:d/",
+            "ern:a:b:c:d//e",
+            "ern:a:serv*:c:d",
+            "ern:a:b:c:d/e*",
+            "ern:a:b:c:d/x:y",
+            "ern:a:b:c:d#2",
+        ] {
+            assert!(ErnPattern::parse(invalid).is_err(), "{invalid}");
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_uses_the_text_form() -> anyhow::Result<()> {
+        let pattern = ErnPattern::parse("ern:acton:*:*:*/**")?;
+        let json = serde_json::to_string(&pattern)?;
+        assert_eq!(json, "\"ern:acton:*:*:*/**\"");
+        assert_eq!(serde_json::from_str::<ErnPattern>(&json)?, pattern);
+        assert!(serde_json::from_str::<ErnPattern>("\"ern:a:b*:c:d\"").is_err());
+        let star = ErnPattern::parse_with_config(
+            r"ern:a:b:c:d.\*",
+            &ErnConfig::default().with_parts_separator('.')?,
+        )?;
+        assert_eq!(
+            serde_json::from_str::<ErnPattern>(&serde_json::to_string(&star)?)?,
+            star
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use std::cell::RefCell;
+
+use crate::instrumentation;
+use crate::model::{Ern, Part};
+
+/// The most part buffers kept per thread; extra returned buffers are dropped.
+pub const BUILDER_POOL_CAPACITY: usize = 64;
+
+/// The part capacity newly allocated pooled buffers start with.
+const BUFFER_CAPACITY: usize = 8;
+
+thread_local! {
+    static BUILDER_POOL: RefCell<BuilderPool> = const { RefCell::new(BuilderPool::new()) };
+}
+
+/// Hit and miss counters for the current thread's builder pool, as reported by
+/// [`ErnBuilder::pool_stats`](crate::ErnBuilder::pool_stats).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BuilderPoolStats {
+    /// Pooled builders that reused a buffer.
+    pub hits: u64,
+    /// Pooled builders that had to allocate a fresh buffer.
+    pub misses: u64,
+    /// Buffers handed back to the pool.
+    pub returned: u64,
+    /// Buffers currently waiting in the pool.
+    pub available: usize,
+}
+
+impl BuilderPoolStats {
+    /// Returns the fraction of pooled builders served from the pool, or `0.0` before any were created.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+struct BuilderPool {
+    buffers: Vec<Vec<Part>>,
+    hits: u64,
+    misses: u64,
+    returned: u64,
+}
+
+impl BuilderPool {
+    const fn new() -> Self {
+        Self {
+            buffers: Vec::new(),
+            hits: 0,
+            misses: 0,
+            returned: 0,
+        }
+    }
+}
+
+/// Takes a cleared part buffer from the current thread's pool, allocating one on a miss.
+pub(crate) fn take_buffer() -> Vec<Part> {
+    BUILDER_POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+        match pool.buffers.pop() {
+            Some(buffer) => {
+                instrumentation::increment(instrumentation::CACHE_HITS, Some("builder_pool"));
+                pool.hits += 1;
+                buffer
+            }
+            None => {
+                instrumentation::increment(instrumentation::CACHE_MISSES, Some("builder_pool"));
+                pool.misses += 1;
+                Vec::with_capacity(BUFFER_CAPACITY)
+            }
+        }
+    })
+}
+
+/// Clears `buffer` and keeps it for the next pooled builder, unless the pool is full or the buffer never allocated.
+pub(crate) fn return_buffer(mut buffer: Vec<Part>) {
+    if buffer.capacity() == 0 {
+        return;
+    }
+    buffer.clear();
+    BUILDER_POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+        if pool.buffers.len() < BUILDER_POOL_CAPACITY {
+            pool.buffers.push(buffer);
+            pool.returned += 1;
+        }
+    });
+}
+
+/// Hands the part storage of a no-longer-needed ERN (Entity Resource Name) back to the pool.
+pub(crate) fn recycle(ern: Ern) {
+    return_buffer(ern.parts.0);
+}
+
+pub(crate) fn stats() -> BuilderPoolStats {
+    BUILDER_POOL.with(|pool| {
+        let pool = pool.borrow();
+        BuilderPoolStats {
+            hits: pool.hits,
+            misses: pool.misses,
+            returned: pool.returned,
+            available: pool.buffers.len(),
+        }
+    })
+}
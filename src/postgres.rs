@@ -0,0 +1,206 @@
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::types::{PgRecordDecoder, PgRecordEncoder};
+use sqlx::postgres::{
+    PgArgumentBuffer, PgArguments, PgHasArrayType, PgRow, PgTypeInfo, PgValueRef,
+};
+use sqlx::query::Query;
+use sqlx::{Decode, Encode, FromRow, Postgres, Row, Type};
+
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+use crate::wire::ErnWire;
+
+/// Creates the composite type used by [`ErnComposite`].
+pub const ERN_COMPOSITE_TYPE_SQL: &str =
+    "CREATE TYPE ern AS (domain text, category text, account text, root text, parts text[])";
+
+/// Stores an ERN (Entity Resource Name) as its canonical string in a `text` column.
+impl Type<Postgres> for Ern {
+    fn type_info() -> PgTypeInfo {
+        <String as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <String as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for Ern {
+    fn array_type_info() -> PgTypeInfo {
+        <String as PgHasArrayType>::array_type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for Ern {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <String as Encode<Postgres>>::encode(self.to_string(), buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Ern {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = <String as Decode<Postgres>>::decode(value)?;
+        Ok(ErnParser::new(value).parse()?)
+    }
+}
+
+/// Stores an ERN (Entity Resource Name) as the `ern` composite type created by [`ERN_COMPOSITE_TYPE_SQL`],
+/// so individual components can be indexed and queried as `(column).account`.
+///
+/// The revision is not stored; keep it in its own column if it is needed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErnComposite(pub Ern);
+
+impl From<Ern> for ErnComposite {
+    fn from(ern: Ern) -> Self {
+        ErnComposite(ern)
+    }
+}
+
+impl From<ErnComposite> for Ern {
+    fn from(composite: ErnComposite) -> Self {
+        composite.0
+    }
+}
+
+impl Type<Postgres> for ErnComposite {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("ern")
+    }
+}
+
+impl PgHasArrayType for ErnComposite {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_ern")
+    }
+}
+
+impl Encode<'_, Postgres> for ErnComposite {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let row = ErnRow::from(&self.0);
+        let mut encoder = PgRecordEncoder::new(buf);
+        encoder
+            .encode(row.domain)?
+            .encode(row.category)?
+            .encode(row.account)?
+            .encode(row.root)?
+            .encode(row.parts)?;
+        encoder.finish();
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for ErnComposite {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let mut decoder = PgRecordDecoder::new(value)?;
+        let row = ErnRow {
+            domain: decoder.try_decode()?,
+            category: decoder.try_decode()?,
+            account: decoder.try_decode()?,
+            root: decoder.try_decode()?,
+            parts: decoder.try_decode()?,
+        };
+        Ok(ErnComposite(Ern::try_from(row)?))
+    }
+}
+
+/// An ERN (Entity Resource Name) spread over separate `domain`, `category`, `account`, `root`, and
+/// `parts text[]` columns.
+///
+/// Read it from a query with `query_as::<_, ErnRow>` and bind it with [`ErnRow::bind`]. The
+/// revision is not stored.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErnRow {
+    pub domain: String,
+    pub category: String,
+    pub account: String,
+    pub root: String,
+    pub parts: Vec<String>,
+}
+
+impl ErnRow {
+    /// Binds the five columns, in the order above, as the next parameters of `query`.
+    pub fn bind<'q>(
+        self,
+        query: Query<'q, Postgres, PgArguments>,
+    ) -> Query<'q, Postgres, PgArguments> {
+        query
+            .bind(self.domain)
+            .bind(self.category)
+            .bind(self.account)
+            .bind(self.root)
+            .bind(self.parts)
+    }
+}
+
+impl From<&Ern> for ErnRow {
+    fn from(ern: &Ern) -> Self {
+        let wire = ErnWire::from(ern);
+        Self {
+            domain: wire.domain,
+            category: wire.category,
+            account: wire.account,
+            root: wire.root,
+            parts: wire.parts,
+        }
+    }
+}
+
+impl TryFrom<ErnRow> for Ern {
+    type Error = ErnError;
+
+    fn try_from(row: ErnRow) -> Result<Self, Self::Error> {
+        let components = [row.domain, row.category, row.account, row.root];
+        Ern::try_from(ErnWire::from_segments(
+            "postgres row",
+            components.into_iter().chain(row.parts),
+        )?)
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for ErnRow {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            domain: row.try_get("domain")?,
+            category: row.try_get("category")?,
+            account: row.try_get("account")?,
+            root: row.try_get("root")?,
+            parts: row.try_get("parts")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.with_parts(["a", "b"])?;
+        let row = ErnRow::from(&ern);
+        assert_eq!(row.parts, vec!["a", "b"]);
+        assert_eq!(Ern::try_from(row)?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_validates_components() -> anyhow::Result<()> {
+        let mut row = ErnRow::from(&Ern::with_root("root")?);
+        row.parts.push("a/b".to_string());
+        assert_eq!(Ern::try_from(row), Err(ErnError::InvalidPartFormat));
+        Ok(())
+    }
+
+    #[test]
+    fn test_composite_encodes_five_fields() -> anyhow::Result<()> {
+        let composite = ErnComposite(Ern::with_root("root")?.add_part("a")?);
+        let mut buf = PgArgumentBuffer::default();
+        assert!(matches!(composite.encode_by_ref(&mut buf), Ok(IsNull::No)));
+        // A record starts with its field count
+        assert_eq!(buf[..4], 5u32.to_be_bytes());
+        assert_eq!(ErnComposite::type_info(), PgTypeInfo::with_name("ern"));
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+use std::time::SystemTime;
+
+use crate::errors::ErnError;
+use crate::model::Ern;
+
+/// Audit metadata describing who created an ERN (Entity Resource Name), when, and through which system.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErnProvenance {
+    /// The ERN (Entity Resource Name) of the actor or service that created the resource.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_ern"))]
+    pub created_by: Ern,
+    /// When the resource was created.
+    pub created_at: SystemTime,
+    /// The system or workflow the resource originated from (e.g. `api`, `import`).
+    pub source: String,
+}
+
+impl ErnProvenance {
+    /// Creates a provenance record stamped with the current time.
+    pub fn new(created_by: Ern, source: impl Into<String>) -> Self {
+        Self {
+            created_by,
+            created_at: SystemTime::now(),
+            source: source.into(),
+        }
+    }
+
+    /// Checks that this record is plausible for `ern`.
+    ///
+    /// The record must not be dated in the future, must not predate the timestamp embedded in the
+    /// ERN's root, and the ERN must not claim to have created itself.
+    pub fn verify(&self, ern: &Ern) -> Result<(), ErnError> {
+        if self.created_at > SystemTime::now() {
+            return Err(ErnError::ProvenanceMismatch(
+                "created_at is in the future".to_string(),
+            ));
+        }
+        if let Some(root_created) = ern.root.timestamp() {
+            if self.created_at < root_created {
+                return Err(ErnError::ProvenanceMismatch(
+                    "created_at predates the resource root".to_string(),
+                ));
+            }
+        }
+        if &self.created_by == ern {
+            return Err(ErnError::ProvenanceMismatch(
+                "resource cannot be its own creator".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Verifies this record against `ern` and pairs them together.
+    pub fn attach(self, ern: Ern) -> Result<ProvenancedErn, ErnError> {
+        self.verify(&ern)?;
+        Ok(ProvenancedErn {
+            ern,
+            provenance: self,
+        })
+    }
+}
+
+/// An ERN (Entity Resource Name) stored alongside its verified provenance record.
+///
+/// Deserializing verifies the record the same way [`ErnProvenance::attach`] does.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RawProvenancedErn")
+)]
+pub struct ProvenancedErn {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_ern"))]
+    ern: Ern,
+    provenance: ErnProvenance,
+}
+
+/// The unverified form a [`ProvenancedErn`] is deserialized through.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawProvenancedErn {
+    #[serde(with = "crate::serde_ern")]
+    ern: Ern,
+    provenance: ErnProvenance,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawProvenancedErn> for ProvenancedErn {
+    type Error = ErnError;
+
+    fn try_from(raw: RawProvenancedErn) -> Result<Self, Self::Error> {
+        raw.provenance.attach(raw.ern)
+    }
+}
+
+impl ProvenancedErn {
+    /// Returns the ERN (Entity Resource Name).
+    pub fn ern(&self) -> &Ern {
+        &self.ern
+    }
+
+    /// Returns the provenance record.
+    pub fn provenance(&self) -> &ErnProvenance {
+        &self.provenance
+    }
+
+    /// Re-checks the provenance record, e.g. after it has been held for a while.
+    pub fn verify(&self) -> Result<(), ErnError> {
+        self.provenance.verify(&self.ern)
+    }
+
+    /// Splits the pair back into its parts.
+    pub fn into_parts(self) -> (Ern, ErnProvenance) {
+        (self.ern, self.provenance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_attach_and_verify() -> anyhow::Result<()> {
+        let creator = Ern::with_root("supervisor")?;
+        let ern = Ern::with_root("worker")?;
+        let provenance = ErnProvenance::new(creator.clone(), "spawn");
+        let attached = provenance.attach(ern.clone())?;
+        assert_eq!(attached.ern(), &ern);
+        assert_eq!(attached.provenance().created_by, creator);
+        assert!(attached.verify().is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_rejects_future_timestamp() -> anyhow::Result<()> {
+        let mut provenance = ErnProvenance::new(Ern::with_root("supervisor")?, "spawn");
+        provenance.created_at = SystemTime::now() + Duration::from_secs(3600);
+        assert!(matches!(
+            provenance.verify(&Ern::with_root("worker")?),
+            Err(ErnError::ProvenanceMismatch(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_rejects_record_older_than_root() -> anyhow::Result<()> {
+        let mut provenance = ErnProvenance::new(Ern::with_root("supervisor")?, "spawn");
+        provenance.created_at = SystemTime::now() - Duration::from_secs(3600);
+        assert!(provenance.verify(&Ern::with_root("worker")?).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_rejects_self_creation() -> anyhow::Result<()> {
+        let ern = Ern::with_root("worker")?;
+        let provenance = ErnProvenance::new(ern.clone(), "spawn");
+        assert!(provenance.verify(&ern).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("worker")?;
+        let provenance = ErnProvenance::new(Ern::with_root("supervisor")?, "spawn");
+        let attached = provenance.attach(ern)?;
+        let json = serde_json::to_string(&attached)?;
+        assert!(json.contains("ern:acton:reactive:component:supervisor_"));
+        let restored: ProvenancedErn = serde_json::from_str(&json)?;
+        assert_eq!(restored, attached);
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_rejects_mismatched_record() -> anyhow::Result<()> {
+        let ern = Ern::with_root("worker")?;
+        let json = serde_json::json!({
+            "ern": ern.to_string(),
+            "provenance": ErnProvenance::new(ern.clone(), "spawn"),
+        });
+        let error = serde_json::from_value::<ProvenancedErn>(json).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("resource cannot be its own creator"));
+        Ok(())
+    }
+}
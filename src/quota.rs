@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+use crate::errors::ErnError;
+use crate::model::{Ern, ErnPrefix};
+
+/// Usage counters keyed by the first `depth` components of each ERN (Entity Resource Name), with limits.
+///
+/// A depth of 3 groups by domain, category, and account, which expresses limits such as "at most
+/// 10,000 actors per account". Counters are atomic and the ledger is `Sync`, so it can be shared
+/// across threads behind an `Arc`.
+#[derive(Debug, Default)]
+pub struct QuotaLedger {
+    depth: usize,
+    default_limit: Option<u64>,
+    limits: HashMap<ErnPrefix, u64>,
+    usage: RwLock<HashMap<ErnPrefix, AtomicU64>>,
+}
+
+impl QuotaLedger {
+    /// Creates an unlimited ledger that groups ERNs by their first `depth` components.
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            ..Self::default()
+        }
+    }
+
+    /// Applies `limit` to every prefix without its own limit.
+    pub fn with_default_limit(mut self, limit: u64) -> Self {
+        self.default_limit = Some(limit);
+        self
+    }
+
+    /// Overrides the limit for one prefix, which should have the ledger's depth.
+    pub fn with_limit(mut self, prefix: ErnPrefix, limit: u64) -> Self {
+        self.limits.insert(prefix, limit);
+        self
+    }
+
+    /// Returns the prefix `ern` is counted under.
+    pub fn key(&self, ern: &Ern) -> ErnPrefix {
+        ErnPrefix::of(ern, self.depth)
+    }
+
+    /// Returns the limit for `prefix`, or `None` if it is unlimited.
+    pub fn limit(&self, prefix: &ErnPrefix) -> Option<u64> {
+        self.limits.get(prefix).copied().or(self.default_limit)
+    }
+
+    /// Returns the usage recorded under `ern`'s prefix.
+    pub fn usage(&self, ern: &Ern) -> u64 {
+        let usage = self
+            .usage
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        usage
+            .get(&self.key(ern))
+            .map_or(0, |counter| counter.load(Ordering::Acquire))
+    }
+
+    /// Returns how much more `ern`'s prefix may use, or `None` if it is unlimited.
+    pub fn remaining(&self, ern: &Ern) -> Option<u64> {
+        self.limit(&self.key(ern))
+            .map(|limit| limit.saturating_sub(self.usage(ern)))
+    }
+
+    /// Atomically adds `amount` to `ern`'s prefix if that stays within its limit, returning the new usage.
+    ///
+    /// Fails with [`ErnError::LimitExceeded`] `("quota", limit, requested)` and leaves the usage
+    /// unchanged otherwise, so concurrent callers can never overshoot the limit together.
+    pub fn try_increment(&self, ern: &Ern, amount: u64) -> Result<u64, ErnError> {
+        let key = self.key(ern);
+        let limit = self.limit(&key).unwrap_or(u64::MAX);
+        let exceeded = |current: u64| {
+            ErnError::LimitExceeded(
+                "quota",
+                usize::try_from(limit).unwrap_or(usize::MAX),
+                usize::try_from(current.saturating_add(amount)).unwrap_or(usize::MAX),
+            )
+        };
+        // An amount over the limit fails at any usage; rejecting it here keeps a failed
+        // increment from creating a counter for a prefix that has never been used
+        if amount > limit {
+            return Err(exceeded(self.usage(ern)));
+        }
+        self.with_counter(key, |counter| {
+            counter
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                    current
+                        .checked_add(amount)
+                        .filter(|requested| *requested <= limit)
+                })
+                .map(|previous| previous + amount)
+                .map_err(exceeded)
+        })
+    }
+
+    /// Atomically subtracts `amount` from `ern`'s prefix, stopping at zero, and returns the new usage.
+    ///
+    /// A prefix with no recorded usage is left out of the ledger rather than recorded as zero.
+    pub fn decrement(&self, ern: &Ern, amount: u64) -> u64 {
+        let usage = self
+            .usage
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let Some(counter) = usage.get(&self.key(ern)) else {
+            return 0;
+        };
+        let previous = counter
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                Some(current.saturating_sub(amount))
+            })
+            .unwrap_or_else(|current| current);
+        previous.saturating_sub(amount)
+    }
+
+    /// Returns every prefix with recorded usage and its current count.
+    pub fn snapshot(&self) -> Vec<(ErnPrefix, u64)> {
+        let usage = self
+            .usage
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut snapshot: Vec<_> = usage
+            .iter()
+            .map(|(prefix, counter)| (prefix.clone(), counter.load(Ordering::Acquire)))
+            .collect();
+        snapshot.sort();
+        snapshot
+    }
+
+    fn with_counter<T>(&self, key: ErnPrefix, f: impl FnOnce(&AtomicU64) -> T) -> T {
+        {
+            let usage = self
+                .usage
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(counter) = usage.get(&key) {
+                return f(counter);
+            }
+        }
+        let mut usage = self
+            .usage
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(usage.entry(key).or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn test_limits_are_enforced_per_prefix() -> anyhow::Result<()> {
+        let ledger = QuotaLedger::new(3).with_default_limit(2);
+        let first = Ern::with_root("a")?;
+        let sibling = Ern::with_root("b")?;
+        assert_eq!(ledger.try_increment(&first, 1)?, 1);
+        assert_eq!(ledger.try_increment(&sibling, 1)?, 2);
+        assert_eq!(
+            ledger.try_increment(&first, 1),
+            Err(ErnError::LimitExceeded("quota", 2, 3))
+        );
+        assert_eq!(ledger.usage(&first), 2);
+        assert_eq!(ledger.remaining(&first), Some(0));
+
+        let other_account = Ern::with_account("other")?;
+        assert_eq!(ledger.try_increment(&other_account, 2)?, 2);
+        assert_eq!(ledger.decrement(&first, 5), 0);
+        assert_eq!(ledger.snapshot().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decrement_ignores_unknown_prefixes() -> anyhow::Result<()> {
+        let ledger = QuotaLedger::new(3);
+        assert_eq!(ledger.decrement(&Ern::with_root("a")?, 1), 0);
+        assert!(ledger.snapshot().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejected_increment_records_no_usage() -> anyhow::Result<()> {
+        let ledger = QuotaLedger::new(3).with_default_limit(1);
+        let ern = Ern::with_root("a")?;
+        assert_eq!(
+            ledger.try_increment(&ern, 2),
+            Err(ErnError::LimitExceeded("quota", 1, 2))
+        );
+        assert!(ledger.snapshot().is_empty());
+        ledger.try_increment(&ern, 1)?;
+        assert_eq!(
+            ledger.try_increment(&ern, 2),
+            Err(ErnError::LimitExceeded("quota", 1, 3))
+        );
+        assert_eq!(ledger.snapshot(), vec![(ledger.key(&ern), 1)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_limit_errors_saturate() -> anyhow::Result<()> {
+        let ledger = QuotaLedger::new(3).with_default_limit(u64::MAX);
+        let ern = Ern::with_root("a")?;
+        ledger.try_increment(&ern, u64::MAX)?;
+        assert_eq!(
+            ledger.try_increment(&ern, 1),
+            Err(ErnError::LimitExceeded("quota", usize::MAX, usize::MAX))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefix_override() -> anyhow::Result<()> {
+        let ern = Ern::with_root("a")?;
+        let ledger = QuotaLedger::new(3)
+            .with_default_limit(1)
+            .with_limit("acton:reactive:component".parse()?, 10);
+        assert_eq!(ledger.limit(&ledger.key(&ern)), Some(10));
+        assert_eq!(ledger.try_increment(&ern, 10)?, 10);
+        assert_eq!(QuotaLedger::new(1).remaining(&ern), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_concurrent_increments_never_overshoot() -> anyhow::Result<()> {
+        let ledger = Arc::new(QuotaLedger::new(3).with_default_limit(100));
+        let ern = Ern::with_root("a")?;
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let (ledger, ern) = (Arc::clone(&ledger), ern.clone());
+                std::thread::spawn(move || {
+                    (0..50)
+                        .filter(|_| ledger.try_increment(&ern, 1).is_ok())
+                        .count()
+                })
+            })
+            .collect();
+        let granted: usize = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .sum();
+        assert_eq!(granted, 100);
+        assert_eq!(ledger.usage(&ern), 100);
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use crate::errors::ErnError;
+use crate::model::{Ern, ErnPrefix};
+
+/// Namespace reservations that keep teams or services from claiming overlapping ERN (Entity Resource Name) prefixes.
+///
+/// One owner may hold nested reservations (`billing` and `billing:invoices`), but a reservation
+/// that overlaps another owner's is refused.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReservationTable {
+    reservations: BTreeMap<ErnPrefix, String>,
+}
+
+impl ReservationTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of reservations.
+    pub fn len(&self) -> usize {
+        self.reservations.len()
+    }
+
+    /// Returns true if nothing is reserved.
+    pub fn is_empty(&self) -> bool {
+        self.reservations.is_empty()
+    }
+
+    /// Reserves `prefix` for `owner`, failing with [`ErnError::PrefixReserved`] if another owner
+    /// holds an overlapping prefix.
+    pub fn reserve(&mut self, prefix: ErnPrefix, owner: impl Into<String>) -> Result<(), ErnError> {
+        let owner = owner.into();
+        if let Some((existing, holder)) = self
+            .reservations
+            .iter()
+            .find(|(existing, holder)| existing.overlaps(&prefix) && **holder != owner)
+        {
+            return Err(ErnError::PrefixReserved(
+                existing.to_string(),
+                holder.clone(),
+            ));
+        }
+        self.reservations.insert(prefix, owner);
+        Ok(())
+    }
+
+    /// Removes the reservation of exactly `prefix`, returning its owner.
+    pub fn release(&mut self, prefix: &ErnPrefix) -> Option<String> {
+        self.reservations.remove(prefix)
+    }
+
+    /// Returns the owner of the most specific reservation covering `ern`.
+    pub fn owner_of(&self, ern: &Ern) -> Option<&str> {
+        self.reservations
+            .iter()
+            .filter(|(prefix, _)| prefix.contains(ern))
+            .max_by_key(|(prefix, _)| prefix.depth())
+            .map(|(_, owner)| owner.as_str())
+    }
+
+    /// Checks whether `claimant` may create `ern`, reporting the reservation it would violate.
+    ///
+    /// ERNs outside every reservation are free for anyone to claim.
+    pub fn check(&self, ern: &Ern, claimant: &str) -> Result<(), ErnError> {
+        match self
+            .reservations
+            .iter()
+            .find(|(prefix, owner)| prefix.contains(ern) && owner.as_str() != claimant)
+        {
+            Some((prefix, owner)) => {
+                Err(ErnError::PrefixReserved(prefix.to_string(), owner.clone()))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Returns every reservation, ordered by prefix.
+    pub fn iter(&self) -> impl Iterator<Item = (&ErnPrefix, &str)> {
+        self.reservations
+            .iter()
+            .map(|(prefix, owner)| (prefix, owner.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefix(value: &str) -> ErnPrefix {
+        value.parse().expect("test prefixes are valid")
+    }
+
+    #[test]
+    fn test_overlapping_reservations_are_refused() -> anyhow::Result<()> {
+        let mut table = ReservationTable::new();
+        table.reserve(prefix("billing"), "payments-team")?;
+        table.reserve(prefix("billing:invoices"), "payments-team")?;
+        assert_eq!(
+            table.reserve(prefix("billing:refunds"), "growth-team"),
+            Err(ErnError::PrefixReserved(
+                "ern:billing".to_string(),
+                "payments-team".to_string()
+            ))
+        );
+        table.reserve(prefix("billing-eu"), "growth-team")?;
+        assert_eq!(table.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_reports_conflicts() -> anyhow::Result<()> {
+        let mut table = ReservationTable::new();
+        table.reserve(prefix("acton:reactive"), "core")?;
+        let ern = Ern::with_root("orders")?;
+        assert!(table.check(&ern, "core").is_ok());
+        let error = table.check(&ern, "intruder").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Reservation Error - `ern:acton:reactive` is reserved by core"
+        );
+        assert_eq!(error.http_status(), 409);
+        assert!(table
+            .check(&Ern::with_domain("billing")?, "intruder")
+            .is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_owner_of_and_release() -> anyhow::Result<()> {
+        let mut table = ReservationTable::new();
+        table.reserve(prefix("acton"), "platform")?;
+        table.reserve(prefix("acton:reactive"), "platform")?;
+        let ern = Ern::with_root("orders")?;
+        assert_eq!(table.owner_of(&ern), Some("platform"));
+        assert_eq!(
+            table.release(&prefix("acton")),
+            Some("platform".to_string())
+        );
+        assert_eq!(table.release(&prefix("acton")), None);
+        assert_eq!(table.iter().count(), 1);
+        Ok(())
+    }
+}
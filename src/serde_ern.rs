@@ -0,0 +1,93 @@
+//! Serde helpers that encode an `Ern` as its canonical string and validate it through the parser
+//! when decoding.
+//!
+//! Use them on fields with `#[serde(with = "acton_ern::serde_ern")]`, or
+//! `acton_ern::serde_ern::option` for `Option<Ern>`. `Ern` itself implements `Serialize` and
+//! `Deserialize` with the same encoding, so new code can derive directly; the helpers keep
+//! existing `with` attributes working.
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+use crate::model::Ern;
+use crate::parser::ErnParser;
+
+pub fn serialize<S: Serializer>(ern: &Ern, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(ern)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Ern, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    parse(value)
+}
+
+fn parse<E: serde::de::Error>(value: String) -> Result<Ern, E> {
+    ErnParser::new(value.clone())
+        .parse()
+        .map_err(|error| E::custom(format!("invalid ERN `{value}`: {error}")))
+}
+
+/// The same encoding for optional fields.
+pub mod option {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::model::Ern;
+
+    pub fn serialize<S: Serializer>(ern: &Option<Ern>, serializer: S) -> Result<S::Ok, S::Error> {
+        match ern {
+            Some(ern) => serializer.serialize_some(&ern.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Ern>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(super::parse)
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::model::Ern;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        #[serde(with = "crate::serde_ern")]
+        ern: Ern,
+        #[serde(with = "crate::serde_ern::option")]
+        actor: Option<Ern>,
+    }
+
+    fn records() -> anyhow::Result<Vec<Record>> {
+        let ern: Ern = "ern:acton:service:acct:orders_01h455vb4pex5vsknk084sn02q/eu".parse()?;
+        Ok(vec![
+            Record {
+                ern: ern.clone(),
+                actor: Some(ern.with_revision(2)),
+            },
+            Record { ern, actor: None },
+        ])
+    }
+
+    #[test]
+    fn test_json_round_trip() -> anyhow::Result<()> {
+        for record in records()? {
+            let json = serde_json::to_string(&record)?;
+            assert_eq!(serde_json::from_str::<Record>(&json)?, record);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_postcard_round_trip() -> anyhow::Result<()> {
+        for record in records()? {
+            let bytes = postcard::to_allocvec(&record)?;
+            assert_eq!(postcard::from_bytes::<Record>(&bytes)?, record);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::wire::ErnWire;
+
+/// The maximum length of a SPIFFE ID, per the SPIFFE ID specification.
+const MAX_SPIFFE_ID_LENGTH: usize = 2048;
+
+fn check_trust_domain(trust_domain: &str) -> Result<(), ErnError> {
+    if trust_domain.is_empty() {
+        return Err(ErnError::parse_failure(
+            "spiffe id",
+            "trust domain cannot be empty",
+        ));
+    }
+    match trust_domain
+        .chars()
+        .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '-' | '_')))
+    {
+        Some(invalid) => Err(ErnError::InvalidCharacter("trust domain", invalid)),
+        None => Ok(()),
+    }
+}
+
+fn check_segment(segment: &str) -> Result<(), ErnError> {
+    if segment.is_empty() || segment == "." || segment == ".." {
+        return Err(ErnError::parse_failure(
+            "spiffe id",
+            format!("`{}` is not a valid path segment", segment),
+        ));
+    }
+    match segment
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')))
+    {
+        Some(invalid) => Err(ErnError::InvalidCharacter("spiffe id", invalid)),
+        None => Ok(()),
+    }
+}
+
+impl Ern {
+    /// Returns the SPIFFE ID `spiffe://<trust_domain>/<domain>/<category>/<account>/<root>/<parts…>`.
+    ///
+    /// SPIFFE path segments only allow ASCII letters, digits, `.`, `-`, and `_`, so components with
+    /// other characters are rejected with [`ErnError::InvalidCharacter`] rather than escaped.
+    pub fn to_spiffe_id(&self, trust_domain: &str) -> Result<String, ErnError> {
+        check_trust_domain(trust_domain)?;
+        let wire = ErnWire::from(self);
+        let mut id = format!("spiffe://{}", trust_domain);
+        for segment in wire.segments() {
+            check_segment(segment)?;
+            id.push('/');
+            id.push_str(segment);
+        }
+        if id.len() > MAX_SPIFFE_ID_LENGTH {
+            return Err(ErnError::LimitExceeded(
+                "spiffe id",
+                MAX_SPIFFE_ID_LENGTH,
+                id.len(),
+            ));
+        }
+        Ok(id)
+    }
+
+    /// Parses a SPIFFE ID produced by [`Ern::to_spiffe_id`], requiring it to belong to `trust_domain`.
+    pub fn try_from_spiffe_id(id: &str, trust_domain: &str) -> Result<Self, ErnError> {
+        if id.len() > MAX_SPIFFE_ID_LENGTH {
+            return Err(ErnError::LimitExceeded(
+                "spiffe id",
+                MAX_SPIFFE_ID_LENGTH,
+                id.len(),
+            ));
+        }
+        let rest = id
+            .strip_prefix("spiffe://")
+            .ok_or_else(|| ErnError::parse_failure("spiffe id", "missing `spiffe://` scheme"))?;
+        let (found, path) = rest.split_once('/').unwrap_or((rest, ""));
+        if found != trust_domain {
+            return Err(ErnError::parse_failure(
+                "spiffe id",
+                format!("trust domain `{}` does not match `{}`", found, trust_domain),
+            ));
+        }
+        // An empty path has no segments rather than one empty segment
+        let segments = path
+            .split('/')
+            .filter(|_| !path.is_empty())
+            .map(|segment| {
+                check_segment(segment)?;
+                Ok(segment.to_string())
+            })
+            .collect::<Result<Vec<_>, ErnError>>()?;
+        Ern::try_from(ErnWire::from_segments("spiffe id", segments)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spiffe_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("worker")?.with_parts(["pool-1", "actor_7"])?;
+        let id = ern.to_spiffe_id("prod.acton.example")?;
+        assert!(id.starts_with("spiffe://prod.acton.example/acton/reactive/component/worker_"));
+        assert!(id.ends_with("/pool-1/actor_7"));
+        assert_eq!(Ern::try_from_spiffe_id(&id, "prod.acton.example")?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_unrepresentable_components() -> anyhow::Result<()> {
+        let ern = Ern::with_root("worker")?.add_part("has space")?;
+        assert_eq!(
+            ern.to_spiffe_id("acton.example"),
+            Err(ErnError::InvalidCharacter("spiffe id", ' '))
+        );
+        assert_eq!(
+            Ern::with_root("worker")?.to_spiffe_id("Acton"),
+            Err(ErnError::InvalidCharacter("trust domain", 'A'))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_foreign_or_malformed_ids() -> anyhow::Result<()> {
+        let id = Ern::with_root("worker")?.to_spiffe_id("a.example")?;
+        assert!(Ern::try_from_spiffe_id(&id, "b.example").is_err());
+        assert!(Ern::try_from_spiffe_id("spiffe://a.example/acton/reactive", "a.example").is_err());
+        assert!(Ern::try_from_spiffe_id("https://a.example/x", "a.example").is_err());
+        assert!(Ern::try_from_spiffe_id(&format!("{id}/../x"), "a.example").is_err());
+        Ok(())
+    }
+}
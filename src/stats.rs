@@ -0,0 +1,202 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::model::{Ern, ErnPrefix};
+
+/// Exact counts of distinct ERNs (Entity Resource Names) under every prefix of a collection,
+/// such as a registry dump, for spotting runaway resource creation.
+///
+/// Each distinct ERN is counted once under each of its prefixes, from its domain down to the
+/// full path; revisions are ignored, so `…:root#1/a` and `…:root#2/a` count as one resource.
+/// Memory grows with the number of distinct prefixes, so use
+/// [`ErnCardinality`](crate::ErnCardinality) for unbounded streams.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErnStats {
+    total: usize,
+    seen: HashSet<ErnPrefix>,
+    prefixes: BTreeMap<ErnPrefix, usize>,
+}
+
+impl ErnStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an ERN (Entity Resource Name), returning false if it was already counted.
+    pub fn insert(&mut self, ern: &Ern) -> bool {
+        self.total += 1;
+        let full = ErnPrefix::of(ern, usize::MAX);
+        let depth = full.depth();
+        if !self.seen.insert(full) {
+            return false;
+        }
+        for prefix_depth in 1..=depth {
+            *self
+                .prefixes
+                .entry(ErnPrefix::of(ern, prefix_depth))
+                .or_default() += 1;
+        }
+        true
+    }
+
+    /// Returns the number of ERNs recorded, including duplicates.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the number of distinct ERNs recorded.
+    pub fn distinct(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Returns the number of distinct ERNs at or below `prefix`.
+    pub fn cardinality(&self, prefix: &ErnPrefix) -> usize {
+        if prefix.depth() == 0 {
+            return self.distinct();
+        }
+        self.prefixes.get(prefix).copied().unwrap_or_default()
+    }
+
+    /// Returns each prefix with `depth` components and the number of distinct ERNs below it, in
+    /// prefix order.
+    pub fn prefixes_at(&self, depth: usize) -> impl Iterator<Item = (&ErnPrefix, usize)> {
+        self.prefixes
+            .iter()
+            .filter(move |(prefix, _)| prefix.depth() == depth)
+            .map(|(prefix, count)| (prefix, *count))
+    }
+
+    /// Returns the number of distinct prefixes at each depth, starting with depth 1 (domains).
+    ///
+    /// Comparing neighbouring entries shows where the hierarchy fans out: a jump from 4 (roots)
+    /// to 5 means each root carries many first-level parts.
+    pub fn growth_by_depth(&self) -> Vec<usize> {
+        let mut growth = Vec::new();
+        for prefix in self.prefixes.keys() {
+            if growth.len() < prefix.depth() {
+                growth.resize(prefix.depth(), 0);
+            }
+            growth[prefix.depth() - 1] += 1;
+        }
+        growth
+    }
+
+    /// Returns the `n` prefixes with `depth` components holding the most distinct ERNs, largest
+    /// first; ties are broken by prefix order.
+    pub fn top_at(&self, depth: usize, n: usize) -> Vec<(ErnPrefix, usize)> {
+        let mut entries: Vec<_> = self
+            .prefixes_at(depth)
+            .map(|(prefix, count)| (prefix.clone(), count))
+            .collect();
+        entries.sort_by(|(left, left_count), (right, right_count)| {
+            right_count.cmp(left_count).then_with(|| left.cmp(right))
+        });
+        entries.truncate(n);
+        entries
+    }
+
+    /// Returns the `n` categories with the most distinct ERNs, as `ern:domain:category` prefixes.
+    pub fn top_categories(&self, n: usize) -> Vec<(ErnPrefix, usize)> {
+        self.top_at(2, n)
+    }
+
+    /// Returns the `n` accounts with the most distinct ERNs, as `ern:domain:category:account`
+    /// prefixes, so the same account name under different categories is reported separately.
+    pub fn top_accounts(&self, n: usize) -> Vec<(ErnPrefix, usize)> {
+        self.top_at(3, n)
+    }
+}
+
+impl FromIterator<Ern> for ErnStats {
+    fn from_iter<T: IntoIterator<Item = Ern>>(iter: T) -> Self {
+        let mut stats = ErnStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+impl<'a> FromIterator<&'a Ern> for ErnStats {
+    fn from_iter<T: IntoIterator<Item = &'a Ern>>(iter: T) -> Self {
+        let mut stats = ErnStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+impl Extend<Ern> for ErnStats {
+    fn extend<T: IntoIterator<Item = Ern>>(&mut self, iter: T) {
+        for ern in iter {
+            self.insert(&ern);
+        }
+    }
+}
+
+impl<'a> Extend<&'a Ern> for ErnStats {
+    fn extend<T: IntoIterator<Item = &'a Ern>>(&mut self, iter: T) {
+        for ern in iter {
+            self.insert(ern);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> anyhow::Result<Vec<Ern>> {
+        let orders = Ern::with_root("orders")?;
+        let users: Ern = "ern:acton:reactive:tenant-b:users".parse()?;
+        Ok(vec![
+            orders.add_part("eu")?,
+            orders.add_part("us")?,
+            orders.add_part("eu")?.add_part("shard")?,
+            orders.add_part("eu")?.with_revision(3),
+            users.clone(),
+            users.add_part("alice")?,
+        ])
+    }
+
+    #[test]
+    fn test_per_prefix_cardinality() -> anyhow::Result<()> {
+        let erns = registry()?;
+        let stats: ErnStats = erns.iter().collect();
+        assert_eq!(stats.total(), 6);
+        assert_eq!(stats.distinct(), 5);
+        assert_eq!(stats.cardinality(&ErnPrefix::default()), 5);
+        assert_eq!(stats.cardinality(&"acton:reactive".parse()?), 5);
+        assert_eq!(stats.cardinality(&ErnPrefix::of(&erns[0], 4)), 3);
+        assert_eq!(stats.cardinality(&ErnPrefix::of(&erns[0], 5)), 2);
+        assert_eq!(stats.cardinality(&"billing".parse()?), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_growth_by_depth() -> anyhow::Result<()> {
+        let stats = ErnStats::from_iter(registry()?);
+        assert_eq!(stats.growth_by_depth(), vec![1, 1, 2, 2, 3, 1]);
+        assert_eq!(ErnStats::new().growth_by_depth(), Vec::<usize>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn test_top_accounts_and_categories() -> anyhow::Result<()> {
+        let stats = ErnStats::from_iter(registry()?);
+        let accounts: Vec<_> = stats
+            .top_accounts(5)
+            .into_iter()
+            .map(|(prefix, count)| (prefix.to_string(), count))
+            .collect();
+        assert_eq!(
+            accounts,
+            vec![
+                ("ern:acton:reactive:component".to_string(), 3),
+                ("ern:acton:reactive:tenant-b".to_string(), 2)
+            ]
+        );
+        assert_eq!(stats.top_accounts(1).len(), 1);
+        assert_eq!(
+            stats.top_categories(5),
+            vec![("acton:reactive".parse()?, 5)]
+        );
+        Ok(())
+    }
+}
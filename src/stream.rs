@@ -0,0 +1,173 @@
+use crate::config::ErnConfig;
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::parser::ErnParser;
+
+/// The frame length limit used by [`ErnStreamParser::default`].
+pub const DEFAULT_MAX_FRAME_LENGTH: usize = 4096;
+
+/// A push-based parser for newline-delimited ERNs (Entity Resource Names) arriving in arbitrary chunks.
+///
+/// Bytes are buffered until a `\n` completes a frame (a trailing `\r` is ignored), so an ERN split
+/// across network reads is parsed once it is whole. A frame longer than the maximum length is
+/// reported as [`ErnError::LimitExceeded`] and skipped up to the next newline without being buffered.
+#[derive(Debug, Clone)]
+pub struct ErnStreamParser {
+    config: ErnConfig,
+    max_frame_length: usize,
+    buffer: Vec<u8>,
+    /// Set while skipping the rest of a frame that already exceeded the limit.
+    discarding: Option<usize>,
+}
+
+impl Default for ErnStreamParser {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FRAME_LENGTH)
+    }
+}
+
+impl ErnStreamParser {
+    /// Creates a parser that rejects frames longer than `max_frame_length` bytes.
+    pub fn new(max_frame_length: usize) -> Self {
+        Self::with_config(max_frame_length, ErnConfig::default())
+    }
+
+    /// Creates a parser that applies `config` to every frame.
+    pub fn with_config(max_frame_length: usize, config: ErnConfig) -> Self {
+        Self {
+            config,
+            max_frame_length,
+            buffer: Vec::new(),
+            discarding: None,
+        }
+    }
+
+    /// Returns the number of bytes buffered for an incomplete frame.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Consumes a chunk of input and returns a result for every frame it completes, in order.
+    pub fn feed(&mut self, mut chunk: &[u8]) -> Vec<Result<Ern, ErnError>> {
+        let mut results = Vec::new();
+        while !chunk.is_empty() {
+            let newline = chunk.iter().position(|&b| b == b'\n');
+            let (head, rest) = match newline {
+                Some(at) => (&chunk[..at], Some(&chunk[at + 1..])),
+                None => (chunk, None),
+            };
+
+            if let Some(length) = self.discarding.as_mut() {
+                *length += head.len();
+            } else if self.buffer.len() + head.len() > self.max_frame_length {
+                self.discarding = Some(self.buffer.len() + head.len());
+                self.buffer.clear();
+            } else {
+                self.buffer.extend_from_slice(head);
+            }
+
+            match rest {
+                Some(rest) => {
+                    results.push(self.complete_frame());
+                    chunk = rest;
+                }
+                None => break,
+            }
+        }
+        results
+    }
+
+    /// Parses any final frame that was not terminated by a newline, leaving the parser empty.
+    pub fn finish(&mut self) -> Option<Result<Ern, ErnError>> {
+        if self.buffer.is_empty() && self.discarding.is_none() {
+            return None;
+        }
+        Some(self.complete_frame())
+    }
+
+    fn complete_frame(&mut self) -> Result<Ern, ErnError> {
+        if let Some(length) = self.discarding.take() {
+            return Err(ErnError::LimitExceeded(
+                "frame length",
+                self.max_frame_length,
+                length,
+            ));
+        }
+        let frame = std::mem::take(&mut self.buffer);
+        parse_frame(&frame, &self.config)
+    }
+}
+
+/// Parses one frame's bytes, dropping a trailing `\r`.
+pub(crate) fn parse_frame(frame: &[u8], config: &ErnConfig) -> Result<Ern, ErnError> {
+    let frame = frame.strip_suffix(b"\r").unwrap_or(frame);
+    let value =
+        std::str::from_utf8(frame).map_err(|e| ErnError::ParseFailure("frame", e.to_string()))?;
+    ErnParser::new(value.to_string()).parse_with_config(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_split_across_chunks() -> anyhow::Result<()> {
+        let first = Ern::with_root("orders")?.add_part("a")?.to_string();
+        let second = Ern::with_root("users")?.to_string();
+        let input = format!("{first}\n{second}\r\n");
+        let (left, right) = input.as_bytes().split_at(first.len() / 2);
+
+        let mut parser = ErnStreamParser::default();
+        assert!(parser.feed(left).is_empty());
+        assert_eq!(parser.buffered(), left.len());
+        let results = parser.feed(right);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().map(Ern::to_string), Ok(first));
+        assert_eq!(results[1].as_ref().map(Ern::to_string), Ok(second));
+        assert_eq!(parser.buffered(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_oversized_frame_is_skipped() -> anyhow::Result<()> {
+        let valid = Ern::with_root("root")?.to_string();
+        let mut parser = ErnStreamParser::new(valid.len());
+        let oversized = "x".repeat(valid.len() + 10);
+        let mut results = parser.feed(&oversized.as_bytes()[..valid.len() + 5]);
+        assert_eq!(parser.buffered(), 0);
+        results.extend(
+            parser.feed(format!("{}\n{valid}\n", &oversized[valid.len() + 5..]).as_bytes()),
+        );
+        assert_eq!(
+            results[0],
+            Err(ErnError::LimitExceeded(
+                "frame length",
+                valid.len(),
+                oversized.len()
+            ))
+        );
+        assert!(results[1].is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_frame_does_not_stop_the_stream() {
+        let mut parser = ErnStreamParser::default();
+        let results = parser.feed(b"not-an-ern\n\xff\n");
+        assert_eq!(results[0], Err(ErnError::InvalidFormat));
+        assert!(matches!(
+            results[1],
+            Err(ErnError::ParseFailure("frame", _))
+        ));
+    }
+
+    #[test]
+    fn test_finish_parses_unterminated_frame() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?;
+        let mut parser = ErnStreamParser::default();
+        assert!(parser.feed(ern.to_string().as_bytes()).is_empty());
+        assert_eq!(parser.finish(), Some(Ok(ern)));
+        assert_eq!(parser.finish(), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+//! Round-trip and differential checks for code that produces or consumes ERN (Entity Resource Name) strings.
+//!
+//! Enabled with the `test-utils` feature. [`canonicalize`] is an independent, string-level
+//! reference for what the normalizing parser should accept and how its result should display, so
+//! [`roundtrip_check`] catches disagreement between the two as well as `Display`/parse drift.
+
+use crate::config::ErnConfig;
+use crate::model::{EntityRoot, Ern};
+use crate::parser::ErnParser;
+
+/// Returns the canonical text of `input`, or `None` if the parser is expected to reject it
+/// or would have to generate a new root id for it (which can never round-trip).
+///
+/// Components are normalized as with [`ErnConfig::normalize`].
+pub fn canonicalize(input: &str) -> Option<String> {
+    let config = ErnConfig::normalizing();
+    let mut fields = input.trim().splitn(5, ':');
+    let (scheme, domain, category, account, rest) = (
+        config.normalized(fields.next()?),
+        config.normalized(fields.next()?),
+        config.normalized(fields.next()?),
+        config.normalized(fields.next()?),
+        fields.next()?,
+    );
+    if scheme != "ern" || domain.is_empty() {
+        return None;
+    }
+
+    let (root_and_revision, parts) = match rest.split_once('/') {
+        Some((root, parts)) => (root, Some(parts)),
+        None => (rest, None),
+    };
+    let (root, revision) = match root_and_revision.split_once('#') {
+        Some((root, revision)) => (root, Some(canonical_revision(revision)?)),
+        None => (root_and_revision, None),
+    };
+    EntityRoot::parse(root).ok()?;
+
+    let mut canonical = format!("{scheme}:{domain}:{category}:{account}:{root}");
+    if let Some(revision) = revision {
+        canonical.push('#');
+        canonical.push_str(&revision);
+    }
+    if let Some(parts) = parts {
+        for part in parts.split('/').map(|part| config.normalized(part)) {
+            if part.is_empty() || part.contains(':') {
+                return None;
+            }
+            canonical.push('/');
+            canonical.push_str(&part);
+        }
+    }
+    Some(canonical)
+}
+
+fn canonical_revision(revision: &str) -> Option<String> {
+    let digits = revision.strip_prefix('+').unwrap_or(revision);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse::<u64>().ok().map(|value| value.to_string())
+}
+
+/// Asserts that the normalizing parser and [`canonicalize`] agree on `input`, and that a
+/// successful parse survives `parse(Display(ern)) == ern`.
+///
+/// # Panics
+///
+/// Panics with a description of the first invariant that does not hold.
+pub fn roundtrip_check(input: &str) {
+    let parsed = ErnParser::new(input.to_string()).parse_with_config(&ErnConfig::normalizing());
+    match (parsed, canonicalize(input)) {
+        (Ok(ern), Some(expected)) => {
+            assert_eq!(
+                ern.to_string(),
+                expected,
+                "Display(parse({input:?})) is not canonical"
+            );
+            roundtrip_check_ern(&ern);
+        }
+        (Err(_), None) => {}
+        (Ok(ern), None) => {
+            panic!("parser accepted {input:?} as {ern}, but it is not canonicalizable")
+        }
+        (Err(err), Some(expected)) => {
+            panic!("parser rejected {input:?} ({err}), but it canonicalizes to {expected:?}")
+        }
+    }
+}
+
+/// Asserts that `ern` parses back from its `Display` output unchanged.
+///
+/// # Panics
+///
+/// Panics if the displayed form fails to parse or parses to a different value.
+pub fn roundtrip_check_ern(ern: &Ern) {
+    let text = ern.to_string();
+    match ErnParser::new(text.clone()).parse() {
+        Ok(reparsed) => assert_eq!(&reparsed, ern, "parse(Display(ern)) changed {text:?}"),
+        Err(err) => panic!("Display output {text:?} does not parse: {err}"),
+    }
+}
+
+/// Exhaustively enumerates ERN (Entity Resource Name) strings, valid and invalid, over a small catalogue of tricky components.
+///
+/// Every combination of the catalogued domains, categories, accounts, revisions, and part sequences
+/// up to `max_depth` is produced, sharing one freshly generated root id.
+pub struct ErnGenerator {
+    root: String,
+    max_depth: usize,
+    index: usize,
+    total: usize,
+}
+
+const DOMAINS: &[&str] = &["acton", "", "acton-internal", "x.y"];
+const CATEGORIES: &[&str] = &["hr", "", "svc_1", "a/b"];
+const ACCOUNTS: &[&str] = &["company123", "", "Acct"];
+const REVISIONS: &[&str] = &["", "#0", "#7", "#007", "#+3", "#x", "#"];
+const PARTS: &[&str] = &["a", "team-1", "Ünïcode", "", "x:y", "#1"];
+
+impl ErnGenerator {
+    /// Creates a generator producing part sequences of up to `max_depth` parts.
+    pub fn new(max_depth: usize) -> Self {
+        let root = EntityRoot::new("root".to_string())
+            .expect("generating a root id does not fail")
+            .to_string();
+        let sequences: usize = (0..=max_depth)
+            .map(|depth| PARTS.len().pow(depth as u32))
+            .sum();
+        Self {
+            root,
+            max_depth,
+            index: 0,
+            total: DOMAINS.len() * CATEGORIES.len() * ACCOUNTS.len() * REVISIONS.len() * sequences,
+        }
+    }
+
+    fn render(&self, mut index: usize) -> String {
+        let mut take = |len: usize| {
+            let value = index % len;
+            index /= len;
+            value
+        };
+        let domain = DOMAINS[take(DOMAINS.len())];
+        let category = CATEGORIES[take(CATEGORIES.len())];
+        let account = ACCOUNTS[take(ACCOUNTS.len())];
+        let revision = REVISIONS[take(REVISIONS.len())];
+        let mut text = format!("ern:{domain}:{category}:{account}:{}{revision}", self.root);
+
+        // The remaining index selects a part sequence: first by depth, then by position
+        let mut remaining = index;
+        for depth in 0..=self.max_depth {
+            let count = PARTS.len().pow(depth as u32);
+            if remaining < count {
+                for _ in 0..depth {
+                    text.push('/');
+                    text.push_str(PARTS[remaining % PARTS.len()]);
+                    remaining /= PARTS.len();
+                }
+                break;
+            }
+            remaining -= count;
+        }
+        text
+    }
+}
+
+impl Iterator for ErnGenerator {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.total {
+            return None;
+        }
+        let item = self.render(self.index);
+        self.index += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.total - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_normalizes_revision() {
+        let root = EntityRoot::new("root".to_string()).unwrap();
+        assert_eq!(
+            canonicalize(&format!("ern:acton:hr:acct:{root}#007/a")),
+            Some(format!("ern:acton:hr:acct:{root}#7/a"))
+        );
+        assert_eq!(canonicalize("ern:acton:hr:acct:plainroot"), None);
+    }
+
+    #[test]
+    fn test_canonicalize_normalizes_like_the_parser() {
+        let root = EntityRoot::new("root".to_string()).unwrap();
+        let input = format!(" ERN:Acton: HR :Acct:{root}/Ünïcode/ A ");
+        assert_eq!(
+            canonicalize(&input),
+            Some(format!("ern:acton:hr:acct:{root}/ünïcode/a"))
+        );
+        roundtrip_check(&input);
+    }
+
+    #[test]
+    fn test_generator_is_exhaustive_and_unique() {
+        let generated: Vec<_> = ErnGenerator::new(2).collect();
+        let unique: std::collections::HashSet<_> = generated.iter().collect();
+        assert_eq!(generated.len(), unique.len());
+        assert_eq!(generated.len(), 4 * 4 * 3 * 7 * (1 + 6 + 36));
+    }
+
+    #[test]
+    fn test_parser_agrees_with_reference() {
+        for input in ErnGenerator::new(2) {
+            roundtrip_check(&input);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not canonicalizable")]
+    fn test_roundtrip_check_flags_unstable_roots() {
+        roundtrip_check("ern:acton:hr:acct:plainroot");
+    }
+}
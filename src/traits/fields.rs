@@ -0,0 +1,144 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::ErnError;
+use crate::model::{Account, Category, Domain, EntityRoot, Ern, Part, Parts};
+
+/// Names one component of an ERN (Entity Resource Name) for use with [`ErnFields`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ComponentKind {
+    Domain,
+    Category,
+    Account,
+    Root,
+    /// The `/`-separated path after the root.
+    Parts,
+}
+
+impl ComponentKind {
+    /// Every component, in display order.
+    pub const ALL: [ComponentKind; 5] = [
+        ComponentKind::Domain,
+        ComponentKind::Category,
+        ComponentKind::Account,
+        ComponentKind::Root,
+        ComponentKind::Parts,
+    ];
+
+    /// Returns the lowercase component name, e.g. `"domain"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ComponentKind::Domain => "domain",
+            ComponentKind::Category => "category",
+            ComponentKind::Account => "account",
+            ComponentKind::Root => "root",
+            ComponentKind::Parts => "parts",
+        }
+    }
+}
+
+impl fmt::Display for ComponentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Uniform string access to the components of an ERN (Entity Resource Name), for generic tooling.
+pub trait ErnFields {
+    /// Returns the component as it appears in the ERN (Entity Resource Name) string.
+    fn get(&self, kind: ComponentKind) -> Cow<'_, str>;
+
+    /// Replaces the component with `value`, validated as its constructor would.
+    ///
+    /// On error the ERN (Entity Resource Name) is left unchanged.
+    fn set(&mut self, kind: ComponentKind, value: &str) -> Result<(), ErnError>;
+
+    /// Returns every component paired with its kind, in display order.
+    fn fields(&self) -> Vec<(ComponentKind, Cow<'_, str>)> {
+        ComponentKind::ALL
+            .into_iter()
+            .map(|kind| (kind, self.get(kind)))
+            .collect()
+    }
+}
+
+impl ErnFields for Ern {
+    fn get(&self, kind: ComponentKind) -> Cow<'_, str> {
+        match kind {
+            ComponentKind::Domain => Cow::Borrowed(self.domain.as_str()),
+            ComponentKind::Category => Cow::Borrowed(self.category.as_str()),
+            ComponentKind::Account => Cow::Borrowed(self.account.as_str()),
+            ComponentKind::Root => Cow::Borrowed(self.root.as_str()),
+            ComponentKind::Parts => Cow::Owned(self.parts.to_string()),
+        }
+    }
+
+    fn set(&mut self, kind: ComponentKind, value: &str) -> Result<(), ErnError> {
+        let value = value.to_string();
+        match kind {
+            ComponentKind::Domain => self.domain = Domain::new(value)?,
+            ComponentKind::Category => self.category = Category::new(value),
+            ComponentKind::Account => self.account = Account::new(value),
+            // Keep an existing root id intact; plain names get a freshly generated id, as when parsing.
+            ComponentKind::Root => {
+                self.root = match EntityRoot::parse(&value) {
+                    Ok(root) => root,
+                    Err(_) => EntityRoot::from_str(&value)?,
+                }
+            }
+            ComponentKind::Parts => {
+                let parts = if value.is_empty() {
+                    Vec::new()
+                } else {
+                    value
+                        .split('/')
+                        .map(|part| Part::new(part.to_string()))
+                        .collect::<Result<_, _>>()?
+                };
+                self.parts = Parts::new(parts);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_every_component() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.with_parts(["a", "b"])?;
+        let fields = ern.fields();
+        assert_eq!(fields[0], (ComponentKind::Domain, Cow::Borrowed("acton")));
+        assert_eq!(fields[3].1, ern.root.as_str());
+        assert_eq!(fields[4].1, "a/b");
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_components() -> anyhow::Result<()> {
+        let mut ern = Ern::with_root("root")?;
+        let root = ern.root.clone();
+        ern.set(ComponentKind::Account, "tenant9")?;
+        ern.set(ComponentKind::Parts, "x/y/z")?;
+        ern.set(ComponentKind::Root, root.as_str())?;
+        assert_eq!(ern.get(ComponentKind::Account), "tenant9");
+        assert_eq!(ern.parts.len(), 3);
+        assert_eq!(ern.root, root);
+        ern.set(ComponentKind::Parts, "")?;
+        assert!(ern.parts.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_set_leaves_ern_unchanged() -> anyhow::Result<()> {
+        let mut ern = Ern::with_root("root")?.with_parts(["a"])?;
+        let before = ern.clone();
+        assert!(ern.set(ComponentKind::Domain, "").is_err());
+        assert!(ern.set(ComponentKind::Parts, "ok/bad:part").is_err());
+        assert_eq!(ern, before);
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::mem::size_of;
+
+use crate::{Account, Category, Domain, EntityRoot, Ern, Part, Parts};
+
+/// Reports the heap memory owned by a value, excluding the value itself.
+///
+/// Figures are based on allocated capacity where it is observable and on length otherwise,
+/// so they are a close estimate suitable for capacity planning rather than an exact count.
+pub trait HeapSize {
+    /// Returns the number of heap bytes owned by this value.
+    fn heap_size(&self) -> usize;
+
+    /// Returns the inline size of the value plus its heap bytes.
+    fn total_size(&self) -> usize
+    where
+        Self: Sized,
+    {
+        size_of::<Self>() + self.heap_size()
+    }
+}
+
+impl HeapSize for Cow<'static, str> {
+    fn heap_size(&self) -> usize {
+        match self {
+            Cow::Borrowed(_) => 0,
+            Cow::Owned(value) => value.capacity(),
+        }
+    }
+}
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+macro_rules! impl_heap_size_for_component {
+    ($($type:ty),*) => {
+        $(
+            impl HeapSize for $type {
+                fn heap_size(&self) -> usize {
+                    self.0.heap_size()
+                }
+            }
+        )*
+    };
+}
+
+impl_heap_size_for_component!(Domain, Category, Account, Part);
+
+impl HeapSize for EntityRoot {
+    fn heap_size(&self) -> usize {
+        // The id keeps its prefix and its rendered string; capacities are not exposed, so use lengths
+        self.base_name().len() + self.as_str().len()
+    }
+}
+
+impl HeapSize for Parts {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size()
+    }
+}
+
+impl HeapSize for Ern {
+    fn heap_size(&self) -> usize {
+        self.domain.heap_size()
+            + self.category.heap_size()
+            + self.account.heap_size()
+            + self.root.heap_size()
+            + self.parts.heap_size()
+    }
+}
+
+impl<T: HeapSize> HeapSize for [T] {
+    fn heap_size(&self) -> usize {
+        self.iter().map(HeapSize::heap_size).sum()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.as_slice().heap_size()
+    }
+}
+
+impl<T: HeapSize, S> HeapSize for HashSet<T, S> {
+    fn heap_size(&self) -> usize {
+        // Table overhead (control bytes, load factor) is not included
+        self.capacity() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<K: HeapSize, V: HeapSize, S> HeapSize for HashMap<K, V, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(key, value)| key.heap_size() + value.heap_size())
+                .sum::<usize>()
+    }
+}
+
+impl<K: HeapSize, V: HeapSize> HeapSize for BTreeMap<K, V> {
+    fn heap_size(&self) -> usize {
+        self.len() * size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(key, value)| key.heap_size() + value.heap_size())
+                .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrowed_components_use_no_heap() {
+        assert_eq!(Domain::default().heap_size(), 0);
+        assert_eq!(Category::from_cow("static").heap_size(), 0);
+    }
+
+    #[test]
+    fn test_owned_components_report_capacity() {
+        let account = Account::new(String::with_capacity(64));
+        assert_eq!(account.heap_size(), 64);
+    }
+
+    #[test]
+    fn test_ern_heap_size_grows_with_parts() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?;
+        let deeper = ern.with_parts(["alpha".to_string(), "beta".to_string()])?;
+        assert!(ern.heap_size() >= ern.root.as_str().len());
+        assert!(deeper.heap_size() >= ern.heap_size() + 2 * size_of::<Part>() + 9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_heap_size() -> anyhow::Result<()> {
+        let erns = vec![Ern::with_root("a")?, Ern::with_root("b")?];
+        let expected =
+            erns.capacity() * size_of::<Ern>() + erns[0].heap_size() + erns[1].heap_size();
+        assert_eq!(erns.heap_size(), expected);
+        Ok(())
+    }
+}
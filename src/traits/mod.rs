@@ -1,4 +1,7 @@
-pub use ern_component::ErnComponent;
-
-mod ern_component;
-
+pub use ern_component::ErnComponent;
+pub use fields::{ComponentKind, ErnFields};
+pub use heap_size::HeapSize;
+
+mod ern_component;
+mod fields;
+mod heap_size;
@@ -0,0 +1,207 @@
+use crate::errors::ErnError;
+use crate::model::Ern;
+use crate::wire::ErnWire;
+
+/// Maps ERNs (Entity Resource Names) to fetchable URLs and back.
+///
+/// Implementors only choose the base URL; the built-in mappers place the resource at
+/// `<base>/<domain>/<category>/<account>/<root>/<parts…>` with each segment percent-encoded.
+pub trait UrlMapper {
+    /// Returns the base URL, without a trailing slash, that resource paths are appended to.
+    fn base_url(&self) -> String;
+
+    /// Returns the URL for `ern`.
+    fn to_url(&self, ern: &Ern) -> String {
+        let wire = ErnWire::from(ern);
+        let mut url = self.base_url();
+        for segment in wire.segments() {
+            url.push('/');
+            percent_encode(segment, &mut url);
+        }
+        url
+    }
+
+    /// Recovers the ERN (Entity Resource Name) from a URL produced by [`UrlMapper::to_url`].
+    ///
+    /// Any query string or fragment, such as a presigned URL's signature, is ignored.
+    fn parse_url(&self, url: &str) -> Result<Ern, ErnError> {
+        let url = url.split(['?', '#']).next().unwrap_or_default();
+        let base = self.base_url();
+        let path = url
+            .strip_prefix(base.as_str())
+            .and_then(|path| path.strip_prefix('/'))
+            .ok_or_else(|| {
+                ErnError::parse_failure("url", format!("`{}` is not under `{}`", url, base))
+            })?;
+        let segments = path
+            .split('/')
+            .map(|segment| {
+                percent_decode(segment).ok_or_else(|| {
+                    ErnError::parse_failure(
+                        "url",
+                        format!("`{segment}` is not valid percent-encoded UTF-8"),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ern::try_from(ErnWire::from_segments("url", segments)?)
+    }
+}
+
+/// Serves resources from beneath a fixed HTTPS (or any other) URL prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixUrlMapper {
+    prefix: String,
+}
+
+impl PrefixUrlMapper {
+    pub fn new(prefix: impl Into<String>) -> Self {
+        let mut prefix = prefix.into();
+        while prefix.ends_with('/') {
+            prefix.pop();
+        }
+        Self { prefix }
+    }
+}
+
+impl UrlMapper for PrefixUrlMapper {
+    fn base_url(&self) -> String {
+        self.prefix.clone()
+    }
+}
+
+/// Serves resources from an S3 bucket using virtual-hosted–style URLs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3UrlMapper {
+    pub bucket: String,
+    pub region: String,
+}
+
+impl S3UrlMapper {
+    pub fn new(bucket: impl Into<String>, region: impl Into<String>) -> Self {
+        Self {
+            bucket: bucket.into(),
+            region: region.into(),
+        }
+    }
+}
+
+impl UrlMapper for S3UrlMapper {
+    fn base_url(&self) -> String {
+        format!("https://{}.s3.{}.amazonaws.com", self.bucket, self.region)
+    }
+}
+
+/// Serves resources from a Google Cloud Storage bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GcsUrlMapper {
+    pub bucket: String,
+}
+
+impl GcsUrlMapper {
+    pub fn new(bucket: impl Into<String>) -> Self {
+        Self {
+            bucket: bucket.into(),
+        }
+    }
+}
+
+impl UrlMapper for GcsUrlMapper {
+    fn base_url(&self) -> String {
+        format!("https://storage.googleapis.com/{}", self.bucket)
+    }
+}
+
+fn percent_encode(segment: &str, out: &mut String) {
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+}
+
+/// Decodes `%XX` escapes, each of which must be followed by exactly two ASCII hex digits, and
+/// returns `None` if an escape is malformed or the result is not UTF-8.
+pub(crate) fn percent_decode(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let high = hex_digit(*bytes.get(index + 1)?)?;
+            let low = hex_digit(*bytes.get(index + 2)?)?;
+            decoded.push(high << 4 | low);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+fn hex_digit(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_mappers_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("assets")?.with_parts(["logo v2.png"])?;
+        let mappers: [Box<dyn UrlMapper>; 3] = [
+            Box::new(PrefixUrlMapper::new("https://cdn.example.com/static/")),
+            Box::new(S3UrlMapper::new("acton-assets", "us-east-1")),
+            Box::new(GcsUrlMapper::new("acton-assets")),
+        ];
+        for mapper in &mappers {
+            let url = mapper.to_url(&ern);
+            assert!(url.ends_with("/logo%20v2.png"), "{url}");
+            assert_eq!(mapper.parse_url(&url)?, ern);
+            assert_eq!(
+                mapper.parse_url(&format!("{url}?X-Amz-Signature=abc"))?,
+                ern
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_base_urls() {
+        assert_eq!(
+            S3UrlMapper::new("b", "eu-west-1").base_url(),
+            "https://b.s3.eu-west-1.amazonaws.com"
+        );
+        assert_eq!(
+            GcsUrlMapper::new("b").base_url(),
+            "https://storage.googleapis.com/b"
+        );
+        assert_eq!(PrefixUrlMapper::new("https://x/").base_url(), "https://x");
+    }
+
+    #[test]
+    fn test_rejects_foreign_urls() {
+        let mapper = GcsUrlMapper::new("acton-assets");
+        assert!(mapper
+            .parse_url("https://storage.googleapis.com/other/acton/reactive/component/x")
+            .is_err());
+        assert!(mapper
+            .parse_url("https://storage.googleapis.com/acton-assets/acton/reactive")
+            .is_err());
+        assert!(mapper
+            .parse_url("https://storage.googleapis.com/acton-assets/a/b/c/%zz")
+            .is_err());
+        assert!(mapper
+            .parse_url("https://storage.googleapis.com/acton-assets/a/b/c/%+1")
+            .is_err());
+    }
+}
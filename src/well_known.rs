@@ -0,0 +1,106 @@
+use std::sync::OnceLock;
+
+use crate::errors::ErnError;
+use crate::model::{Account, Category, Domain, EntityRoot, Ern, Part, Parts};
+
+/// The domain of every framework-standard ERN (Entity Resource Name).
+pub const SYSTEM_DOMAIN: Domain = Domain::from_static("acton");
+/// The category of every framework-standard ERN.
+pub const SYSTEM_CATEGORY: Category = Category::from_static("system");
+/// The account of every framework-standard ERN.
+pub const SYSTEM_ACCOUNT: Account = Account::from_static("framework");
+
+/// The root name of the system supervisor.
+pub const SUPERVISOR_ROOT: &str = "supervisor";
+/// The root name of the dead-letter queue.
+pub const DEAD_LETTER_ROOT: &str = "dead_letter";
+/// The root name of the message broker.
+pub const BROKER_ROOT: &str = "broker";
+
+/// Returns the ERN (Entity Resource Name) of the system supervisor.
+pub fn system_supervisor() -> &'static Ern {
+    static ERN: OnceLock<Ern> = OnceLock::new();
+    ERN.get_or_init(|| system(SUPERVISOR_ROOT))
+}
+
+/// Returns the ERN (Entity Resource Name) of the dead-letter queue.
+pub fn dead_letter_queue() -> &'static Ern {
+    static ERN: OnceLock<Ern> = OnceLock::new();
+    ERN.get_or_init(|| system(DEAD_LETTER_ROOT))
+}
+
+/// Returns the ERN (Entity Resource Name) of the message broker's root.
+pub fn broker_root() -> &'static Ern {
+    static ERN: OnceLock<Ern> = OnceLock::new();
+    ERN.get_or_init(|| system(BROKER_ROOT))
+}
+
+/// Builds a framework-standard ERN (Entity Resource Name) for another system service, e.g. `system_ern("scheduler", ["timers"])`.
+///
+/// Like the built-in services, its root id is derived from the root name instead of generated,
+/// so every process constructs the same ERN. Fails if a part is invalid.
+pub fn system_ern<I, S>(root: &str, parts: I) -> Result<Ern, ErnError>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let parts = parts
+        .into_iter()
+        .map(|part| Part::new(part.into()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Ern {
+        parts: Parts::new(parts),
+        ..system(root)
+    })
+}
+
+/// Returns true if `ern` uses the framework's system domain, category, and account.
+pub fn is_system_ern(ern: &Ern) -> bool {
+    ern.domain == SYSTEM_DOMAIN && ern.category == SYSTEM_CATEGORY && ern.account == SYSTEM_ACCOUNT
+}
+
+fn system(root: &str) -> Ern {
+    let key = format!("{SYSTEM_DOMAIN}:{SYSTEM_CATEGORY}:{SYSTEM_ACCOUNT}:{root}");
+    Ern {
+        domain: SYSTEM_DOMAIN,
+        category: SYSTEM_CATEGORY,
+        account: SYSTEM_ACCOUNT,
+        root: EntityRoot::derived(root, key.as_bytes()),
+        ..Ern::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ErnParser;
+
+    #[test]
+    fn test_well_known_erns_are_stable() -> anyhow::Result<()> {
+        let supervisor = system_supervisor();
+        assert_eq!(supervisor, &system(SUPERVISOR_ROOT));
+        assert!(supervisor
+            .to_string()
+            .starts_with("ern:acton:system:framework:supervisor_"));
+        assert_eq!(&ErnParser::new(supervisor.to_string()).parse()?, supervisor);
+        let dead_letter = dead_letter_queue();
+        assert_eq!(dead_letter.root.base_name(), DEAD_LETTER_ROOT);
+        assert_eq!(
+            &ErnParser::new(dead_letter.to_string()).parse()?,
+            dead_letter
+        );
+        assert_ne!(dead_letter.root, broker_root().root);
+        Ok(())
+    }
+
+    #[test]
+    fn test_system_ern() -> anyhow::Result<()> {
+        let timers = system_ern("scheduler", ["timers"])?;
+        assert_eq!(timers, system_ern("scheduler", ["timers"])?);
+        assert_eq!(timers.parts.to_string(), "timers");
+        assert!(is_system_ern(&timers));
+        assert!(!is_system_ern(&Ern::with_root("scheduler")?));
+        assert!(system_ern("scheduler", [""]).is_err());
+        Ok(())
+    }
+}
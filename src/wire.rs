@@ -0,0 +1,146 @@
+use crate::errors::ErnError;
+use crate::model::{Account, Category, Domain, EntityRoot, Ern, Part, Parts};
+
+/// The layout version written by [`ErnWire::from`] and the only one [`Ern::try_from`] accepts.
+pub const ERN_WIRE_VERSION: u8 = 1;
+
+/// A fixed-layout representation of an ERN (Entity Resource Name) for compact binary formats such as postcard or bincode.
+///
+/// Fields are serialized in declaration order, which is part of the layout and will not change
+/// within a version. The root is carried verbatim, so decoding never generates a new id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErnWire {
+    /// The layout version, always first so decoders can reject layouts they do not understand.
+    pub version: u8,
+    pub domain: String,
+    pub category: String,
+    pub account: String,
+    pub root: String,
+    pub revision: Option<u64>,
+    pub parts: Vec<String>,
+}
+
+impl ErnWire {
+    /// Returns the components in hierarchy order: domain, category, account, root, then each part.
+    pub(crate) fn segments(&self) -> impl Iterator<Item = &str> {
+        [&self.domain, &self.category, &self.account, &self.root]
+            .into_iter()
+            .chain(&self.parts)
+            .map(String::as_str)
+    }
+
+    /// Rebuilds a wire value from components in the order produced by [`ErnWire::segments`].
+    ///
+    /// Missing components are reported as a [`ErnError::ParseFailure`] of `format`.
+    pub(crate) fn from_segments(
+        format: &'static str,
+        segments: impl IntoIterator<Item = String>,
+    ) -> Result<Self, ErnError> {
+        let mut segments = segments.into_iter();
+        let mut next = |name: &str| {
+            segments
+                .next()
+                .ok_or_else(|| ErnError::ParseFailure(format, format!("missing {}", name)))
+        };
+        Ok(Self {
+            version: ERN_WIRE_VERSION,
+            domain: next("domain")?,
+            category: next("category")?,
+            account: next("account")?,
+            root: next("root")?,
+            revision: None,
+            parts: segments.collect(),
+        })
+    }
+}
+
+impl From<&Ern> for ErnWire {
+    fn from(ern: &Ern) -> Self {
+        Self {
+            version: ERN_WIRE_VERSION,
+            domain: ern.domain.to_string(),
+            category: ern.category.to_string(),
+            account: ern.account.to_string(),
+            root: ern.root.to_string(),
+            revision: ern.revision(),
+            parts: (&ern.parts)
+                .into_iter()
+                .map(|part| part.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl From<Ern> for ErnWire {
+    fn from(ern: Ern) -> Self {
+        Self::from(&ern)
+    }
+}
+
+impl TryFrom<ErnWire> for Ern {
+    type Error = ErnError;
+
+    fn try_from(wire: ErnWire) -> Result<Self, Self::Error> {
+        if wire.version != ERN_WIRE_VERSION {
+            return Err(ErnError::UnsupportedWireVersion(wire.version));
+        }
+        let parts = wire
+            .parts
+            .into_iter()
+            .map(Part::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        let ern = Ern::new(
+            Domain::new(wire.domain)?,
+            Category::new(wire.category),
+            Account::new(wire.account),
+            EntityRoot::parse(&wire.root)?,
+            Parts::new(parts),
+        );
+        Ok(match wire.revision {
+            Some(revision) => ern.with_revision(revision),
+            None => ern,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wire_roundtrip() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?
+            .with_parts(["a", "b"])?
+            .with_revision(4);
+        let wire = ErnWire::from(&ern);
+        assert_eq!(wire.version, ERN_WIRE_VERSION);
+        assert_eq!(wire.parts, vec!["a", "b"]);
+        assert_eq!(Ern::try_from(wire)?, ern);
+        Ok(())
+    }
+
+    #[test]
+    fn test_wire_rejects_unknown_version() -> anyhow::Result<()> {
+        let wire = ErnWire {
+            version: 9,
+            ..ErnWire::from(Ern::with_root("root")?)
+        };
+        assert_eq!(
+            Ern::try_from(wire),
+            Err(ErnError::UnsupportedWireVersion(9))
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_wire_postcard_layout() -> anyhow::Result<()> {
+        let ern = Ern::with_root("root")?.add_part("a")?;
+        let bytes = postcard::to_allocvec(&ErnWire::from(&ern))?;
+        assert_eq!(bytes[0], ERN_WIRE_VERSION);
+        let decoded: ErnWire = postcard::from_bytes(&bytes)?;
+        assert_eq!(Ern::try_from(decoded)?, ern);
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+//! Loads configuration containing ERNs (Entity Resource Names) from YAML manifests.
+//!
+//! Enabled with the `yaml` feature. Mark ERN fields with
+//! `#[serde(with = "acton_ern::serde_ern")]` (or `acton_ern::serde_ern::option`) and load the
+//! file with [`from_str`] or [`from_reader`]; a malformed value is reported with its YAML path and
+//! location, e.g. ``services[1].owner: invalid ERN `ern:acton`: Ern has invalid format at line 4 column 5``.
+
+use std::fmt;
+use std::io;
+
+use serde::de::DeserializeOwned;
+
+/// A YAML configuration error locating the value that failed to deserialize.
+#[derive(Debug)]
+pub struct YamlError {
+    path: String,
+    line: Option<usize>,
+    column: Option<usize>,
+    message: String,
+}
+
+impl YamlError {
+    fn new(error: serde_path_to_error::Error<serde_yaml::Error>) -> Self {
+        let path = error.path().to_string();
+        let error = error.into_inner();
+        let location = error.location();
+        let mut message = error.to_string();
+        // serde_yaml decorates its message with its own, shorter path and the location; both are
+        // reported separately here
+        if let Some(location) = &location {
+            let suffix = format!(" at line {} column {}", location.line(), location.column());
+            if message.ends_with(&suffix) {
+                message.truncate(message.len() - suffix.len());
+            }
+        }
+        if let Some((prefix, rest)) = message.split_once(": ") {
+            if path.starts_with(prefix) {
+                message = rest.to_string();
+            }
+        }
+        Self {
+            path,
+            line: location.as_ref().map(|location| location.line()),
+            column: location.as_ref().map(|location| location.column()),
+            message,
+        }
+    }
+
+    /// Returns the dotted path to the offending value, e.g. `services[1].owner`, or `.` for the document itself.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the 1-based line of the offending value, or of the entry enclosing it, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Returns the 1-based column matching [`YamlError::line`], if known.
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
+    /// Returns the underlying error message, without the path or location.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for YamlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)?;
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, " at line {line} column {column}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for YamlError {}
+
+/// Deserializes a configuration value from a YAML string.
+pub fn from_str<T: DeserializeOwned>(input: &str) -> Result<T, YamlError> {
+    serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(input))
+        .map_err(YamlError::new)
+}
+
+/// Deserializes a configuration value from a YAML reader such as an open file.
+pub fn from_reader<R: io::Read, T: DeserializeOwned>(reader: R) -> Result<T, YamlError> {
+    serde_path_to_error::deserialize(serde_yaml::Deserializer::from_reader(reader))
+        .map_err(YamlError::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::model::Ern;
+
+    #[derive(Debug, Deserialize)]
+    struct Manifest {
+        services: Vec<Service>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Service {
+        name: String,
+        #[serde(with = "crate::serde_ern")]
+        owner: Ern,
+        #[serde(default, with = "crate::serde_ern::option")]
+        parent: Option<Ern>,
+    }
+
+    #[test]
+    fn test_loads_erns_from_yaml() -> anyhow::Result<()> {
+        let owner = Ern::with_root("orders")?.add_part("eu")?;
+        let yaml = format!("services:\n  - name: orders\n    owner: \"{owner}\"\n");
+        let manifest: Manifest = from_str(&yaml)?;
+        assert_eq!(manifest.services[0].name, "orders");
+        assert_eq!(manifest.services[0].owner, owner);
+        assert_eq!(manifest.services[0].parent, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_reports_yaml_path_and_line() -> anyhow::Result<()> {
+        let owner = Ern::with_root("orders")?;
+        let yaml = format!(
+            "services:\n  - name: orders\n    owner: \"{owner}\"\n  - name: billing\n    owner: \"{owner}\"\n    parent: ern:acton\n"
+        );
+        let error = from_str::<Manifest>(&yaml).unwrap_err();
+        assert_eq!(error.path(), "services[1].parent");
+        assert_eq!(
+            error.message(),
+            "invalid ERN `ern:acton`: Ern has invalid format"
+        );
+        assert_eq!(
+            error.to_string(),
+            "services[1].parent: invalid ERN `ern:acton`: Ern has invalid format at line 4 column 5"
+        );
+        Ok(())
+    }
+}
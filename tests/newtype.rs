@@ -0,0 +1,52 @@
+#![cfg(feature = "derive")]
+
+use std::str::FromStr;
+
+use acton_ern::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, ErnNewtype)]
+#[ern(domain = "acton", category = "orders")]
+struct OrderErn(Ern);
+
+#[derive(Debug, ErnNewtype)]
+struct AnyErn(Ern);
+
+#[test]
+fn test_derived_newtype_roundtrips() -> anyhow::Result<()> {
+    let order = OrderErn::from_str("ern:acton:orders:tenant9:order1/line2")?;
+    let reparsed: OrderErn = order.to_string().parse()?;
+    assert_eq!(order, reparsed);
+    assert_eq!(Ern::from(order).parts.to_string(), "line2");
+    Ok(())
+}
+
+#[test]
+fn test_derived_newtype_enforces_constraints() {
+    assert_eq!(
+        OrderErn::from_str("ern:acton:invoices:tenant9:order1").err(),
+        Some(ErnError::UnexpectedComponent(
+            "category",
+            "orders",
+            "invoices".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_unconstrained_newtype_accepts_any_ern() -> anyhow::Result<()> {
+    let ern = Ern::with_root("root")?;
+    let wrapped = AnyErn::try_from(ern.clone())?;
+    assert_eq!(wrapped.as_ref(), &ern);
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_derived_newtype_serde_validates() -> anyhow::Result<()> {
+    let order = OrderErn::from_str("ern:acton:orders:tenant9:order1")?;
+    let json = serde_json::to_string(&order)?;
+    assert_eq!(json, format!("\"{order}\""));
+    assert_eq!(serde_json::from_str::<OrderErn>(&json)?, order);
+    assert!(serde_json::from_str::<OrderErn>("\"ern:acton:invoices:tenant9:order1\"").is_err());
+    Ok(())
+}
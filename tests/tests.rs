@@ -1,5 +1,5 @@
-use acton_ern::EntityRoot;
 use acton_ern::prelude::*;
+use acton_ern::EntityRoot;
 
 //
 // /// Tests for the Acton Ern implementation
@@ -7,12 +7,12 @@ use acton_ern::prelude::*;
 fn test() -> anyhow::Result<()> {
     // Create an ERN (Entity Resource Name) using the ErnBuilder with specified components
     let ern: Result<Ern, ErnError> = ErnBuilder::new()
-        .with::<Domain>("acton-internal".into())?
-        .with::<Category>("hr".into())?
-        .with::<Account>("company123".into())?
-        .with::<EntityRoot>("root".into())?
-        .with::<Part>("departmentA".into())?
-        .with::<Part>("team1".into())?
+        .with::<Domain>("acton-internal")?
+        .with::<Category>("hr")?
+        .with::<Account>("company123")?
+        .with::<EntityRoot>("root")?
+        .with::<Part>("departmentA")?
+        .with::<Part>("team1")?
         .build();
 
     // Verify the constructed ERN (Entity Resource Name) matches the expected value
@@ -123,3 +123,216 @@ fn test() -> anyhow::Result<()> {
 //     );
 //     Ok(())
 // }
+
+#[test]
+fn test_parse_roundtrip() -> anyhow::Result<()> {
+    let ern = Ern::with_root("root")?.with_parts(["departmentA", "team1"])?;
+    let parsed = ErnParser::new(ern.to_string()).parse()?;
+    assert_eq!(parsed, ern);
+    Ok(())
+}
+
+#[test]
+fn test_from_str_and_try_from() -> anyhow::Result<()> {
+    let ern = Ern::with_root("root")?.with_parts(["departmentA", "team1"])?;
+    let text = ern.to_string();
+    assert_eq!(text.parse::<Ern>()?, ern);
+    assert_eq!(Ern::try_from(text.as_str())?, ern);
+    assert_eq!(Ern::try_from(text)?, ern);
+    assert_eq!(
+        "urn:acton:hr:acct:root".parse::<Ern>(),
+        Err(ErnError::InvalidFormat)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_revision_display_and_parse() -> anyhow::Result<()> {
+    let ern = Ern::with_root("root")?.add_part("orders")?.with_revision(7);
+    let text = ern.to_string();
+    assert!(text.contains("#7/orders"));
+
+    let parsed = ErnParser::new(text).parse()?;
+    assert_eq!(parsed.revision(), Some(7));
+    assert_eq!(parsed, ern);
+    Ok(())
+}
+
+#[test]
+fn test_revision_bump_and_ordering() -> anyhow::Result<()> {
+    let ern = Ern::with_root("root")?;
+    assert_eq!(ern.revision(), None);
+
+    let first = ern.bump_revision()?;
+    let second = first.bump_revision()?;
+    assert_eq!(first.revision(), Some(1));
+    assert_eq!(second.revision(), Some(2));
+    assert!(ern < first && first < second);
+
+    assert_eq!(
+        ern.with_revision(u64::MAX).bump_revision(),
+        Err(ErnError::RevisionOverflow)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_ordering_is_consistent_with_equality() -> anyhow::Result<()> {
+    let ern = Ern::with_root("root")?.with_parts(["a", "b"])?;
+    let mut other_account = ern.clone();
+    other_account.account = Account::new("other");
+    let variants = [
+        ern.clone(),
+        ern.with_parts(["a", "c"])?,
+        ern.with_parts(["a"])?,
+        ern.with_revision(1),
+        other_account,
+    ];
+    for a in &variants {
+        for b in &variants {
+            assert_eq!(a.cmp(b) == std::cmp::Ordering::Equal, a == b, "{a} vs {b}");
+        }
+    }
+    let set: std::collections::BTreeSet<_> = variants.iter().cloned().collect();
+    assert_eq!(set.len(), variants.len());
+
+    let Ern {
+        domain,
+        category,
+        account,
+        root,
+        parts,
+        revision,
+    } = ern.with_revision(3);
+    let rebuilt = Ern {
+        domain,
+        category,
+        account,
+        root,
+        parts,
+        revision,
+    };
+    assert_eq!(rebuilt, ern.with_revision(3));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_revision_is_rejected() {
+    let result = ErnParser::new("ern:acton:hr:acct:root#seven".to_string()).parse();
+    assert!(matches!(result, Err(ErnError::ParseFailure("revision", _))));
+}
+
+#[test]
+fn test_builder_accepts_owned_and_static_strings() -> anyhow::Result<()> {
+    let account = format!("company{}", 123);
+    let ern = ErnBuilder::new()
+        .with::<Domain>("acton-internal")?
+        .with::<Category>(String::from("hr"))?
+        .with::<Account>(account)?
+        .with::<EntityRoot>("root")?
+        .build()?;
+    assert_eq!(ern.account.as_str(), "company123");
+    assert_eq!(ern.category.as_str(), "hr");
+    Ok(())
+}
+
+#[test]
+fn test_parse_with_config() -> anyhow::Result<()> {
+    let config = ErnConfig {
+        scheme: "urn".into(),
+        normalize: true,
+        validation: ValidationConfig {
+            max_depth: Some(2),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let ern = Ern::parse_with_config(" URN:Acton:HR:Company123:root/Team1 ", &config)?;
+    assert_eq!(ern.domain.as_str(), "acton");
+    assert_eq!(ern.category.as_str(), "hr");
+    assert_eq!(ern.account.as_str(), "company123");
+    assert_eq!(ern.parts.to_string(), "team1");
+
+    assert_eq!(
+        Ern::parse_with_config("ern:acton:hr:company123:root", &config),
+        Err(ErnError::InvalidFormat)
+    );
+    assert_eq!(
+        Ern::parse_with_config("urn:acton:hr:company123:root/a/b/c", &config),
+        Err(ErnError::LimitExceeded("depth", 2, 3))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parse_with_strict_config() {
+    let result = Ern::parse_with_config("ern:acton:hr:company 123:root", &ErnConfig::strict());
+    assert_eq!(result, Err(ErnError::InvalidCharacter("account", ' ')));
+}
+
+#[test]
+fn test_static_component_table() -> anyhow::Result<()> {
+    const DOMAIN: Domain = Domain::from_static("acton");
+    const CATEGORY: Category = Category::from_static("orders");
+    const PARTS: [Part; 2] = [Part::from_static("region1"), Part::from_static("shard2")];
+
+    let ern = Ern::new(
+        DOMAIN,
+        CATEGORY,
+        Account::from_static("tenant9"),
+        EntityRoot::new("root".to_string())?,
+        Parts::new(PARTS.to_vec()),
+    );
+    assert!(ern
+        .to_string()
+        .starts_with("ern:acton:orders:tenant9:root_"));
+    assert!(ern.to_string().ends_with("/region1/shard2"));
+    Ok(())
+}
+
+#[test]
+fn test_hash_is_structural() -> anyhow::Result<()> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let hash = |ern: &Ern| {
+        let mut hasher = DefaultHasher::new();
+        ern.hash(&mut hasher);
+        hasher.finish()
+    };
+    let ern = Ern::with_root("root")?;
+    assert_eq!(
+        hash(&ern.with_parts(["a", "bc"])?),
+        hash(&ern.with_parts(["a", "bc"])?)
+    );
+    assert_ne!(
+        hash(&ern.with_parts(["a", "bc"])?),
+        hash(&ern.with_parts(["ab", "c"])?)
+    );
+    assert_ne!(hash(&ern), hash(&ern.bump_revision()?));
+    Ok(())
+}
+
+#[test]
+fn test_components_convert_from_owned_strings() {
+    assert_eq!(Domain::from(String::from("billing")).as_str(), "billing");
+    assert_eq!(Account::from(String::from("tenant-1")).as_str(), "tenant-1");
+}
+
+#[test]
+fn test_equality_compares_every_component() -> anyhow::Result<()> {
+    let ern = Ern::with_root("root")?.with_parts(["a", "b"])?;
+    assert_eq!(ern, ern.clone());
+    assert_ne!(ern, ern.with_parts(["a", "c"])?);
+    assert_ne!(ern, ern.with_parts(["a"])?);
+    assert_ne!(ern, ern.with_revision(1));
+    let mut other = ern.clone();
+    other.account = Account::new("other");
+    assert_ne!(ern, other);
+    let mut other = ern.clone();
+    other.domain = Domain::new("other")?;
+    assert_ne!(ern, other);
+    assert_ne!(ern, Ern::with_root("root")?.with_parts(["a", "b"])?);
+    Ok(())
+}